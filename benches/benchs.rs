@@ -5,7 +5,7 @@ extern crate rand;
 extern crate rand_pcg;
 extern crate test;
 
-use evalexpr::build_operator_tree;
+use evalexpr::{build_operator_tree, EmptyContext};
 use rand::{distributions::Uniform, seq::SliceRandom, Rng, SeedableRng};
 use rand_pcg::Pcg32;
 use std::{fmt::Write, hint::black_box};
@@ -13,6 +13,8 @@ use test::Bencher;
 
 const BENCHMARK_LEN: usize = 100_000;
 const EXPONENTIAL_TUPLE_ITERATIONS: usize = 12;
+// Each level adds two levels to the operator tree, which must stay below `DEFAULT_MAX_EVALUATION_DEPTH`.
+const NESTED_EXPRESSION_DEPTH: usize = 500;
 
 fn generate_expression<Gen: Rng>(len: usize, gen: &mut Gen) -> String {
     let int_distribution = Uniform::new_inclusive(1, 100);
@@ -22,15 +24,40 @@ fn generate_expression<Gen: Rng>(len: usize, gen: &mut Gen) -> String {
     write!(result, "{}", gen.sample(int_distribution)).unwrap();
 
     while result.len() < len {
+        let operator = operators.choose(gen).unwrap();
         result.push_str(whitespaces.choose(gen).unwrap());
-        result.push_str(operators.choose(gen).unwrap());
+        result.push_str(operator);
         result.push_str(whitespaces.choose(gen).unwrap());
         write!(result, "{}", gen.sample(int_distribution)).unwrap();
+        // Integer exponentiation fails on overflow, so exponents are floats.
+        if *operator == "^" {
+            result.push_str(".0");
+        }
     }
 
     result
 }
 
+fn generate_nested_expression<Gen: Rng>(depth: usize, gen: &mut Gen) -> String {
+    let int_distribution = Uniform::new_inclusive(1, 100);
+    let operators = vec!["+", "-"];
+    let mut result = String::new();
+    for _ in 0..depth {
+        write!(
+            result,
+            "({} {} ",
+            gen.sample(int_distribution),
+            operators.choose(gen).unwrap()
+        )
+        .unwrap();
+    }
+    write!(result, "{}", gen.sample(int_distribution)).unwrap();
+    for _ in 0..depth {
+        result.push(')');
+    }
+    result
+}
+
 fn generate_expression_chain<Gen: Rng>(len: usize, gen: &mut Gen) -> String {
     let mut chain = generate_expression(10, gen);
     while chain.len() < len {
@@ -106,7 +133,26 @@ fn bench_evaluate_deep_expression_trees(bencher: &mut Bencher) {
     let deep_expression_tree =
         build_operator_tree(&generate_expression(BENCHMARK_LEN, &mut gen)).unwrap();
 
-    bencher.iter(|| deep_expression_tree.eval().unwrap());
+    // The tree is deeper than `DEFAULT_MAX_EVALUATION_DEPTH`, but the stack of the main thread is large enough to evaluate it.
+    let max_depth = deep_expression_tree.depth();
+
+    bencher.iter(|| {
+        deep_expression_tree
+            .eval_with_context_and_max_depth(&EmptyContext, max_depth)
+            .unwrap()
+    });
+}
+
+#[bench]
+fn bench_evaluate_nested_expression(bencher: &mut Bencher) {
+    let mut gen = Pcg32::seed_from_u64(22);
+    let nested_expression = build_operator_tree(&generate_nested_expression(
+        NESTED_EXPRESSION_DEPTH,
+        &mut gen,
+    ))
+    .unwrap();
+
+    bencher.iter(|| nested_expression.eval().unwrap());
 }
 
 #[bench]
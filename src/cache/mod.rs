@@ -0,0 +1,164 @@
+//! The `cache` module contains the `ExpressionCache`, which memoizes the operator trees of expression strings.
+
+use alloc::string::{String, ToString};
+
+// Without the standard library, the `ExpressionCache` is backed by a `BTreeMap` instead.
+#[cfg(not(feature = "std"))]
+use alloc::collections::BTreeMap as HashMap;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+use crate::{
+    build_operator_tree, Context, ContextWithMutableVariables, EvalexprResult, HashMapContext,
+    Node, Value,
+};
+
+/// A cached operator tree, together with the time of its last use.
+#[derive(Clone, Debug)]
+struct CachedTree {
+    tree: Node,
+    last_used: u64,
+}
+
+/// A cache that builds the operator tree of an expression string on first use and reuses it afterwards.
+///
+/// A cache constructed with `ExpressionCache::with_capacity` holds at most the given amount of operator trees.
+/// If it is full, the least recently used tree is evicted to make room for a new one.
+/// Expressions that fail to parse are not cached.
+///
+/// # Examples
+///
+/// ```rust
+/// use evalexpr::*;
+///
+/// let mut cache = ExpressionCache::with_capacity(2);
+/// let context = context_map! { "x" => 3 }.unwrap(); // Do proper error handling here
+/// assert_eq!(cache.eval_with_context("x + 1", &context), Ok(Value::from(4)));
+/// assert_eq!(cache.eval_with_context("x * 2", &context), Ok(Value::from(6)));
+/// assert_eq!(cache.eval_with_context("x + 1", &context), Ok(Value::from(4)));
+/// assert_eq!(cache.len(), 2);
+///
+/// // Evicts `x * 2`, which was used less recently than `x + 1`.
+/// assert_eq!(cache.eval_with_context("x - 1", &context), Ok(Value::from(2)));
+/// assert!(cache.contains("x + 1"));
+/// assert!(!cache.contains("x * 2"));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ExpressionCache {
+    trees: HashMap<String, CachedTree>,
+    capacity: Option<usize>,
+    /// Increases with every use of the cache, to order the cached trees by their last use.
+    clock: u64,
+}
+
+impl ExpressionCache {
+    /// Constructs an `ExpressionCache` without a capacity, which keeps every operator tree it builds.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Constructs an `ExpressionCache` that holds at most `capacity` operator trees.
+    /// A capacity of zero is treated as one.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            capacity: Some(capacity.max(1)),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the maximum amount of operator trees this cache holds, or `None` if it has no capacity.
+    pub fn capacity(&self) -> Option<usize> {
+        self.capacity
+    }
+
+    /// Returns the amount of operator trees in this cache.
+    pub fn len(&self) -> usize {
+        self.trees.len()
+    }
+
+    /// Returns true if this cache holds no operator trees.
+    pub fn is_empty(&self) -> bool {
+        self.trees.is_empty()
+    }
+
+    /// Returns true if this cache holds the operator tree of the given expression string.
+    pub fn contains(&self, expression: &str) -> bool {
+        self.trees.contains_key(expression)
+    }
+
+    /// Removes all operator trees from this cache.
+    pub fn clear(&mut self) {
+        self.trees.clear();
+    }
+
+    /// Returns the operator tree of the given expression string, building and caching it if it is not cached yet.
+    ///
+    /// Fails if the expression is not cached and cannot be parsed, see `build_operator_tree`.
+    pub fn get_or_build(&mut self, expression: &str) -> EvalexprResult<&Node> {
+        self.clock += 1;
+        if !self.trees.contains_key(expression) {
+            let tree = build_operator_tree(expression)?;
+            if let Some(capacity) = self.capacity {
+                while self.trees.len() >= capacity {
+                    self.evict_least_recently_used();
+                }
+            }
+            self.trees.insert(
+                expression.to_string(),
+                CachedTree {
+                    tree,
+                    last_used: self.clock,
+                },
+            );
+        }
+
+        let cached = self
+            .trees
+            .get_mut(expression)
+            .expect("the tree was cached above");
+        cached.last_used = self.clock;
+        Ok(&cached.tree)
+    }
+
+    /// Evaluates the given expression string with an empty context, using the cached operator tree if there is one.
+    ///
+    /// *See `eval`.*
+    pub fn eval(&mut self, expression: &str) -> EvalexprResult<Value> {
+        self.eval_with_context_mut(expression, &mut HashMapContext::new())
+    }
+
+    /// Evaluates the given expression string with the given context, using the cached operator tree if there is one.
+    ///
+    /// *See `eval_with_context`.*
+    pub fn eval_with_context<C: Context>(
+        &mut self,
+        expression: &str,
+        context: &C,
+    ) -> EvalexprResult<Value> {
+        self.get_or_build(expression)?.eval_with_context(context)
+    }
+
+    /// Evaluates the given expression string with the given mutable context, using the cached operator tree if there is one.
+    ///
+    /// *See `eval_with_context_mut`.*
+    pub fn eval_with_context_mut<C: ContextWithMutableVariables>(
+        &mut self,
+        expression: &str,
+        context: &mut C,
+    ) -> EvalexprResult<Value> {
+        self.get_or_build(expression)?
+            .eval_with_context_mut(context)
+    }
+
+    /// Removes the operator tree that was used least recently.
+    fn evict_least_recently_used(&mut self) {
+        let least_recently_used = self
+            .trees
+            .iter()
+            .min_by_key(|(_, cached)| cached.last_used)
+            .map(|(expression, _)| expression.clone());
+        if let Some(expression) = least_recently_used {
+            self.trees.remove(&expression);
+        }
+    }
+}
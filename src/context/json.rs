@@ -0,0 +1,127 @@
+use alloc::string::ToString;
+
+use serde_json::Value as JsonValue;
+
+use crate::{
+    context::{Context, GetFunctionContext},
+    function::Function,
+    value::{FloatType, IntType, MapType, TupleType, Value},
+    EvalexprError, EvalexprResult,
+};
+
+/// A context that reads the values of variables from a JSON object.
+///
+/// Identifiers are looked up as keys of the object.
+/// If the object does not contain an identifier with dots like `user.age` as key, it is looked up as a path through nested objects instead.
+///
+/// The JSON values are converted when they are read.
+/// Numbers become a `Value::Int` if they fit into `IntType` and a `Value::Float` otherwise,
+/// strings become a `Value::String`, booleans a `Value::Boolean`, arrays a `Value::Tuple`, objects a `Value::Map` and `null` becomes `Value::Empty`.
+///
+/// Since the values are converted on demand, `get_value` always returns `None`, and variables are read through `get_owned_value`.
+/// No functions can be defined in this context, but builtin functions are enabled by default.
+///
+/// This context requires the `json_support` feature flag.
+///
+/// # Examples
+///
+/// ```rust
+/// use evalexpr::*;
+///
+/// let json = serde_json::json!({ "limit": 18, "user": { "name": "Ada", "age": 36 } });
+/// let context = JsonContext::new(json);
+/// assert_eq!(eval_with_context("user.age > limit", &context), Ok(Value::from(true)));
+/// assert_eq!(eval_with_context("user.name", &context), Ok(Value::from("Ada")));
+/// ```
+#[derive(Clone, Debug)]
+pub struct JsonContext {
+    json: JsonValue,
+
+    /// True if builtin functions are disabled.
+    without_builtin_functions: bool,
+}
+
+impl JsonContext {
+    /// Constructs a `JsonContext` that reads variables from the given JSON object.
+    /// If `json` is not an object, no variables are found.
+    pub fn new(json: JsonValue) -> Self {
+        Self {
+            json,
+            without_builtin_functions: false,
+        }
+    }
+
+    /// Returns the JSON value this context reads variables from.
+    pub fn json(&self) -> &JsonValue {
+        &self.json
+    }
+
+    /// Returns the JSON value that is linked to the given identifier, either as a key or as a path of keys separated by dots.
+    fn lookup(&self, identifier: &str) -> Option<&JsonValue> {
+        self.json.get(identifier).or_else(|| {
+            identifier
+                .split('.')
+                .try_fold(&self.json, |json, key| json.get(key))
+        })
+    }
+}
+
+/// Converts the given JSON value into a `Value`, or returns `None` if it is or contains a number that is not representable as `f64`.
+fn json_to_value(json: &JsonValue) -> Option<Value> {
+    match json {
+        JsonValue::Null => Some(Value::Empty),
+        JsonValue::Bool(boolean) => Some(Value::Boolean(*boolean)),
+        JsonValue::Number(number) => Some(if let Some(int) = number.as_i64() {
+            Value::Int(int as IntType)
+        } else {
+            Value::Float(number.as_f64()? as FloatType)
+        }),
+        JsonValue::String(string) => Some(Value::String(string.clone())),
+        JsonValue::Array(array) => array
+            .iter()
+            .map(json_to_value)
+            .collect::<Option<TupleType>>()
+            .map(Value::Tuple),
+        JsonValue::Object(object) => object
+            .iter()
+            .map(|(key, value)| Some((key.clone(), json_to_value(value)?)))
+            .collect::<Option<MapType>>()
+            .map(Value::Map),
+    }
+}
+
+impl Context for JsonContext {
+    /// Always returns `None`, since the values of a `JsonContext` are converted on demand.
+    fn get_value(&self, _identifier: &str) -> Option<&Value> {
+        None
+    }
+
+    fn get_owned_value(&self, identifier: &str) -> Option<Value> {
+        self.lookup(identifier).and_then(json_to_value)
+    }
+
+    fn call_function(&self, identifier: &str, _argument: &Value) -> EvalexprResult<Value> {
+        Err(EvalexprError::FunctionIdentifierNotFound(
+            identifier.to_string(),
+        ))
+    }
+
+    fn has_function(&self, _identifier: &str) -> bool {
+        false
+    }
+
+    fn are_builtin_functions_disabled(&self) -> bool {
+        self.without_builtin_functions
+    }
+
+    fn set_builtin_functions_disabled(&mut self, disabled: bool) -> EvalexprResult<()> {
+        self.without_builtin_functions = disabled;
+        Ok(())
+    }
+}
+
+impl GetFunctionContext for JsonContext {
+    fn get_function(&self, _identifier: &str) -> Option<&Function> {
+        None
+    }
+}
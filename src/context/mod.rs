@@ -2,33 +2,108 @@
 //! If mutable, it also allows to assign to variables.
 //!
 //! This crate implements two basic variants, the `EmptyContext`, that returns `None` for each identifier and cannot be manipulated, and the `HashMapContext`, that stores its mappings in hash maps.
+//! The `BTreeMapContext` works like the `HashMapContext`, but keeps its mappings ordered by identifier.
+//! The `CombinedContext` looks up identifiers in two other contexts one after the other.
+//! The `FnContext` computes variable values on demand with a closure.
+//! With the `json_support` feature flag, the `JsonContext` reads variable values from a JSON object.
 //! The HashMapContext is type-safe and returns an error if the user tries to assign a value of a different type than before to an identifier.
 
-use std::{collections::HashMap, iter};
+use alloc::{
+    borrow::Cow,
+    collections::{btree_map, BTreeMap},
+    string::{String, ToString},
+};
+use core::{fmt, iter};
+
+// Without the standard library, the `HashMapContext` is backed by a `BTreeMap` instead.
+#[cfg(not(feature = "std"))]
+use alloc::collections::{btree_map as map, BTreeMap as HashMap};
+#[cfg(feature = "std")]
+use std::collections::{hash_map as map, HashMap};
 
 use crate::{
-    function::Function,
+    function::{call_with_packed_arguments, Function},
     value::{value_type::ValueType, Value},
     EvalexprError, EvalexprResult,
 };
 
+#[cfg(feature = "json_support")]
+mod json;
 mod predefined;
 
+#[cfg(feature = "json_support")]
+pub use json::JsonContext;
+
 /// An immutable context.
 pub trait Context {
     /// Returns the value that is linked to the given identifier.
     fn get_value(&self, identifier: &str) -> Option<&Value>;
 
+    /// Returns an owned copy of the value that is linked to the given identifier.
+    /// This is the method used to read variables during evaluation.
+    ///
+    /// The default implementation clones the result of `get_value`.
+    /// Contexts that compute values on demand and therefore cannot return references, like the `FnContext`, override this method instead.
+    fn get_owned_value(&self, identifier: &str) -> Option<Value> {
+        self.get_value(identifier).cloned()
+    }
+
     /// Calls the function that is linked to the given identifier with the given argument.
     /// If no function with the given identifier is found, this method returns `EvalexprError::FunctionIdentifierNotFound`.
     fn call_function(&self, identifier: &str, argument: &Value) -> EvalexprResult<Value>;
 
+    /// Calls the function that is linked to the given identifier with the given argument list.
+    /// This is the method used to call functions during evaluation.
+    /// If no function with the given identifier is found, this method returns `EvalexprError::FunctionIdentifierNotFound`.
+    ///
+    /// The default implementation packs the arguments into a single value and calls `call_function`.
+    /// No arguments are packed into `Value::Empty`, a single argument is passed as itself, and multiple arguments are packed into a tuple.
+    /// Contexts that store functions created with `Function::new_with_arguments` override this method to pass the argument list on unchanged.
+    fn call_function_with_arguments(
+        &self,
+        identifier: &str,
+        arguments: &[Value],
+    ) -> EvalexprResult<Value> {
+        call_with_packed_arguments(arguments, |argument| {
+            self.call_function(identifier, argument)
+        })
+    }
+
+    /// Checks if a function is linked to the given identifier, not counting builtin functions.
+    /// Since functions in the context take precedence over builtin functions, the lazy builtin `if` is only used if this returns `false` for `if`.
+    ///
+    /// The default implementation returns `true`, since any call may reach a function of the context.
+    /// Contexts that know which functions they contain override this method.
+    fn has_function(&self, _identifier: &str) -> bool {
+        true
+    }
+
     /// Checks if builtin functions are disabled.
     fn are_builtin_functions_disabled(&self) -> bool;
 
     /// Disables builtin functions if `disabled` is `true`, and enables them otherwise.
     /// If the context does not support enabling or disabling builtin functions, an error is returned.
     fn set_builtin_functions_disabled(&mut self, disabled: bool) -> EvalexprResult<()>;
+
+    /// Checks if evaluating an expression to a non-finite float, i.e. NaN or an infinity, is an error.
+    /// If so, the evaluation fails with `EvalexprError::NonFiniteResult` as soon as any part of the expression evaluates to a non-finite float.
+    ///
+    /// The default implementation returns `false`, such that floats follow the IEEE 754 semantics.
+    fn are_non_finite_floats_forbidden(&self) -> bool {
+        false
+    }
+
+    /// Forbids non-finite float results if `forbidden` is `true`, and allows them otherwise.
+    /// If the context does not support forbidding non-finite floats, an error is returned.
+    ///
+    /// The default implementation only allows non-finite floats.
+    fn set_non_finite_floats_forbidden(&mut self, forbidden: bool) -> EvalexprResult<()> {
+        if forbidden {
+            Err(EvalexprError::NonFiniteFloatsCannotBeForbidden)
+        } else {
+            Ok(())
+        }
+    }
 }
 
 /// A context that allows to assign to variables.
@@ -37,6 +112,65 @@ pub trait ContextWithMutableVariables: Context {
     fn set_value(&mut self, _identifier: String, _value: Value) -> EvalexprResult<()> {
         Err(EvalexprError::ContextNotMutable)
     }
+
+    /// Calls the function that is linked to the given identifier with the given argument list, allowing it to mutate this context.
+    /// This is the method used to call functions during evaluation with a mutable context, like with `eval_with_context_mut`.
+    /// If no function with the given identifier is found, this method returns `EvalexprError::FunctionIdentifierNotFound`.
+    ///
+    /// The default implementation calls `Context::call_function_with_arguments`.
+    /// Contexts that keep mutable state for their functions, like a counter that increases with every call, override this method.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use evalexpr::*;
+    ///
+    /// struct CounterContext {
+    ///     count: IntType,
+    /// }
+    ///
+    /// impl Context for CounterContext {
+    ///     fn get_value(&self, _identifier: &str) -> Option<&Value> {
+    ///         None
+    ///     }
+    ///
+    ///     fn call_function(&self, identifier: &str, _argument: &Value) -> EvalexprResult<Value> {
+    ///         Err(EvalexprError::FunctionIdentifierNotFound(identifier.into()))
+    ///     }
+    ///
+    ///     fn are_builtin_functions_disabled(&self) -> bool {
+    ///         false
+    ///     }
+    ///
+    ///     fn set_builtin_functions_disabled(&mut self, _disabled: bool) -> EvalexprResult<()> {
+    ///         Err(EvalexprError::BuiltinFunctionsCannotBeDisabled)
+    ///     }
+    /// }
+    ///
+    /// impl ContextWithMutableVariables for CounterContext {
+    ///     fn call_function_mut(&mut self, identifier: &str, arguments: &[Value]) -> EvalexprResult<Value> {
+    ///         if identifier == "counter" {
+    ///             self.count += 1;
+    ///             Ok(Value::from(self.count))
+    ///         } else {
+    ///             self.call_function_with_arguments(identifier, arguments)
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let mut context = CounterContext { count: 0 };
+    /// assert_eq!(eval_with_context_mut("(counter(), counter(), counter())", &mut context),
+    ///     Ok(Value::from(vec![Value::from(1), Value::from(2), Value::from(3)])));
+    /// assert_eq!(eval_with_context_mut("max(counter(), 2)", &mut context), Ok(Value::from(4)));
+    /// assert_eq!(context.count, 4);
+    /// ```
+    fn call_function_mut(
+        &mut self,
+        identifier: &str,
+        arguments: &[Value],
+    ) -> EvalexprResult<Value> {
+        self.call_function_with_arguments(identifier, arguments)
+    }
 }
 
 /// A context that allows to assign to function identifiers.
@@ -63,14 +197,13 @@ pub trait IterateVariablesContext<'a> {
     fn iter_variable_names(&'a self) -> Self::VariableNameIterator;
 }
 
-/*/// A context that allows to retrieve functions programmatically.
+/// A context that allows to retrieve functions programmatically.
 pub trait GetFunctionContext: Context {
     /// Returns the function that is linked to the given identifier.
     ///
-    /// This might not be possible for all functions, as some might be hard-coded.
-    /// In this case, a special error variant should be returned (Not yet implemented).
+    /// Builtin functions are not returned by this method.
     fn get_function(&self, identifier: &str) -> Option<&Function>;
-}*/
+}
 
 /// A context that returns `None` for each identifier.
 /// Builtin functions are disabled and cannot be enabled.
@@ -88,6 +221,10 @@ impl Context for EmptyContext {
         ))
     }
 
+    fn has_function(&self, _identifier: &str) -> bool {
+        false
+    }
+
     /// Builtin functions are always disabled for `EmptyContext`.
     fn are_builtin_functions_disabled(&self) -> bool {
         true
@@ -103,6 +240,12 @@ impl Context for EmptyContext {
     }
 }
 
+impl GetFunctionContext for EmptyContext {
+    fn get_function(&self, _identifier: &str) -> Option<&Function> {
+        None
+    }
+}
+
 impl<'a> IterateVariablesContext<'a> for EmptyContext {
     type VariableIterator = iter::Empty<(String, Value)>;
     type VariableNameIterator = iter::Empty<String>;
@@ -132,6 +275,10 @@ impl Context for EmptyContextWithBuiltinFunctions {
         ))
     }
 
+    fn has_function(&self, _identifier: &str) -> bool {
+        false
+    }
+
     /// Builtin functions are always enabled for EmptyContextWithBuiltinFunctions.
     fn are_builtin_functions_disabled(&self) -> bool {
         false
@@ -147,6 +294,12 @@ impl Context for EmptyContextWithBuiltinFunctions {
     }
 }
 
+impl GetFunctionContext for EmptyContextWithBuiltinFunctions {
+    fn get_function(&self, _identifier: &str) -> Option<&Function> {
+        None
+    }
+}
+
 impl<'a> IterateVariablesContext<'a> for EmptyContextWithBuiltinFunctions {
     type VariableIterator = iter::Empty<(String, Value)>;
     type VariableNameIterator = iter::Empty<String>;
@@ -160,94 +313,673 @@ impl<'a> IterateVariablesContext<'a> for EmptyContextWithBuiltinFunctions {
     }
 }
 
-/// A context that stores its mappings in hash maps.
+/// Defines a context with the given documentation that stores its mappings in maps of the given type.
+/// The `HashMapContext` and the `BTreeMapContext` only differ in the map type, so they share their implementation through this macro.
+macro_rules! map_context {
+    ($(#[$attribute:meta])* $context:ident, $map:ident, $map_module:ident) => {
+        $(#[$attribute])*
+        #[derive(Clone, Debug, Default)]
+        #[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+        pub struct $context {
+            variables: $map<String, Value>,
+            #[cfg_attr(feature = "serde_support", serde(skip))]
+            functions: $map<String, Function>,
+
+            /// True if builtin functions are disabled.
+            without_builtin_functions: bool,
+
+            /// True if identifiers are stored and looked up in lower case.
+            #[cfg_attr(feature = "serde_support", serde(default))]
+            case_insensitive: bool,
+
+            /// True if evaluating to a non-finite float is an error.
+            #[cfg_attr(feature = "serde_support", serde(default))]
+            non_finite_floats_forbidden: bool,
+        }
+
+        impl $context {
+            #[doc = concat!("Constructs a `", stringify!($context), "` with no mappings.")]
+            pub fn new() -> Self {
+                Default::default()
+            }
+
+            #[doc = concat!("Constructs a `", stringify!($context), "` with no mappings that matches variable and function identifiers case-insensitively.")]
+            ///
+            /// Identifiers are stored in lower case, so identifiers that only differ in case refer to the same variable or function.
+            /// Assigning to such an identifier overwrites the existing variable, subject to the usual type safety, and setting such a function replaces the existing function.
+            /// Iterating over the variables yields their identifiers in lower case.
+            ///
+            /// # Examples
+            ///
+            /// ```rust
+            /// use evalexpr::*;
+            ///
+            #[doc = concat!("let mut context = ", stringify!($context), "::new_case_insensitive();")]
+            /// context.set_value("temperature".into(), 20.into()).unwrap(); // Do proper error handling here
+            /// assert_eq!(eval_with_context("TEMPERATURE + 1", &context), Ok(Value::from(21)));
+            /// context.set_value("Temperature".into(), 25.into()).unwrap(); // Do proper error handling here
+            /// assert_eq!(eval_with_context("temperature", &context), Ok(Value::from(25)));
+            /// ```
+            pub fn new_case_insensitive() -> Self {
+                Self {
+                    case_insensitive: true,
+                    ..Default::default()
+                }
+            }
+
+            /// Removes the variable with the given identifier and returns its value, or `None` if there is no such variable.
+            ///
+            /// # Examples
+            ///
+            /// ```rust
+            /// use evalexpr::*;
+            ///
+            #[doc = concat!("let mut context = ", stringify!($context), "::new();")]
+            /// context.set_value("a".into(), 5.into()).unwrap(); // Do proper error handling here
+            /// assert_eq!(context.remove_value("a"), Some(Value::from(5)));
+            /// assert_eq!(context.remove_value("a"), None);
+            /// assert_eq!(eval_with_context("a", &context), Err(EvalexprError::VariableIdentifierNotFound("a".into())));
+            /// ```
+            pub fn remove_value(&mut self, identifier: &str) -> Option<Value> {
+                let key = self.key(identifier);
+                self.variables.remove(key.as_ref())
+            }
+
+            /// Returns an iterator over pairs of function identifiers and functions.
+            /// Builtin functions are not included.
+            ///
+            /// The variables of the context can be iterated with the methods of `IterateVariablesContext`.
+            ///
+            /// # Examples
+            ///
+            /// ```rust
+            /// use evalexpr::*;
+            ///
+            #[doc = concat!("let mut context = ", stringify!($context), "::new();")]
+            /// context_map! {
+            ///     (&mut context)
+            ///     "x" => 1,
+            ///     "f" => Function::new(|argument| Ok(argument.clone())),
+            /// }.unwrap(); // Do proper error handling here
+            /// let identifiers: Vec<_> = context.iter_functions().map(|(identifier, _)| identifier).collect();
+            /// assert_eq!(identifiers, vec!["f"]);
+            /// ```
+            pub fn iter_functions(&self) -> impl Iterator<Item = (&str, &Function)> {
+                self.functions
+                    .iter()
+                    .map(|(identifier, function)| (identifier.as_str(), function))
+            }
+
+            /// Returns the key under which the given identifier is stored.
+            fn key<'a>(&self, identifier: &'a str) -> Cow<'a, str> {
+                if self.case_insensitive {
+                    Cow::Owned(identifier.to_lowercase())
+                } else {
+                    Cow::Borrowed(identifier)
+                }
+            }
+
+            /// Returns the key under which the given owned identifier is stored.
+            fn owned_key(&self, identifier: String) -> String {
+                if self.case_insensitive {
+                    identifier.to_lowercase()
+                } else {
+                    identifier
+                }
+            }
+        }
+
+        impl Context for $context {
+            fn get_value(&self, identifier: &str) -> Option<&Value> {
+                self.variables.get(self.key(identifier).as_ref())
+            }
+
+            fn call_function(&self, identifier: &str, argument: &Value) -> EvalexprResult<Value> {
+                if let Some(function) = self.functions.get(self.key(identifier).as_ref()) {
+                    function.call(argument)
+                } else {
+                    Err(EvalexprError::FunctionIdentifierNotFound(
+                        identifier.to_string(),
+                    ))
+                }
+            }
+
+            fn call_function_with_arguments(
+                &self,
+                identifier: &str,
+                arguments: &[Value],
+            ) -> EvalexprResult<Value> {
+                if let Some(function) = self.functions.get(self.key(identifier).as_ref()) {
+                    function.call_with_arguments(arguments)
+                } else {
+                    Err(EvalexprError::FunctionIdentifierNotFound(
+                        identifier.to_string(),
+                    ))
+                }
+            }
+
+            fn has_function(&self, identifier: &str) -> bool {
+                self.functions.contains_key(self.key(identifier).as_ref())
+            }
+
+            fn set_builtin_functions_disabled(&mut self, disabled: bool) -> EvalexprResult<()> {
+                self.without_builtin_functions = disabled;
+                Ok(())
+            }
+
+            fn are_builtin_functions_disabled(&self) -> bool {
+                self.without_builtin_functions
+            }
+
+            fn are_non_finite_floats_forbidden(&self) -> bool {
+                self.non_finite_floats_forbidden
+            }
+
+            fn set_non_finite_floats_forbidden(&mut self, forbidden: bool) -> EvalexprResult<()> {
+                self.non_finite_floats_forbidden = forbidden;
+                Ok(())
+            }
+        }
+
+        impl ContextWithMutableVariables for $context {
+            fn set_value(&mut self, identifier: String, value: Value) -> EvalexprResult<()> {
+                let identifier = self.owned_key(identifier);
+                if let Some(existing_value) = self.variables.get_mut(&identifier) {
+                    if ValueType::from(&existing_value) == ValueType::from(&value) {
+                        *existing_value = value;
+                        return Ok(());
+                    } else {
+                        return Err(EvalexprError::expected_type(existing_value, value));
+                    }
+                }
+
+                // Implicit else, because `self.variables` and `identifier` are not unborrowed in else
+                self.variables.insert(identifier, value);
+                Ok(())
+            }
+        }
+
+        impl ContextWithMutableFunctions for $context {
+            fn set_function(&mut self, identifier: String, function: Function) -> EvalexprResult<()> {
+                let identifier = self.owned_key(identifier);
+                self.functions.insert(identifier, function);
+                Ok(())
+            }
+        }
+
+        impl GetFunctionContext for $context {
+            fn get_function(&self, identifier: &str) -> Option<&Function> {
+                self.functions.get(self.key(identifier).as_ref())
+            }
+        }
+
+        impl<'a> IterateVariablesContext<'a> for $context {
+            type VariableIterator = iter::Map<
+                $map_module::Iter<'a, String, Value>,
+                fn((&String, &Value)) -> (String, Value),
+            >;
+            type VariableNameIterator = iter::Cloned<$map_module::Keys<'a, String, Value>>;
+
+            fn iter_variables(&'a self) -> Self::VariableIterator {
+                self.variables
+                    .iter()
+                    .map(|(string, value)| (string.clone(), value.clone()))
+            }
+
+            fn iter_variable_names(&'a self) -> Self::VariableNameIterator {
+                self.variables.keys().cloned()
+            }
+        }
+    };
+}
+
+map_context!(
+    /// A context that stores its mappings in hash maps.
+    ///
+    /// *Value and function mappings are stored independently, meaning that there can be a function and a value with the same identifier.*
+    ///
+    /// This context is type-safe, meaning that an identifier that is assigned a value of some type once cannot be assigned a value of another type.
+    ///
+    /// A context constructed with `HashMapContext::new_case_insensitive` matches identifiers case-insensitively.
+    HashMapContext,
+    HashMap,
+    map
+);
+
+impl HashMapContext {
+    /// Constructs a `HashMapContextBuilder` to build a `HashMapContext` with chained method calls.
+    pub fn builder() -> HashMapContextBuilder {
+        HashMapContextBuilder::new()
+    }
+}
+
+/// A builder for a `HashMapContext` whose mappings are given by chained method calls.
+///
+/// Errors of the individual steps, like assigning values of different types to the same identifier, are reported by `build`.
+///
+/// # Examples
 ///
-/// *Value and function mappings are stored independently, meaning that there can be a function and a value with the same identifier.*
+/// ```rust
+/// use evalexpr::*;
 ///
-/// This context is type-safe, meaning that an identifier that is assigned a value of some type once cannot be assigned a value of another type.
+/// let context = HashMapContext::builder()
+///     .with_variable("x", 8)
+///     .with_variable("name", "evalexpr")
+///     .with_function("f", Function::new(|argument| Ok(Value::from(argument.as_int()? * 2))))
+///     .build()
+///     .unwrap(); // Do proper error handling here
+/// assert_eq!(eval_with_context("f(x) + len(name)", &context), Ok(Value::from(24)));
+///
+/// assert_eq!(
+///     HashMapContext::builder().with_variable("x", 8).with_variable("x", 1.5).build().unwrap_err(),
+///     EvalexprError::expected_int(Value::from(1.5))
+/// );
+/// ```
 #[derive(Clone, Debug, Default)]
-#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
-pub struct HashMapContext {
-    variables: HashMap<String, Value>,
-    #[cfg_attr(feature = "serde_support", serde(skip))]
-    functions: HashMap<String, Function>,
-
-    /// True if builtin functions are disabled.
-    without_builtin_functions: bool,
+pub struct HashMapContextBuilder {
+    context: HashMapContext,
+    error: Option<EvalexprError>,
 }
 
-impl HashMapContext {
-    /// Constructs a `HashMapContext` with no mappings.
+impl HashMapContextBuilder {
+    /// Constructs a `HashMapContextBuilder` for a context with no mappings.
     pub fn new() -> Self {
         Default::default()
     }
+
+    /// Sets the variable with the given identifier to the given value.
+    pub fn with_variable(mut self, identifier: impl Into<String>, value: impl Into<Value>) -> Self {
+        if self.error.is_none() {
+            self.error = self
+                .context
+                .set_value(identifier.into(), value.into())
+                .err();
+        }
+        self
+    }
+
+    /// Sets the function with the given identifier to the given function.
+    pub fn with_function(mut self, identifier: impl Into<String>, function: Function) -> Self {
+        if self.error.is_none() {
+            self.error = self.context.set_function(identifier.into(), function).err();
+        }
+        self
+    }
+
+    /// Disables builtin functions if `disabled` is `true`, and enables them otherwise.
+    pub fn with_builtin_functions_disabled(mut self, disabled: bool) -> Self {
+        self.context.without_builtin_functions = disabled;
+        self
+    }
+
+    /// Builds the context, or returns the first error that occurred while setting its mappings.
+    pub fn build(self) -> EvalexprResult<HashMapContext> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(self.context),
+        }
+    }
+}
+
+map_context!(
+    /// A context that stores its mappings in B-tree maps, ordered by identifier.
+    ///
+    /// It has the same API as the `HashMapContext`, but iterating over its variables or functions always yields them in the order of their identifiers.
+    /// This makes iteration, and anything derived from it like serialized output, deterministic.
+    ///
+    /// *Value and function mappings are stored independently, meaning that there can be a function and a value with the same identifier.*
+    ///
+    /// This context is type-safe, meaning that an identifier that is assigned a value of some type once cannot be assigned a value of another type.
+    ///
+    /// A context constructed with `BTreeMapContext::new_case_insensitive` matches identifiers case-insensitively.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use evalexpr::*;
+    ///
+    /// let mut context = BTreeMapContext::new();
+    /// context_map! {
+    ///     (&mut context)
+    ///     "x" => 1,
+    ///     "g" => Function::new(|argument| Ok(argument.clone())),
+    ///     "f" => Function::new(|argument| Ok(argument.clone())),
+    /// }.unwrap(); // Do proper error handling here
+    /// let identifiers: Vec<_> = context.iter_functions().map(|(identifier, _)| identifier).collect();
+    /// assert_eq!(identifiers, vec!["f", "g"]);
+    /// ```
+    BTreeMapContext,
+    BTreeMap,
+    btree_map
+);
+
+/// A context that combines two contexts, looking up identifiers in the primary context first and in the fallback context second.
+///
+/// Since a `CombinedContext` is a context itself, it can be combined again to stack more than two contexts.
+/// Builtin functions are enabled if they are enabled in the primary context, and they cannot be enabled or disabled through the `CombinedContext`.
+/// The same holds for forbidding non-finite floats.
+///
+/// # Examples
+///
+/// ```rust
+/// use evalexpr::*;
+///
+/// let globals = context_map! { "pi" => 3.0, "limit" => 10 }.unwrap(); // Do proper error handling here
+/// let request = context_map! { "limit" => 5 }.unwrap(); // Do proper error handling here
+/// let context = CombinedContext::new(&request, &globals);
+/// assert_eq!(eval_with_context("limit * pi", &context), Ok(Value::from(15.0)));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct CombinedContext<'a, P, F> {
+    primary: &'a P,
+    fallback: &'a F,
+}
+
+impl<'a, P: Context, F: Context> CombinedContext<'a, P, F> {
+    /// Constructs a `CombinedContext` that looks up identifiers in `primary` first and in `fallback` second.
+    pub fn new(primary: &'a P, fallback: &'a F) -> Self {
+        Self { primary, fallback }
+    }
 }
 
-impl Context for HashMapContext {
+impl<P: Context, F: Context> Context for CombinedContext<'_, P, F> {
     fn get_value(&self, identifier: &str) -> Option<&Value> {
-        self.variables.get(identifier)
+        self.primary
+            .get_value(identifier)
+            .or_else(|| self.fallback.get_value(identifier))
+    }
+
+    fn get_owned_value(&self, identifier: &str) -> Option<Value> {
+        self.primary
+            .get_owned_value(identifier)
+            .or_else(|| self.fallback.get_owned_value(identifier))
     }
 
     fn call_function(&self, identifier: &str, argument: &Value) -> EvalexprResult<Value> {
-        if let Some(function) = self.functions.get(identifier) {
-            function.call(argument)
-        } else {
-            Err(EvalexprError::FunctionIdentifierNotFound(
-                identifier.to_string(),
-            ))
+        match self.primary.call_function(identifier, argument) {
+            Err(EvalexprError::FunctionIdentifierNotFound(_)) => {
+                self.fallback.call_function(identifier, argument)
+            },
+            result => result,
+        }
+    }
+
+    fn call_function_with_arguments(
+        &self,
+        identifier: &str,
+        arguments: &[Value],
+    ) -> EvalexprResult<Value> {
+        match self
+            .primary
+            .call_function_with_arguments(identifier, arguments)
+        {
+            Err(EvalexprError::FunctionIdentifierNotFound(_)) => self
+                .fallback
+                .call_function_with_arguments(identifier, arguments),
+            result => result,
+        }
+    }
+
+    fn has_function(&self, identifier: &str) -> bool {
+        self.primary.has_function(identifier) || self.fallback.has_function(identifier)
+    }
+
+    /// Builtin functions are enabled for `CombinedContext` if they are enabled in the primary context.
+    fn are_builtin_functions_disabled(&self) -> bool {
+        self.primary.are_builtin_functions_disabled()
+    }
+
+    /// Builtin functions can't be enabled or disabled for `CombinedContext`.
+    fn set_builtin_functions_disabled(&mut self, disabled: bool) -> EvalexprResult<()> {
+        match (self.are_builtin_functions_disabled(), disabled) {
+            (false, true) => Err(EvalexprError::BuiltinFunctionsCannotBeDisabled),
+            (true, false) => Err(EvalexprError::BuiltinFunctionsCannotBeEnabled),
+            _ => Ok(()),
+        }
+    }
+
+    /// Non-finite floats are forbidden for `CombinedContext` if they are forbidden in the primary context.
+    fn are_non_finite_floats_forbidden(&self) -> bool {
+        self.primary.are_non_finite_floats_forbidden()
+    }
+
+    /// Non-finite floats can't be forbidden or allowed for `CombinedContext`.
+    fn set_non_finite_floats_forbidden(&mut self, forbidden: bool) -> EvalexprResult<()> {
+        match (self.are_non_finite_floats_forbidden(), forbidden) {
+            (false, true) => Err(EvalexprError::NonFiniteFloatsCannotBeForbidden),
+            (true, false) => Err(EvalexprError::NonFiniteFloatsCannotBeAllowed),
+            _ => Ok(()),
         }
     }
+}
+
+impl<P: GetFunctionContext, F: GetFunctionContext> GetFunctionContext
+    for CombinedContext<'_, P, F>
+{
+    fn get_function(&self, identifier: &str) -> Option<&Function> {
+        self.primary
+            .get_function(identifier)
+            .or_else(|| self.fallback.get_function(identifier))
+    }
+}
+
+/// A context that computes the values of variables on demand by calling a closure with the variable identifier.
+///
+/// This is useful if the variables cannot or should not be stored up front, for example if they are read from a database.
+/// Since the values are computed, `get_value` cannot return references to them and always returns `None`.
+/// Variables are read through `get_owned_value` instead, which is what the evaluation uses.
+///
+/// No functions can be defined in this context, but builtin functions are enabled by default.
+/// Combine it with another context using a `CombinedContext` to provide functions as well.
+///
+/// # Examples
+///
+/// ```rust
+/// use evalexpr::*;
+///
+/// let context = FnContext::new(|identifier| match identifier {
+///     "answer" => Some(Value::from(42)),
+///     _ => identifier.strip_prefix("len_").map(|rest| Value::from(rest.len() as IntType)),
+/// });
+/// assert_eq!(eval_with_context("answer + len_abc", &context), Ok(Value::from(45)));
+/// assert_eq!(
+///     eval_with_context("question", &context),
+///     Err(EvalexprError::VariableIdentifierNotFound("question".into()))
+/// );
+/// ```
+#[derive(Clone, Copy)]
+pub struct FnContext<F> {
+    lookup: F,
+
+    /// True if builtin functions are disabled.
+    without_builtin_functions: bool,
+}
+
+impl<F: Fn(&str) -> Option<Value>> FnContext<F> {
+    /// Constructs a `FnContext` that looks up the values of variables by calling `lookup` with their identifier.
+    pub fn new(lookup: F) -> Self {
+        Self {
+            lookup,
+            without_builtin_functions: false,
+        }
+    }
+}
+
+impl<F> fmt::Debug for FnContext<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FnContext")
+            .field("without_builtin_functions", &self.without_builtin_functions)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<F: Fn(&str) -> Option<Value>> Context for FnContext<F> {
+    /// Always returns `None`, since the values of a `FnContext` are computed on demand.
+    fn get_value(&self, _identifier: &str) -> Option<&Value> {
+        None
+    }
+
+    fn get_owned_value(&self, identifier: &str) -> Option<Value> {
+        (self.lookup)(identifier)
+    }
+
+    fn call_function(&self, identifier: &str, _argument: &Value) -> EvalexprResult<Value> {
+        Err(EvalexprError::FunctionIdentifierNotFound(
+            identifier.to_string(),
+        ))
+    }
+
+    fn has_function(&self, _identifier: &str) -> bool {
+        false
+    }
+
+    fn are_builtin_functions_disabled(&self) -> bool {
+        self.without_builtin_functions
+    }
 
     fn set_builtin_functions_disabled(&mut self, disabled: bool) -> EvalexprResult<()> {
         self.without_builtin_functions = disabled;
         Ok(())
     }
+}
+
+impl<F: Fn(&str) -> Option<Value>> GetFunctionContext for FnContext<F> {
+    fn get_function(&self, _identifier: &str) -> Option<&Function> {
+        None
+    }
+}
+
+/// A context that wraps another context and forbids all function calls, for evaluating untrusted expressions that should only compute with values.
+///
+/// Variables are read from and assigned to the wrapped context, but every call of a function, including builtin functions,
+/// fails with `EvalexprError::FunctionsDisabled`, even if the wrapped context defines the function.
+/// Builtin functions are always disabled and cannot be enabled.
+///
+/// # Examples
+///
+/// ```rust
+/// use evalexpr::*;
+///
+/// let context = context_map! {
+///     "x" => 3,
+///     "f" => Function::new(|argument| Ok(argument.clone()))
+/// }.unwrap(); // Do proper error handling here
+/// let mut context = FunctionsDisabledContext::new(context);
+/// assert_eq!(eval_with_context_mut("y = x * 2; y + 1", &mut context), Ok(Value::from(7)));
+/// assert_eq!(
+///     eval_with_context("f(x)", &context),
+///     Err(EvalexprError::FunctionsDisabled("f".into()))
+/// );
+/// assert_eq!(
+///     eval_with_context("max(x, 1)", &context),
+///     Err(EvalexprError::FunctionsDisabled("max".into()))
+/// );
+/// assert_eq!(context.into_inner().get_value("y"), Some(&Value::from(6)));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct FunctionsDisabledContext<C> {
+    context: C,
+}
+
+impl<C: Context> FunctionsDisabledContext<C> {
+    /// Constructs a `FunctionsDisabledContext` that reads and assigns variables in the given context.
+    pub fn new(context: C) -> Self {
+        Self { context }
+    }
+
+    /// Returns a reference to the wrapped context.
+    pub fn inner(&self) -> &C {
+        &self.context
+    }
+
+    /// Returns a mutable reference to the wrapped context.
+    pub fn inner_mut(&mut self) -> &mut C {
+        &mut self.context
+    }
 
+    /// Returns the wrapped context.
+    pub fn into_inner(self) -> C {
+        self.context
+    }
+}
+
+impl<C: Context> Context for FunctionsDisabledContext<C> {
+    fn get_value(&self, identifier: &str) -> Option<&Value> {
+        self.context.get_value(identifier)
+    }
+
+    fn get_owned_value(&self, identifier: &str) -> Option<Value> {
+        self.context.get_owned_value(identifier)
+    }
+
+    fn call_function(&self, identifier: &str, _argument: &Value) -> EvalexprResult<Value> {
+        Err(EvalexprError::FunctionsDisabled(identifier.into()))
+    }
+
+    fn call_function_with_arguments(
+        &self,
+        identifier: &str,
+        _arguments: &[Value],
+    ) -> EvalexprResult<Value> {
+        Err(EvalexprError::FunctionsDisabled(identifier.into()))
+    }
+
+    /// Builtin functions are always disabled for `FunctionsDisabledContext`.
     fn are_builtin_functions_disabled(&self) -> bool {
-        self.without_builtin_functions
+        true
+    }
+
+    /// Builtin functions can't be enabled for `FunctionsDisabledContext`.
+    fn set_builtin_functions_disabled(&mut self, disabled: bool) -> EvalexprResult<()> {
+        if disabled {
+            Ok(())
+        } else {
+            Err(EvalexprError::BuiltinFunctionsCannotBeEnabled)
+        }
+    }
+
+    fn are_non_finite_floats_forbidden(&self) -> bool {
+        self.context.are_non_finite_floats_forbidden()
+    }
+
+    fn set_non_finite_floats_forbidden(&mut self, forbidden: bool) -> EvalexprResult<()> {
+        self.context.set_non_finite_floats_forbidden(forbidden)
     }
 }
 
-impl ContextWithMutableVariables for HashMapContext {
+impl<C: ContextWithMutableVariables> ContextWithMutableVariables for FunctionsDisabledContext<C> {
     fn set_value(&mut self, identifier: String, value: Value) -> EvalexprResult<()> {
-        if let Some(existing_value) = self.variables.get_mut(&identifier) {
-            if ValueType::from(&existing_value) == ValueType::from(&value) {
-                *existing_value = value;
-                return Ok(());
-            } else {
-                return Err(EvalexprError::expected_type(existing_value, value));
-            }
-        }
+        self.context.set_value(identifier, value)
+    }
 
-        // Implicit else, because `self.variables` and `identifier` are not unborrowed in else
-        self.variables.insert(identifier, value);
-        Ok(())
+    fn call_function_mut(
+        &mut self,
+        identifier: &str,
+        _arguments: &[Value],
+    ) -> EvalexprResult<Value> {
+        Err(EvalexprError::FunctionsDisabled(identifier.into()))
     }
 }
 
-impl ContextWithMutableFunctions for HashMapContext {
-    fn set_function(&mut self, identifier: String, function: Function) -> EvalexprResult<()> {
-        self.functions.insert(identifier, function);
-        Ok(())
+impl<C: Context> GetFunctionContext for FunctionsDisabledContext<C> {
+    /// Always returns `None`, since no function can be called in a `FunctionsDisabledContext`.
+    fn get_function(&self, _identifier: &str) -> Option<&Function> {
+        None
     }
 }
 
-impl<'a> IterateVariablesContext<'a> for HashMapContext {
-    type VariableIterator = std::iter::Map<
-        std::collections::hash_map::Iter<'a, String, Value>,
-        fn((&String, &Value)) -> (String, Value),
-    >;
-    type VariableNameIterator =
-        std::iter::Cloned<std::collections::hash_map::Keys<'a, String, Value>>;
+impl<'a, C: IterateVariablesContext<'a>> IterateVariablesContext<'a>
+    for FunctionsDisabledContext<C>
+{
+    type VariableIterator = C::VariableIterator;
+    type VariableNameIterator = C::VariableNameIterator;
 
     fn iter_variables(&'a self) -> Self::VariableIterator {
-        self.variables
-            .iter()
-            .map(|(string, value)| (string.clone(), value.clone()))
+        self.context.iter_variables()
     }
 
     fn iter_variable_names(&'a self) -> Self::VariableNameIterator {
-        self.variables.keys().cloned()
+        self.context.iter_variable_names()
     }
 }
 
@@ -270,16 +1002,22 @@ macro_rules! context_map {
     // Termination (allow missing comma at the end of the argument list)
     ( ($ctx:expr) $k:expr => Function::new($($v:tt)*) ) =>
         { $crate::context_map!(($ctx) $k => Function::new($($v)*),) };
+    ( ($ctx:expr) $k:expr => Function::new_with_arguments($($v:tt)*) ) =>
+        { $crate::context_map!(($ctx) $k => Function::new_with_arguments($($v)*),) };
     ( ($ctx:expr) $k:expr => $v:expr ) =>
         { $crate::context_map!(($ctx) $k => $v,)  };
     // Termination
     ( ($ctx:expr) ) => { Ok(()) };
 
-    // The user has to specify a literal 'Function::new' in order to create a function
+    // The user has to specify a literal 'Function::new' or 'Function::new_with_arguments' in order to create a function
     ( ($ctx:expr) $k:expr => Function::new($($v:tt)*) , $($tt:tt)*) => {{
         $crate::ContextWithMutableFunctions::set_function($ctx, $k.into(), $crate::Function::new($($v)*))
             .and($crate::context_map!(($ctx) $($tt)*))
     }};
+    ( ($ctx:expr) $k:expr => Function::new_with_arguments($($v:tt)*) , $($tt:tt)*) => {{
+        $crate::ContextWithMutableFunctions::set_function($ctx, $k.into(), $crate::Function::new_with_arguments($($v)*))
+            .and($crate::context_map!(($ctx) $($tt)*))
+    }};
     // add a value, and chain the eventual error with the ones in the next values
     ( ($ctx:expr) $k:expr => $v:expr , $($tt:tt)*) => {{
         $crate::ContextWithMutableVariables::set_value($ctx, $k.into(), $v.into())
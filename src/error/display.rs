@@ -1,6 +1,6 @@
-use std::fmt;
+use core::fmt;
 
-use crate::EvalexprError;
+use crate::{EvalexprError, Value};
 
 impl fmt::Display for EvalexprError {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
@@ -43,20 +43,23 @@ impl fmt::Display for EvalexprError {
                 "Expected a Value::Tuple of len {}, but got {:?}.",
                 expected_len, actual
             ),
+            ExpectedMap { actual } => write!(f, "Expected a Value::Map, but got {:?}.", actual),
             ExpectedEmpty { actual } => write!(f, "Expected a Value::Empty, but got {:?}.", actual),
             AppendedToLeafNode => write!(f, "Tried to append a node to a leaf node."),
             PrecedenceViolation => write!(
                 f,
                 "Tried to append a node to another node with higher precedence."
             ),
-            VariableIdentifierNotFound(identifier) => write!(
-                f,
-                "Variable identifier is not bound to anything by context: {:?}.",
-                identifier
-            ),
-            FunctionIdentifierNotFound(identifier) => write!(
+            VariableIdentifierNotFound(identifier) => {
+                write!(f, "Variable '{}' is not bound in the context.", identifier)
+            },
+            KeyNotFound(key) => write!(f, "Key '{}' is not contained in the map.", key),
+            FunctionIdentifierNotFound(identifier) => {
+                write!(f, "Function '{}' is not bound in the context.", identifier)
+            },
+            FunctionsDisabled(identifier) => write!(
                 f,
-                "Function identifier is not bound to anything by context: {:?}.",
+                "Function '{}' cannot be called, as functions are disabled in the context.",
                 identifier
             ),
             TypeError { expected, actual } => {
@@ -64,63 +67,117 @@ impl fmt::Display for EvalexprError {
             },
             WrongTypeCombination { operator, actual } => write!(
                 f,
-                "The operator {:?} was called with a wrong combination of types: {:?}",
+                "The operator {:?} was called with a wrong combination of types: {:?}.",
                 operator, actual
             ),
             UnmatchedLBrace => write!(f, "Found an unmatched opening parenthesis '('."),
             UnmatchedRBrace => write!(f, "Found an unmatched closing parenthesis ')'."),
-            MissingOperatorOutsideOfBrace { .. } => write!(
+            UnmatchedLBracket => write!(f, "Found an unmatched opening bracket '['."),
+            UnmatchedRBracket => write!(f, "Found an unmatched closing bracket ']'."),
+            UnmatchedLCurlyBrace => write!(f, "Found an unmatched opening curly brace '{{'."),
+            UnmatchedRCurlyBrace => write!(f, "Found an unmatched closing curly brace '}}'."),
+            IllegalMapEntry => write!(
+                f,
+                "Found an element of a map literal that is not of the form 'key: value'."
+            ),
+            UnmatchedQuestionMark => write!(f, "Found a '?' without a matching ':'."),
+            UnmatchedColon => write!(f, "Found a ':' without a preceding '?'."),
+            EmptyTupleElement => write!(f, "Found a ',' without a tuple element before it."),
+            MissingOperatorOutsideOfBrace => write!(
                 f,
                 "Found an opening parenthesis that is preceded by something that does not take \
                  any arguments on the right, or found a closing parenthesis that is succeeded by \
                  something that does not take any arguments on the left."
             ),
-            UnmatchedPartialToken { first, second } => {
-                if let Some(second) = second {
-                    write!(
-                        f,
-                        "Found a partial token '{}' that should not be followed by '{}'.",
-                        first, second
-                    )
-                } else {
-                    write!(
-                        f,
-                        "Found a partial token '{}' that should be followed by another partial \
-                         token.",
-                        first
-                    )
-                }
-            },
-            AdditionError { augend, addend } => write!(f, "Error adding {} + {}", augend, addend),
+            AdditionError { augend, addend } => write!(f, "Error adding {} + {}.", augend, addend),
             SubtractionError {
                 minuend,
                 subtrahend,
-            } => write!(f, "Error subtracting {} - {}", minuend, subtrahend),
-            NegationError { argument } => write!(f, "Error negating -{}", argument),
+            } => write!(f, "Error subtracting {} - {}.", minuend, subtrahend),
+            NegationError { argument } => write!(f, "Error negating -{}.", argument),
             MultiplicationError {
                 multiplicand,
                 multiplier,
-            } => write!(f, "Error multiplying {} * {}", multiplicand, multiplier),
+            } => write!(f, "Error multiplying {} * {}.", multiplicand, multiplier),
             DivisionError { dividend, divisor } => {
-                write!(f, "Error dividing {} / {}", dividend, divisor)
+                write!(f, "Error dividing {} / {}.", dividend, divisor)
             },
             ModulationError { dividend, divisor } => {
-                write!(f, "Error modulating {} % {}", dividend, divisor)
+                write!(f, "Error modulating {} % {}.", dividend, divisor)
+            },
+            ExponentiationError { base, exponent } => {
+                write!(f, "Error exponentiating {} ^ {}.", base, exponent)
+            },
+            ShiftLeftError { value, shift } => {
+                write!(f, "Error shifting {} << {}.", value, shift)
+            },
+            ShiftRightError { value, shift } => {
+                write!(f, "Error shifting {} >> {}.", value, shift)
+            },
+            LogarithmError { argument, base } => write!(
+                f,
+                "Error computing the logarithm of {} to base {}: The argument must be positive, and the base must be positive and not one.",
+                argument, base
+            ),
+            FunctionOverflow {
+                identifier,
+                arguments,
+            } => write!(
+                f,
+                "Error computing {}{}: The result overflows.",
+                identifier,
+                Value::Tuple(arguments.clone())
+            ),
+            IndexOutOfBounds { index, length } => {
+                write!(f, "Index {} is out of bounds for length {}.", index, length)
             },
             InvalidRegex { regex, message } => write!(
                 f,
-                "Regular expression {:?} is invalid: {:?}",
+                "Regular expression {:?} is invalid: {:?}.",
                 regex, message
             ),
-            ContextNotMutable => write!(f, "Cannot manipulate context"),
+            RecursionLimitExceeded => write!(
+                f,
+                "The expression is nested too deeply to be evaluated."
+            ),
+            ContextNotMutable => write!(f, "Cannot manipulate context."),
             BuiltinFunctionsCannotBeEnabled => {
-                write!(f, "This context does not allow enabling builtin functions")
+                write!(f, "This context does not allow enabling builtin functions.")
             },
             BuiltinFunctionsCannotBeDisabled => {
-                write!(f, "This context does not allow disabling builtin functions")
+                write!(f, "This context does not allow disabling builtin functions.")
             },
-            IllegalEscapeSequence(string) => write!(f, "Illegal escape sequence: {}", string),
+            NonFiniteFloatsCannotBeForbidden => {
+                write!(f, "This context does not allow forbidding non-finite floats.")
+            },
+            NonFiniteFloatsCannotBeAllowed => {
+                write!(f, "This context does not allow allowing non-finite floats.")
+            },
+            NonFiniteResult(float) => write!(
+                f,
+                "The expression evaluated to the non-finite float {}, which this context forbids.",
+                float
+            ),
+            IllegalEscapeSequence(string) => write!(f, "Illegal escape sequence: {}.", string),
+            IllegalNumberLiteral(string) => write!(f, "Illegal number literal: {}.", string),
+            IllegalConversion { string, target } => {
+                write!(f, "Cannot convert {:?} to {:?}.", string, target)
+            },
+            EmptyPattern => write!(f, "The string to search for must not be empty."),
+            IllegalRange { lower, upper } => write!(
+                f,
+                "The lower bound {} is greater than the upper bound {}.",
+                lower, upper
+            ),
+            IllegalPrecision(precision) => write!(
+                f,
+                "The precision {} is not between 0 and {}.",
+                precision,
+                u16::MAX
+            ),
             CustomMessage(message) => write!(f, "Error: {}", message),
+            ElementError { index, error } => write!(f, "At element {}: {}", index, error),
+            AtPosition { position, error } => write!(f, "At position {}: {}", position, error),
         }
     }
 }
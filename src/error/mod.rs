@@ -5,7 +5,8 @@
 //! The module also contains some helper functions starting with `expect_` that check for a condition and return `Err(_)` if the condition is not fulfilled.
 //! They are meant as shortcuts to not write the same error checking code everywhere.
 
-use crate::{token::PartialToken, value::value_type::ValueType};
+use crate::value::{value_type::ValueType, FloatType, IntType};
+use alloc::{boxed::Box, string::String, vec::Vec};
 
 use crate::{operator::Operator, value::Value};
 
@@ -85,6 +86,12 @@ pub enum EvalexprError {
         actual: Value,
     },
 
+    /// A map value was expected.
+    ExpectedMap {
+        /// The actual value.
+        actual: Value,
+    },
+
     /// An empty value was expected.
     ExpectedEmpty {
         /// The actual value.
@@ -103,9 +110,15 @@ pub enum EvalexprError {
     /// A `VariableIdentifier` operation did not find its value in the context.
     VariableIdentifierNotFound(String),
 
+    /// A map does not contain the key it is indexed with.
+    KeyNotFound(String),
+
     /// A `FunctionIdentifier` operation did not find its value in the context.
     FunctionIdentifierNotFound(String),
 
+    /// A function was called in a context that disables all function calls, like the `FunctionsDisabledContext`.
+    FunctionsDisabled(String),
+
     /// A value has the wrong type.
     /// Only use this if there is no other error that describes the expected and provided types in more detail.
     TypeError {
@@ -129,20 +142,34 @@ pub enum EvalexprError {
     /// A closing brace without a matching opening brace was found.
     UnmatchedRBrace,
 
+    /// An opening bracket without a matching closing bracket was found.
+    UnmatchedLBracket,
+
+    /// A closing bracket without a matching opening bracket was found.
+    UnmatchedRBracket,
+
+    /// A conditional operator `?` without a matching `:` was found.
+    UnmatchedQuestionMark,
+
+    /// A `:` without a preceding conditional operator `?` was found.
+    UnmatchedColon,
+
+    /// An opening curly brace without a matching closing curly brace was found.
+    UnmatchedLCurlyBrace,
+
+    /// A closing curly brace without a matching opening curly brace was found.
+    UnmatchedRCurlyBrace,
+
+    /// An element of a map literal is not an entry of the form `key: value`, like in `{1}`.
+    IllegalMapEntry,
+
+    /// A comma without a tuple element before it was found, like in `(, 1)` or `(1,, 2)`.
+    EmptyTupleElement,
+
     /// Left of an opening brace or right of a closing brace is a token that does not expect the brace next to it.
     /// For example, writing `4(5)` would yield this error, as the `4` does not have any operands.
     MissingOperatorOutsideOfBrace,
 
-    /// A `PartialToken` is unmatched, such that it cannot be combined into a full `Token`.
-    /// This happens if for example a single `=` is found, surrounded by whitespace.
-    /// It is not a token, but it is part of the string representation of some tokens.
-    UnmatchedPartialToken {
-        /// The unmatched partial token.
-        first: PartialToken,
-        /// The token that follows the unmatched partial token and that cannot be matched to the partial token, or `None`, if `first` is the last partial token in the stream.
-        second: Option<PartialToken>,
-    },
-
     /// An addition operation performed by Rust failed.
     AdditionError {
         /// The first argument of the addition.
@@ -189,6 +216,54 @@ pub enum EvalexprError {
         divisor: Value,
     },
 
+    /// An exponentiation operation performed by Rust failed.
+    ExponentiationError {
+        /// The base of the exponentiation.
+        base: Value,
+        /// The exponent of the exponentiation.
+        exponent: Value,
+    },
+
+    /// A left shift operation performed by Rust failed.
+    ShiftLeftError {
+        /// The value that is shifted.
+        value: Value,
+        /// The amount of bits the value is shifted by.
+        shift: Value,
+    },
+
+    /// A right shift operation performed by Rust failed.
+    ShiftRightError {
+        /// The value that is shifted.
+        value: Value,
+        /// The amount of bits the value is shifted by.
+        shift: Value,
+    },
+
+    /// A logarithm was computed of a number that is not positive, or to a base that is not positive or one.
+    LogarithmError {
+        /// The number whose logarithm was computed.
+        argument: Value,
+        /// The base of the logarithm.
+        base: Value,
+    },
+
+    /// The result of a builtin integer function does not fit into `IntType`.
+    FunctionOverflow {
+        /// The identifier of the function.
+        identifier: String,
+        /// The arguments the function was called with.
+        arguments: Vec<Value>,
+    },
+
+    /// An index is outside of the bounds of the string or tuple it accesses.
+    IndexOutOfBounds {
+        /// The index that was accessed.
+        index: IntType,
+        /// The length of the accessed string or tuple.
+        length: usize,
+    },
+
     /// A regular expression could not be parsed
     InvalidRegex {
         /// The invalid regular expression
@@ -197,20 +272,77 @@ pub enum EvalexprError {
         message: String,
     },
 
+    /// An operator tree is nested too deeply to be evaluated without risking a stack overflow.
+    /// See `Node::eval_with_context_and_max_depth`.
+    RecursionLimitExceeded,
+
     /// A modification was attempted on a `Context` that does not allow modifications.
     ContextNotMutable,
 
     /// An escape sequence within a string literal is illegal.
     IllegalEscapeSequence(String),
 
+    /// A number literal is malformed, for example because it contains digits outside of its radix or is out of range.
+    IllegalNumberLiteral(String),
+
+    /// A string could not be converted into a value of the given type.
+    IllegalConversion {
+        /// The string that could not be converted.
+        string: String,
+        /// The type the string was to be converted into.
+        target: ValueType,
+    },
+
+    /// A string to be replaced or split at is empty, so it would occur between every two characters.
+    EmptyPattern,
+
+    /// The lower bound of a range is greater than its upper bound.
+    IllegalRange {
+        /// The lower bound of the range.
+        lower: Value,
+        /// The upper bound of the range.
+        upper: Value,
+    },
+
+    /// A precision for formatting a number is negative or too large.
+    /// Precisions must be between zero and `u16::MAX`.
+    IllegalPrecision(IntType),
+
     /// This context does not allow enabling builtin functions.
     BuiltinFunctionsCannotBeEnabled,
 
     /// This context does not allow disabling builtin functions.
     BuiltinFunctionsCannotBeDisabled,
 
+    /// This context does not allow forbidding non-finite floats.
+    NonFiniteFloatsCannotBeForbidden,
+
+    /// This context does not allow allowing non-finite floats.
+    NonFiniteFloatsCannotBeAllowed,
+
+    /// An expression evaluated to a non-finite float, i.e. NaN or an infinity, while the context forbids non-finite floats.
+    /// See `Context::are_non_finite_floats_forbidden`.
+    NonFiniteResult(FloatType),
+
     /// A custom error explained by its message.
     CustomMessage(String),
+
+    /// An error that occurred while processing an element of a tuple, like when calling a function on it with the builtin function `map`.
+    ElementError {
+        /// The index of the element in the tuple.
+        index: usize,
+        /// The error that occurred.
+        error: Box<EvalexprError>,
+    },
+
+    /// An error caused by a malformed expression, together with the position where it occurred.
+    /// The position is the offset in characters of the token that caused the error.
+    AtPosition {
+        /// The offset in characters of the token that caused the error.
+        position: usize,
+        /// The error that occurred.
+        error: Box<EvalexprError>,
+    },
 }
 
 impl EvalexprError {
@@ -275,6 +407,11 @@ impl EvalexprError {
         }
     }
 
+    /// Constructs `EvalexprError::ExpectedMap{actual}`.
+    pub fn expected_map(actual: Value) -> Self {
+        EvalexprError::ExpectedMap { actual }
+    }
+
     /// Constructs `EvalexprError::ExpectedEmpty{actual}`.
     pub fn expected_empty(actual: Value) -> Self {
         EvalexprError::ExpectedEmpty { actual }
@@ -288,17 +425,11 @@ impl EvalexprError {
             ValueType::Float => Self::expected_float(actual),
             ValueType::Boolean => Self::expected_boolean(actual),
             ValueType::Tuple => Self::expected_tuple(actual),
+            ValueType::Map => Self::expected_map(actual),
             ValueType::Empty => Self::expected_empty(actual),
         }
     }
 
-    pub(crate) fn unmatched_partial_token(
-        first: PartialToken,
-        second: Option<PartialToken>,
-    ) -> Self {
-        EvalexprError::UnmatchedPartialToken { first, second }
-    }
-
     pub(crate) fn addition_error(augend: Value, addend: Value) -> Self {
         EvalexprError::AdditionError { augend, addend }
     }
@@ -329,10 +460,72 @@ impl EvalexprError {
         EvalexprError::ModulationError { dividend, divisor }
     }
 
+    pub(crate) fn exponentiation_error(base: Value, exponent: Value) -> Self {
+        EvalexprError::ExponentiationError { base, exponent }
+    }
+
+    pub(crate) fn shift_left_error(value: Value, shift: Value) -> Self {
+        EvalexprError::ShiftLeftError { value, shift }
+    }
+
+    pub(crate) fn shift_right_error(value: Value, shift: Value) -> Self {
+        EvalexprError::ShiftRightError { value, shift }
+    }
+
+    #[cfg(feature = "std")]
+    pub(crate) fn logarithm_error(argument: Value, base: Value) -> Self {
+        EvalexprError::LogarithmError { argument, base }
+    }
+
+    pub(crate) fn function_overflow(identifier: String, arguments: Vec<Value>) -> Self {
+        EvalexprError::FunctionOverflow {
+            identifier,
+            arguments,
+        }
+    }
+
+    pub(crate) fn index_out_of_bounds(index: IntType, length: usize) -> Self {
+        EvalexprError::IndexOutOfBounds { index, length }
+    }
+
+    pub(crate) fn illegal_conversion(string: String, target: ValueType) -> Self {
+        EvalexprError::IllegalConversion { string, target }
+    }
+
+    pub(crate) fn illegal_range(lower: Value, upper: Value) -> Self {
+        EvalexprError::IllegalRange { lower, upper }
+    }
+
+    pub(crate) fn element_error(index: usize, error: EvalexprError) -> Self {
+        EvalexprError::ElementError {
+            index,
+            error: Box::new(error),
+        }
+    }
+
     /// Constructs `EvalexprError::InvalidRegex(regex)`
     pub fn invalid_regex(regex: String, message: String) -> Self {
         EvalexprError::InvalidRegex { regex, message }
     }
+
+    /// Constructs `EvalexprError::AtPosition{position, error}` from this error, unless it already carries a position.
+    pub fn at_position(self, position: usize) -> Self {
+        match self {
+            EvalexprError::AtPosition { .. } => self,
+            error => EvalexprError::AtPosition {
+                position,
+                error: Box::new(error),
+            },
+        }
+    }
+
+    /// Returns the offset in characters of the token that caused this error, if it is known.
+    pub fn position(&self) -> Option<usize> {
+        match self {
+            EvalexprError::AtPosition { position, .. } => Some(*position),
+            _ => None,
+        }
+    }
 }
 
 /// Returns `Ok(())` if the actual and expected parameters are equal, and `Err(Error::WrongOperatorArgumentAmount)` otherwise.
@@ -368,6 +561,7 @@ pub fn expect_number_or_string(actual: &Value) -> EvalexprResult<()> {
     }
 }
 
+#[cfg(feature = "std")]
 impl std::error::Error for EvalexprError {}
 
 /// Standard result type used by this crate.
@@ -1,6 +1,14 @@
-use crate::{interface::build_operator_tree, Node};
-use serde::{de, Deserialize, Deserializer};
-use std::fmt;
+use crate::{
+    interface::build_operator_tree,
+    value::{IntType, MapType, TupleType},
+    Node, Value,
+};
+use core::{convert::TryFrom, fmt};
+use serde::{
+    de,
+    ser::{SerializeMap, SerializeSeq},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
 
 impl<'de> Deserialize<'de> for Node {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
@@ -33,3 +41,135 @@ impl<'de> de::Visitor<'de> for NodeVisitor {
         }
     }
 }
+
+impl Serialize for Value {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Value::String(string) => serializer.serialize_str(string),
+            Value::Float(float) => serializer.serialize_f64(*float),
+            Value::Int(int) => serializer.serialize_i64(*int),
+            Value::Boolean(boolean) => serializer.serialize_bool(*boolean),
+            Value::Tuple(tuple) => {
+                let mut seq = serializer.serialize_seq(Some(tuple.len()))?;
+                for value in tuple {
+                    seq.serialize_element(value)?;
+                }
+                seq.end()
+            },
+            Value::Map(map) => {
+                let mut serialized_map = serializer.serialize_map(Some(map.len()))?;
+                for (key, value) in map {
+                    serialized_map.serialize_entry(key, value)?;
+                }
+                serialized_map.end()
+            },
+            Value::Empty => serializer.serialize_unit(),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Value {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(ValueVisitor)
+    }
+}
+
+struct ValueVisitor;
+
+impl<'de> de::Visitor<'de> for ValueVisitor {
+    type Value = Value;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "a string, number, boolean, sequence, map or unit value of the `evalexpr` crate"
+        )
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Value::String(v.to_string()))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Value::String(v))
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Value::Float(v))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Value::Int(v))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        match IntType::try_from(v) {
+            Ok(int) => Ok(Value::Int(int)),
+            Err(_) => Err(E::invalid_value(de::Unexpected::Unsigned(v), &self)),
+        }
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Value::Boolean(v))
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        let mut tuple = TupleType::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(value) = seq.next_element()? {
+            tuple.push(value);
+        }
+        Ok(Value::Tuple(tuple))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::MapAccess<'de>,
+    {
+        let mut result = MapType::new();
+        while let Some((key, value)) = map.next_entry()? {
+            result.insert(key, value);
+        }
+        Ok(Value::Map(result))
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Value::Empty)
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Value::Empty)
+    }
+}
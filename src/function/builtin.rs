@@ -1,12 +1,26 @@
+#[cfg(feature = "rand")]
+use rand::Rng;
 #[cfg(feature = "regex_support")]
 use regex::Regex;
 
 use crate::{
-    value::{FloatType, IntType},
-    EvalexprError, Function, Value, ValueType,
+    operator::call_function,
+    value::{FloatType, IntType, TupleType},
+    Context, EvalexprError, EvalexprResult, Function, Value, ValueType,
+};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::{
+    convert::TryFrom,
+    ops::{BitAnd, BitOr, BitXor, Not, Shl, Shr},
 };
-use std::ops::{BitAnd, BitOr, BitXor, Not, Shl, Shr};
 
+// The floating point math functions require the standard library.
+#[cfg(feature = "std")]
 macro_rules! simple_math {
     ($func:ident) => {
         Some(Function::new(|argument| {
@@ -23,6 +37,22 @@ macro_rules! simple_math {
     };
 }
 
+/// Computes the logarithm of the given number with the given function.
+/// Fails if the number is not positive, or if the base is not positive or one.
+#[cfg(feature = "std")]
+fn logarithm(
+    argument: &Value,
+    base: Value,
+    log: impl Fn(FloatType) -> FloatType,
+) -> EvalexprResult<Value> {
+    let (number, base_number) = (argument.as_number()?, base.as_number()?);
+    if number > 0.0 && base_number > 0.0 && base_number != 1.0 {
+        Ok(Value::Float(log(number)))
+    } else {
+        Err(EvalexprError::logarithm_error(argument.clone(), base))
+    }
+}
+
 fn float_is(func: fn(FloatType) -> bool) -> Option<Function> {
     Some(Function::new(move |argument| {
         Ok(func(argument.as_number()?).into())
@@ -45,41 +75,131 @@ macro_rules! int_function {
     };
 }
 
+/// Computes the greatest common divisor of the given numbers with the Euclidean algorithm.
+/// It is iterative, so it does not use more stack space for large numbers.
+fn greatest_common_divisor(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        let remainder = a % b;
+        a = b;
+        b = remainder;
+    }
+    a
+}
+
+/// Returns the elements of the given tuple, or the given value as only element if it is not a tuple.
+fn tuple_or_single_value(argument: &Value) -> Vec<Value> {
+    if let Value::Tuple(tuple) = argument {
+        tuple.clone()
+    } else {
+        vec![argument.clone()]
+    }
+}
+
+/// Returns the elements of the given tuple, no elements if the given value is empty, or the given value as only element otherwise.
+fn tuple_elements(argument: &Value) -> Vec<Value> {
+    if let Value::Empty = argument {
+        Vec::new()
+    } else {
+        tuple_or_single_value(argument)
+    }
+}
+
+/// Folds the given numbers with the given operations, starting with the given integer.
+/// The result is an integer if all numbers are integers, and a float otherwise.
+fn fold_numbers(
+    numbers: Vec<Value>,
+    initial: IntType,
+    int_operation: fn(IntType, IntType) -> Option<IntType>,
+    float_operation: fn(FloatType, FloatType) -> FloatType,
+    error: fn(Value, Value) -> EvalexprError,
+) -> EvalexprResult<Value> {
+    let mut result = Value::Int(initial);
+    for number in numbers {
+        result = match (&result, &number) {
+            (Value::Int(a), Value::Int(b)) => Value::Int(
+                int_operation(*a, *b).ok_or_else(|| error(result.clone(), number.clone()))?,
+            ),
+            (_, Value::Int(_) | Value::Float(_)) => {
+                Value::Float(float_operation(result.as_number()?, number.as_number()?))
+            },
+            _ => return Err(EvalexprError::expected_number(number)),
+        };
+    }
+    Ok(result)
+}
+
 pub fn builtin_function(identifier: &str) -> Option<Function> {
     match identifier {
         // Log
-        "math::ln" => simple_math!(ln),
-        "math::log" => simple_math!(log, 2),
-        "math::log2" => simple_math!(log2),
-        "math::log10" => simple_math!(log10),
+        #[cfg(feature = "std")]
+        "math::ln" => Some(Function::new(|argument| {
+            logarithm(argument, Value::Float(core::f64::consts::E), FloatType::ln)
+        })),
+        #[cfg(feature = "std")]
+        "math::log" => Some(Function::new(|argument| {
+            let tuple = argument.as_fixed_len_tuple(2)?;
+            let base = tuple[1].as_number()?;
+            logarithm(&tuple[0], tuple[1].clone(), |number| number.log(base))
+        })),
+        #[cfg(feature = "std")]
+        "math::log2" => Some(Function::new(|argument| {
+            logarithm(argument, Value::Int(2), FloatType::log2)
+        })),
+        #[cfg(feature = "std")]
+        "math::log10" => Some(Function::new(|argument| {
+            logarithm(argument, Value::Int(10), FloatType::log10)
+        })),
         // Exp
+        #[cfg(feature = "std")]
         "math::exp" => simple_math!(exp),
+        #[cfg(feature = "std")]
         "math::exp2" => simple_math!(exp2),
         // Pow
+        #[cfg(feature = "std")]
         "math::pow" => simple_math!(powf, 2),
         // Cos
+        #[cfg(feature = "std")]
         "math::cos" => simple_math!(cos),
+        #[cfg(feature = "std")]
         "math::acos" => simple_math!(acos),
+        #[cfg(feature = "std")]
         "math::cosh" => simple_math!(cosh),
+        #[cfg(feature = "std")]
         "math::acosh" => simple_math!(acosh),
         // Sin
+        #[cfg(feature = "std")]
         "math::sin" => simple_math!(sin),
+        #[cfg(feature = "std")]
         "math::asin" => simple_math!(asin),
+        #[cfg(feature = "std")]
         "math::sinh" => simple_math!(sinh),
+        #[cfg(feature = "std")]
         "math::asinh" => simple_math!(asinh),
         // Tan
+        #[cfg(feature = "std")]
         "math::tan" => simple_math!(tan),
+        #[cfg(feature = "std")]
         "math::atan" => simple_math!(atan),
+        #[cfg(feature = "std")]
         "math::tanh" => simple_math!(tanh),
+        #[cfg(feature = "std")]
         "math::atanh" => simple_math!(atanh),
+        #[cfg(feature = "std")]
         "math::atan2" => simple_math!(atan2, 2),
         // Root
+        #[cfg(feature = "std")]
         "math::sqrt" => simple_math!(sqrt),
+        #[cfg(feature = "std")]
         "math::cbrt" => simple_math!(cbrt),
         // Hypotenuse
+        #[cfg(feature = "std")]
         "math::hypot" => simple_math!(hypot, 2),
+        #[cfg(feature = "std")]
         "floor" => simple_math!(floor),
+        // Rounds half-way cases away from zero, not to the nearest even number.
+        #[cfg(feature = "std")]
         "round" => simple_math!(round),
+        #[cfg(feature = "std")]
         "ceil" => simple_math!(ceil),
         // Float special values
         "math::is_nan" => float_is(FloatType::is_nan),
@@ -87,28 +207,211 @@ pub fn builtin_function(identifier: &str) -> Option<Function> {
         "math::is_infinite" => float_is(FloatType::is_infinite),
         "math::is_normal" => float_is(FloatType::is_normal),
         // Absolute
-        "math::abs" => Some(Function::new(|argument| {
-            match argument {
-                Value::Float(num) => Ok(Value::Float(num.abs())),
-                Value::Int(num) => Ok(Value::Int(num.abs())),
-                _ => Err(EvalexprError::ExpectedNumber { actual: argument.clone() }),
+        "math::abs" => Some(Function::new(|argument| match argument {
+            Value::Float(num) => Ok(Value::Float(num.abs())),
+            Value::Int(num) => num
+                .checked_abs()
+                .map(Value::Int)
+                .ok_or_else(|| EvalexprError::negation_error(argument.clone())),
+            _ => Err(EvalexprError::ExpectedNumber {
+                actual: argument.clone(),
+            }),
+        })),
+        // Sign
+        "math::signum" => Some(Function::new(|argument| match argument {
+            // Unlike `FloatType::signum`, zero is mapped to itself.
+            Value::Float(num) => Ok(Value::Float(if *num > 0.0 {
+                1.0
+            } else if *num < 0.0 {
+                -1.0
+            } else {
+                *num
+            })),
+            Value::Int(num) => Ok(Value::Int(num.signum())),
+            _ => Err(EvalexprError::ExpectedNumber {
+                actual: argument.clone(),
+            }),
+        })),
+        #[cfg(feature = "std")]
+        "math::copysign" => simple_math!(copysign, 2),
+        // Integer division, truncating towards zero
+        "div" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+            let (a, b) = (arguments[0].as_int()?, arguments[1].as_int()?);
+            a.checked_div(b).map(Value::Int).ok_or_else(|| {
+                EvalexprError::division_error(arguments[0].clone(), arguments[1].clone())
+            })
+        })),
+        "div_floor" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+            let (a, b) = (arguments[0].as_int()?, arguments[1].as_int()?);
+            let quotient = a.checked_div(b).ok_or_else(|| {
+                EvalexprError::division_error(arguments[0].clone(), arguments[1].clone())
+            })?;
+            // Round towards negative infinity if the exact quotient is negative and not whole.
+            let remainder = a.wrapping_rem(b);
+            if remainder != 0 && (remainder < 0) != (b < 0) {
+                Ok(Value::Int(quotient - 1))
+            } else {
+                Ok(Value::Int(quotient))
+            }
+        })),
+        "mod_floor" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+            let (a, b) = (arguments[0].as_int()?, arguments[1].as_int()?);
+            if b == 0 {
+                return Err(EvalexprError::modulation_error(
+                    arguments[0].clone(),
+                    arguments[1].clone(),
+                ));
+            }
+            // Unlike `checked_rem`, this does not fail for `IntType::MIN % -1`, which is zero.
+            let remainder = a.wrapping_rem(b);
+            if remainder != 0 && (remainder < 0) != (b < 0) {
+                Ok(Value::Int(remainder + b))
+            } else {
+                Ok(Value::Int(remainder))
+            }
+        })),
+        // The results are non-negative, and zero if both arguments are zero.
+        "gcd" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+            let (a, b) = (arguments[0].as_int()?, arguments[1].as_int()?);
+            // The only result that does not fit is `IntType::MIN.unsigned_abs()`, for arguments that are both `IntType::MIN` or zero.
+            IntType::try_from(greatest_common_divisor(a.unsigned_abs(), b.unsigned_abs()))
+                .map(Value::Int)
+                .map_err(|_| EvalexprError::function_overflow("gcd".into(), arguments))
+        })),
+        "lcm" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+            let (a, b) = (arguments[0].as_int()?, arguments[1].as_int()?);
+            if a == 0 || b == 0 {
+                return Ok(Value::Int(0));
+            }
+            let (a, b) = (a.unsigned_abs(), b.unsigned_abs());
+            (a / greatest_common_divisor(a, b))
+                .checked_mul(b)
+                .and_then(|lcm| IntType::try_from(lcm).ok())
+                .map(Value::Int)
+                .ok_or_else(|| EvalexprError::function_overflow("lcm".into(), arguments))
+        })),
+        // Integer exponentiation that errors on overflow, falling back to float exponentiation.
+        "pow" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+            let (base, exponent) = (&arguments[0], &arguments[1]);
+            base.as_number()?;
+            exponent.as_number()?;
+            match (base.as_int(), exponent.as_int()) {
+                (Ok(a), Ok(b)) if b >= 0 => u32::try_from(b)
+                    .ok()
+                    .and_then(|b| a.checked_pow(b))
+                    .map(Value::Int)
+                    .ok_or_else(|| {
+                        EvalexprError::exponentiation_error(base.clone(), exponent.clone())
+                    }),
+                #[cfg(feature = "std")]
+                _ => Ok(Value::Float(base.as_number()?.powf(exponent.as_number()?))),
+                // Float exponentiation requires the standard library.
+                #[cfg(not(feature = "std"))]
+                _ => Err(EvalexprError::exponentiation_error(
+                    base.clone(),
+                    exponent.clone(),
+                )),
             }
         })),
         // Other
         "typeof" => Some(Function::new(move |argument| {
-            Ok(match argument {
-                Value::String(_) => "string",
-                Value::Float(_) => "float",
-                Value::Int(_) => "int",
-                Value::Boolean(_) => "boolean",
-                Value::Tuple(_) => "tuple",
-                Value::Empty => "empty",
+            Ok(argument.type_name().into())
+        })),
+        // Type conversion
+        "int" => Some(Function::new(|argument| match argument {
+            Value::Int(int) => Ok(Value::Int(*int)),
+            // Truncates towards zero. The range check also rejects NaN and infinities.
+            Value::Float(float)
+                if (IntType::MIN as FloatType..-(IntType::MIN as FloatType)).contains(float) =>
+            {
+                Ok(Value::Int(*float as IntType))
+            },
+            Value::Float(float) => Err(EvalexprError::illegal_conversion(
+                float.to_string(),
+                ValueType::Int,
+            )),
+            Value::String(string) => string
+                .trim()
+                .parse()
+                .map(Value::Int)
+                .map_err(|_| EvalexprError::illegal_conversion(string.clone(), ValueType::Int)),
+            _ => Err(EvalexprError::type_error(
+                argument.clone(),
+                vec![ValueType::String, ValueType::Float, ValueType::Int],
+            )),
+        })),
+        "float" => {
+            Some(Function::new(|argument| match argument {
+                Value::Float(float) => Ok(Value::Float(*float)),
+                Value::Int(int) => Ok(Value::Float(*int as FloatType)),
+                Value::String(string) => string.trim().parse().map(Value::Float).map_err(|_| {
+                    EvalexprError::illegal_conversion(string.clone(), ValueType::Float)
+                }),
+                _ => Err(EvalexprError::type_error(
+                    argument.clone(),
+                    vec![ValueType::String, ValueType::Float, ValueType::Int],
+                )),
+            }))
+        },
+        "str" => Some(Function::new(|argument| match argument {
+            Value::String(string) => Ok(Value::String(string.clone())),
+            Value::Float(_) | Value::Int(_) | Value::Boolean(_) => {
+                Ok(Value::String(argument.to_string()))
+            },
+            _ => Err(EvalexprError::type_error(
+                argument.clone(),
+                vec![
+                    ValueType::String,
+                    ValueType::Float,
+                    ValueType::Int,
+                    ValueType::Boolean,
+                ],
+            )),
+        })),
+        "format" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+            let precision = arguments[1].as_int()?;
+            let precision = usize::from(
+                u16::try_from(precision).map_err(|_| EvalexprError::IllegalPrecision(precision))?,
+            );
+            // Integers are formatted exactly, even if they cannot be represented as a float.
+            match &arguments[0] {
+                Value::Int(int) if precision == 0 => Ok(Value::String(int.to_string())),
+                Value::Int(int) => Ok(Value::String(format!("{}.{:0<2$}", int, "", precision))),
+                number => Ok(Value::String(format!(
+                    "{:.*}",
+                    precision,
+                    number.as_number()?
+                ))),
             }
-            .into())
+        })),
+        "bool" => Some(Function::new(|argument| match argument {
+            Value::Boolean(boolean) => Ok(Value::Boolean(*boolean)),
+            Value::String(string) => match string.trim() {
+                "true" => Ok(Value::Boolean(true)),
+                "false" => Ok(Value::Boolean(false)),
+                _ => Err(EvalexprError::illegal_conversion(
+                    string.clone(),
+                    ValueType::Boolean,
+                )),
+            },
+            _ => Err(EvalexprError::type_error(
+                argument.clone(),
+                vec![ValueType::String, ValueType::Boolean],
+            )),
         })),
         "min" => Some(Function::new(|argument| {
-            let arguments = argument.as_tuple()?;
-            let mut min_int = IntType::max_value();
+            let arguments = tuple_elements(argument);
+            if arguments.is_empty() {
+                return Err(EvalexprError::wrong_function_argument_amount(0, 1));
+            }
+            let mut min_int = IntType::MAX;
             let mut min_float: FloatType = 1.0 / 0.0;
             debug_assert!(min_float.is_infinite());
 
@@ -129,8 +432,11 @@ pub fn builtin_function(identifier: &str) -> Option<Function> {
             }
         })),
         "max" => Some(Function::new(|argument| {
-            let arguments = argument.as_tuple()?;
-            let mut max_int = IntType::min_value();
+            let arguments = tuple_elements(argument);
+            if arguments.is_empty() {
+                return Err(EvalexprError::wrong_function_argument_amount(0, 1));
+            }
+            let mut max_int = IntType::MIN;
             let mut max_float: FloatType = -1.0 / 0.0;
             debug_assert!(max_float.is_infinite());
 
@@ -150,6 +456,80 @@ pub fn builtin_function(identifier: &str) -> Option<Function> {
                 Ok(Value::Float(max_float))
             }
         })),
+        "sum" => Some(Function::new(|argument| {
+            fold_numbers(
+                tuple_elements(argument),
+                0,
+                IntType::checked_add,
+                |a, b| a + b,
+                EvalexprError::addition_error,
+            )
+        })),
+        "product" => Some(Function::new(|argument| {
+            fold_numbers(
+                tuple_elements(argument),
+                1,
+                IntType::checked_mul,
+                |a, b| a * b,
+                EvalexprError::multiplication_error,
+            )
+        })),
+        "avg" => Some(Function::new(|argument| {
+            let numbers = tuple_elements(argument);
+            if numbers.is_empty() {
+                return Err(EvalexprError::wrong_function_argument_amount(0, 1));
+            }
+            let count = numbers.len() as FloatType;
+            // Summing as floats cannot overflow for integers, unlike summing as integers.
+            let sum = numbers
+                .iter()
+                .map(Value::as_number)
+                .sum::<EvalexprResult<FloatType>>()?;
+            Ok(Value::Float(sum / count))
+        })),
+        "clamp" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(3)?;
+            let illegal_range =
+                || EvalexprError::illegal_range(arguments[1].clone(), arguments[2].clone());
+
+            if let (Value::Int(number), Value::Int(lower), Value::Int(upper)) =
+                (&arguments[0], &arguments[1], &arguments[2])
+            {
+                if lower > upper {
+                    return Err(illegal_range());
+                }
+                Ok(Value::Int(*number.clamp(lower, upper)))
+            } else {
+                let number = arguments[0].as_number()?;
+                let (lower, upper) = (arguments[1].as_number()?, arguments[2].as_number()?);
+                // `clamp` would panic for NaN bounds.
+                if lower > upper || lower.is_nan() || upper.is_nan() {
+                    return Err(illegal_range());
+                }
+                Ok(Value::Float(number.clamp(lower, upper)))
+            }
+        })),
+        "between" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(3)?;
+            let illegal_range =
+                || EvalexprError::illegal_range(arguments[1].clone(), arguments[2].clone());
+
+            if let (Value::Int(number), Value::Int(lower), Value::Int(upper)) =
+                (&arguments[0], &arguments[1], &arguments[2])
+            {
+                if lower > upper {
+                    return Err(illegal_range());
+                }
+                Ok(Value::Boolean(lower <= number && number <= upper))
+            } else {
+                let number = arguments[0].as_number()?;
+                let (lower, upper) = (arguments[1].as_number()?, arguments[2].as_number()?);
+                if lower > upper || lower.is_nan() || upper.is_nan() {
+                    return Err(illegal_range());
+                }
+                Ok(Value::Boolean(lower <= number && number <= upper))
+            }
+        })),
         "if" => Some(Function::new(|argument| {
             let mut arguments = argument.as_fixed_len_tuple(3)?;
             let result_index = if arguments[0].as_boolean()? { 1 } else { 2 };
@@ -157,22 +537,25 @@ pub fn builtin_function(identifier: &str) -> Option<Function> {
         })),
         "contains" => Some(Function::new(move |argument| {
             let arguments = argument.as_fixed_len_tuple(2)?;
-            if let (Value::Tuple(a), b) = (&arguments[0].clone(), &arguments[1].clone()) {
-                if let Value::String(_) | Value::Int(_) | Value::Float(_) | Value::Boolean(_) = b {
-                    Ok(a.contains(b).into())
-                } else {
-                    Err(EvalexprError::type_error(
-                        b.clone(),
-                        vec![
-                            ValueType::String,
-                            ValueType::Int,
-                            ValueType::Float,
-                            ValueType::Boolean,
-                        ],
-                    ))
-                }
-            } else {
-                Err(EvalexprError::expected_tuple(arguments[0].clone()))
+            match (&arguments[0], &arguments[1]) {
+                (Value::String(a), b) => Ok(a.contains(&b.as_string()?).into()),
+                (
+                    Value::Tuple(a),
+                    b @ (Value::String(_) | Value::Int(_) | Value::Float(_) | Value::Boolean(_)),
+                ) => Ok(a.contains(b).into()),
+                (Value::Tuple(_), b) => Err(EvalexprError::type_error(
+                    b.clone(),
+                    vec![
+                        ValueType::String,
+                        ValueType::Int,
+                        ValueType::Float,
+                        ValueType::Boolean,
+                    ],
+                )),
+                (a, _) => Err(EvalexprError::type_error(
+                    a.clone(),
+                    vec![ValueType::String, ValueType::Tuple],
+                )),
             }
         })),
         "contains_any" => Some(Function::new(move |argument| {
@@ -209,22 +592,45 @@ pub fn builtin_function(identifier: &str) -> Option<Function> {
                 Err(EvalexprError::expected_tuple(arguments[0].clone()))
             }
         })),
+        // A single tuple argument cannot be told apart from an argument list, so it is returned unchanged.
+        "tuple" => Some(Function::new(|argument| match argument {
+            Value::Tuple(_) => Ok(argument.clone()),
+            Value::Empty => Ok(Value::Tuple(Vec::new())),
+            argument => Ok(Value::Tuple(vec![argument.clone()])),
+        })),
+        "nth" => Some(Function::new(|argument| {
+            let mut arguments = argument.as_fixed_len_tuple(2)?;
+            let index = arguments[1].as_int()?;
+            let mut tuple = match arguments.swap_remove(0) {
+                Value::Tuple(tuple) => tuple,
+                value => return Err(EvalexprError::expected_tuple(value)),
+            };
+
+            // Negative indices do not count from the end, but are out of bounds, like with the index operator.
+            match usize::try_from(index) {
+                Ok(position) if position < tuple.len() => Ok(tuple.swap_remove(position)),
+                _ => Err(EvalexprError::index_out_of_bounds(index, tuple.len())),
+            }
+        })),
         "len" => Some(Function::new(|argument| {
+            // The length of a string is its amount of Unicode scalar values, not bytes.
             if let Ok(subject) = argument.as_string() {
-                Ok(Value::from(subject.len() as IntType))
+                Ok(Value::from(subject.chars().count() as IntType))
             } else if let Ok(subject) = argument.as_tuple() {
                 Ok(Value::from(subject.len() as IntType))
+            } else if let Value::Map(subject) = argument {
+                Ok(Value::from(subject.len() as IntType))
             } else {
                 Err(EvalexprError::type_error(
                     argument.clone(),
-                    vec![ValueType::String, ValueType::Tuple],
+                    vec![ValueType::String, ValueType::Tuple, ValueType::Map],
                 ))
             }
         })),
         // String functions
         #[cfg(feature = "regex_support")]
         "str::regex_matches" => Some(Function::new(|argument| {
-            let arguments = argument.as_tuple()?;
+            let arguments = argument.as_fixed_len_tuple(2)?;
 
             let subject = arguments[0].as_string()?;
             let re_str = arguments[1].as_string()?;
@@ -238,7 +644,7 @@ pub fn builtin_function(identifier: &str) -> Option<Function> {
         })),
         #[cfg(feature = "regex_support")]
         "str::regex_replace" => Some(Function::new(|argument| {
-            let arguments = argument.as_tuple()?;
+            let arguments = argument.as_fixed_len_tuple(3)?;
 
             let subject = arguments[0].as_string()?;
             let re_str = arguments[1].as_string()?;
@@ -265,6 +671,75 @@ pub fn builtin_function(identifier: &str) -> Option<Function> {
             let subject = argument.as_string()?;
             Ok(Value::from(subject.trim()))
         })),
+        "str::trim_start" => Some(Function::new(|argument| {
+            let subject = argument.as_string()?;
+            Ok(Value::from(subject.trim_start()))
+        })),
+        "str::trim_end" => Some(Function::new(|argument| {
+            let subject = argument.as_string()?;
+            Ok(Value::from(subject.trim_end()))
+        })),
+        "str::starts_with" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+            let (subject, prefix) = (arguments[0].as_string()?, arguments[1].as_string()?);
+            Ok(subject.starts_with(&prefix).into())
+        })),
+        "str::ends_with" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+            let (subject, suffix) = (arguments[0].as_string()?, arguments[1].as_string()?);
+            Ok(subject.ends_with(&suffix).into())
+        })),
+        "str::replace" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(3)?;
+            let subject = arguments[0].as_string()?;
+            let (from, to) = (arguments[1].as_string()?, arguments[2].as_string()?);
+            if from.is_empty() {
+                return Err(EvalexprError::EmptyPattern);
+            }
+            Ok(Value::String(subject.replace(&from, &to)))
+        })),
+        "str::split" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+            let (subject, separator) = (arguments[0].as_string()?, arguments[1].as_string()?);
+            if separator.is_empty() {
+                return Err(EvalexprError::EmptyPattern);
+            }
+            Ok(Value::Tuple(
+                subject.split(&separator).map(Value::from).collect(),
+            ))
+        })),
+        "str::join" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+            let separator = arguments[1].as_string()?;
+            let parts = tuple_elements(&arguments[0])
+                .iter()
+                .map(Value::as_string)
+                .collect::<EvalexprResult<Vec<_>>>()?;
+            Ok(Value::String(parts.join(&separator)))
+        })),
+        // The start and length are counted in characters, and the length is clamped to the end of the string.
+        "str::substring" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(3)?;
+            let subject = arguments[0].as_string()?;
+            let (start, length) = (arguments[1].as_int()?, arguments[2].as_int()?);
+            let char_count = subject.chars().count();
+            if start < 0 || start as usize > char_count {
+                return Err(EvalexprError::index_out_of_bounds(start, char_count));
+            }
+            if length < 0 {
+                return Err(EvalexprError::index_out_of_bounds(
+                    start + length,
+                    char_count,
+                ));
+            }
+            Ok(Value::String(
+                subject
+                    .chars()
+                    .skip(start as usize)
+                    .take(length as usize)
+                    .collect(),
+            ))
+        })),
         "str::from" => Some(Function::new(|argument| {
             Ok(Value::String(argument.to_string()))
         })),
@@ -273,6 +748,18 @@ pub fn builtin_function(identifier: &str) -> Option<Function> {
             argument.as_empty()?;
             Ok(Value::Float(rand::random()))
         })),
+        #[cfg(feature = "rand")]
+        "random_int" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+            let (lower, upper) = (arguments[0].as_int()?, arguments[1].as_int()?);
+            if lower > upper {
+                return Err(EvalexprError::illegal_range(
+                    arguments[0].clone(),
+                    arguments[1].clone(),
+                ));
+            }
+            Ok(Value::Int(rand::thread_rng().gen_range(lower..=upper)))
+        })),
         // Bitwise operators
         "bitand" => int_function!(bitand, 2),
         "bitor" => int_function!(bitor, 2),
@@ -283,3 +770,80 @@ pub fn builtin_function(identifier: &str) -> Option<Function> {
         _ => None,
     }
 }
+
+/// Calls the builtin function with the given identifier if it needs the context to call other functions, like `map`.
+/// Returns `None` if there is no such builtin function.
+pub(crate) fn call_contextual_builtin_function<C: Context>(
+    identifier: &str,
+    argument: &Value,
+    context: &C,
+) -> Option<EvalexprResult<Value>> {
+    match identifier {
+        "map" => Some(map(argument, context)),
+        "filter" => Some(filter(argument, context)),
+        "reduce" => Some(reduce(argument, context)),
+        _ => None,
+    }
+}
+
+/// Splits the arguments of a builtin function like `map` into the function identifier and the tuple to process.
+fn function_identifier_and_tuple(argument: &Value) -> EvalexprResult<(String, TupleType)> {
+    let mut arguments = argument.as_fixed_len_tuple(2)?;
+    let identifier = arguments[0].as_string()?;
+    match arguments.swap_remove(1) {
+        Value::Tuple(tuple) => Ok((identifier, tuple)),
+        value => Err(EvalexprError::expected_tuple(value)),
+    }
+}
+
+/// Calls the function named by the first argument on each element of the tuple given as second argument,
+/// and collects the results into a tuple.
+fn map<C: Context>(argument: &Value, context: &C) -> EvalexprResult<Value> {
+    let (identifier, tuple) = function_identifier_and_tuple(argument)?;
+    tuple
+        .into_iter()
+        .enumerate()
+        .map(|(index, element)| {
+            call_function(context, &identifier, vec![element])
+                .map_err(|error| EvalexprError::element_error(index, error))
+        })
+        .collect::<EvalexprResult<_>>()
+        .map(Value::Tuple)
+}
+
+/// Calls the predicate named by the first argument on each element of the tuple given as second argument,
+/// and collects the elements for which it returns true into a tuple.
+fn filter<C: Context>(argument: &Value, context: &C) -> EvalexprResult<Value> {
+    let (identifier, tuple) = function_identifier_and_tuple(argument)?;
+    let mut result = Vec::new();
+    for (index, element) in tuple.into_iter().enumerate() {
+        let keep = call_function(context, &identifier, vec![element.clone()])
+            .and_then(|keep| keep.as_boolean())
+            .map_err(|error| EvalexprError::element_error(index, error))?;
+        if keep {
+            result.push(element);
+        }
+    }
+    Ok(Value::Tuple(result))
+}
+
+/// Folds the tuple given as third argument from the left, starting with the second argument as accumulator.
+/// For each element, the function named by the first argument is called with the accumulator and the element,
+/// and its result becomes the new accumulator.
+fn reduce<C: Context>(argument: &Value, context: &C) -> EvalexprResult<Value> {
+    let mut arguments = argument.as_fixed_len_tuple(3)?;
+    let identifier = arguments[0].as_string()?;
+    let tuple = match arguments.swap_remove(2) {
+        Value::Tuple(tuple) => tuple,
+        value => return Err(EvalexprError::expected_tuple(value)),
+    };
+    let initial = arguments.swap_remove(1);
+
+    tuple
+        .into_iter()
+        .enumerate()
+        .try_fold(initial, |accumulator, (index, element)| {
+            call_function(context, &identifier, vec![accumulator, element])
+                .map_err(|error| EvalexprError::element_error(index, error))
+        })
+}
@@ -1,4 +1,5 @@
-use std::fmt;
+use alloc::{boxed::Box, vec::Vec};
+use core::{fmt, slice};
 
 use crate::{error::EvalexprResult, value::Value};
 
@@ -24,6 +25,32 @@ where
     }
 }
 
+/// A helper trait to enable cloning through `Fn` trait objects that take an argument list.
+trait ClonableArgumentsFn
+where
+    Self: Fn(&[Value]) -> EvalexprResult<Value>,
+    Self: Send + Sync + 'static,
+{
+    fn dyn_clone(&self) -> Box<dyn ClonableArgumentsFn>;
+}
+
+impl<F> ClonableArgumentsFn for F
+where
+    F: Fn(&[Value]) -> EvalexprResult<Value>,
+    F: Send + Sync + 'static,
+    F: Clone,
+{
+    fn dyn_clone(&self) -> Box<dyn ClonableArgumentsFn> {
+        Box::new(self.clone()) as _
+    }
+}
+
+/// The boxed closure of a `Function`, depending on whether it takes a single value or an argument list.
+enum FunctionImplementation {
+    Value(Box<dyn ClonableFn>),
+    Arguments(Box<dyn ClonableArgumentsFn>),
+}
+
 /// A user-defined function.
 /// Functions can be used in expressions by storing them in a `Context`.
 ///
@@ -39,13 +66,20 @@ where
 /// assert_eq!(eval_with_context("id(4)", &context), Ok(Value::from(4)));
 /// ```
 pub struct Function {
-    function: Box<dyn ClonableFn>,
+    function: FunctionImplementation,
 }
 
 impl Clone for Function {
     fn clone(&self) -> Self {
         Self {
-            function: self.function.dyn_clone(),
+            function: match &self.function {
+                FunctionImplementation::Value(function) => {
+                    FunctionImplementation::Value(function.as_ref().dyn_clone())
+                },
+                FunctionImplementation::Arguments(function) => {
+                    FunctionImplementation::Arguments(function.as_ref().dyn_clone())
+                },
+            },
         }
     }
 }
@@ -61,12 +95,87 @@ impl Function {
         F: Clone,
     {
         Self {
-            function: Box::new(function) as _,
+            function: FunctionImplementation::Value(Box::new(function) as _),
         }
     }
 
-    pub(crate) fn call(&self, argument: &Value) -> EvalexprResult<Value> {
-        (self.function)(argument)
+    /// Creates a user-defined function that receives its arguments as a slice.
+    ///
+    /// A call with an argument list like `f(1, 2)` passes the two arguments, and a call without arguments like `f()` passes an empty slice.
+    /// Any other argument is passed as the only element of the slice, even if it is a tuple.
+    /// So `f((1, 2))` and `f(t)`, where `t` is a tuple, both pass a single tuple argument.
+    ///
+    /// If the function is called with a single value through `Function::call` instead, a tuple is passed as its elements and `Value::Empty` as an empty slice.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use evalexpr::*;
+    ///
+    /// let context = context_map! {
+    ///     "count" => Function::new_with_arguments(|arguments| Ok(Value::from(arguments.len() as IntType))),
+    /// }.unwrap(); // Do proper error handling here
+    /// assert_eq!(eval_with_context("count()", &context), Ok(Value::from(0)));
+    /// assert_eq!(eval_with_context("count(\"a\")", &context), Ok(Value::from(1)));
+    /// assert_eq!(eval_with_context("count(1, 2, 3)", &context), Ok(Value::from(3)));
+    /// assert_eq!(eval_with_context("count((1, 2, 3))", &context), Ok(Value::from(1)));
+    /// ```
+    pub fn new_with_arguments<F>(function: F) -> Self
+    where
+        F: Fn(&[Value]) -> EvalexprResult<Value>,
+        F: Send + Sync + 'static,
+        F: Clone,
+    {
+        Self {
+            function: FunctionImplementation::Arguments(Box::new(function) as _),
+        }
+    }
+
+    /// Calls the function with the given argument.
+    pub fn call(&self, argument: &Value) -> EvalexprResult<Value> {
+        match &self.function {
+            FunctionImplementation::Value(function) => function(argument),
+            FunctionImplementation::Arguments(function) => match argument {
+                Value::Tuple(arguments) => function(arguments),
+                Value::Empty => function(&[]),
+                argument => function(slice::from_ref(argument)),
+            },
+        }
+    }
+
+    /// Calls the function with the given argument list.
+    ///
+    /// A function created with `Function::new` receives the arguments packed into a single value, like it does when called from an expression.
+    /// No arguments are passed as `Value::Empty`, a single argument as itself, and multiple arguments as a tuple.
+    pub fn call_with_arguments(&self, arguments: &[Value]) -> EvalexprResult<Value> {
+        match &self.function {
+            FunctionImplementation::Value(_) => {
+                call_with_packed_arguments(arguments, |argument| self.call(argument))
+            },
+            FunctionImplementation::Arguments(function) => function(arguments),
+        }
+    }
+}
+
+/// Calls `function` with the given arguments packed into a single value.
+/// No arguments are packed into `Value::Empty`, a single argument is passed as itself, and multiple arguments are packed into a tuple.
+pub(crate) fn call_with_packed_arguments<R>(
+    arguments: &[Value],
+    function: impl FnOnce(&Value) -> R,
+) -> R {
+    match arguments {
+        [] => function(&Value::Empty),
+        [argument] => function(argument),
+        arguments => function(&Value::Tuple(arguments.to_vec())),
+    }
+}
+
+/// Packs the given arguments into a single value like `call_with_packed_arguments`, but without cloning them.
+pub(crate) fn pack_arguments(mut arguments: Vec<Value>) -> Value {
+    match arguments.len() {
+        0 => Value::Empty,
+        1 => arguments.swap_remove(0),
+        _ => Value::Tuple(arguments),
     }
 }
 
@@ -78,6 +187,7 @@ impl fmt::Debug for Function {
 
 /// A trait to ensure a type is `Send` and `Sync`.
 /// If implemented for a type, the crate will not compile if the type is not `Send` and `Sync`.
+#[allow(dead_code)]
 trait IsSendAndSync: Send + Sync {}
 
 impl IsSendAndSync for Function {}
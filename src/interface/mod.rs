@@ -1,7 +1,8 @@
 use crate::{
     token, tree, value::TupleType, Context, ContextWithMutableVariables, EmptyType, EvalexprError,
-    EvalexprResult, FloatType, HashMapContext, IntType, Node, Value, EMPTY_VALUE,
+    EvalexprResult, FloatType, HashMapContext, IntType, Node, Token, Value, EMPTY_VALUE,
 };
+use alloc::{string::String, vec::Vec};
 
 /// Evaluate the given expression string.
 ///
@@ -87,6 +88,104 @@ pub fn build_operator_tree(string: &str) -> EvalexprResult<Node> {
     tree::tokens_to_operator_tree(token::tokenize(string)?)
 }
 
+/// Converts the given expression string into tokens, each paired with the character offset it starts at.
+///
+/// The tokens can be transformed and then built into an operator tree with [`build_operator_tree_from_tokens`].
+///
+/// # Examples
+///
+/// ```rust
+/// use evalexpr::*;
+///
+/// let tokens = tokenize("a + 2").unwrap(); // Do proper error handling here
+/// assert_eq!(
+///     tokens,
+///     vec![
+///         (Token::Identifier("a".into()), 0),
+///         (Token::Plus, 2),
+///         (Token::Int(2), 4)
+///     ]
+/// );
+/// ```
+pub fn tokenize(string: &str) -> EvalexprResult<Vec<(Token, usize)>> {
+    token::tokenize(string)
+}
+
+/// Converts the given expression string into tokens like [`tokenize`], but continues past tokens that cannot be parsed.
+///
+/// Such tokens are left out of the result.
+/// Instead, their errors are collected ordered by position, each wrapped in an [`EvalexprError::AtPosition`] that gives the offset of the faulty token.
+///
+/// # Examples
+///
+/// ```rust
+/// use evalexpr::*;
+///
+/// let (tokens, errors) = tokenize_collect_errors("0xZ + 2");
+/// assert_eq!(tokens, vec![(Token::Plus, 4), (Token::Int(2), 6)]);
+/// assert_eq!(
+///     errors,
+///     vec![EvalexprError::IllegalNumberLiteral("0xZ".into()).at_position(0)]
+/// );
+/// ```
+pub fn tokenize_collect_errors(string: &str) -> (Vec<(Token, usize)>, Vec<EvalexprError>) {
+    let mut errors = Vec::new();
+    let tokens = token::tokenize_collecting_errors(string, &mut errors);
+    errors.sort_by_key(|(_, position)| *position);
+    let errors = errors
+        .into_iter()
+        .map(|(error, position)| error.at_position(position))
+        .collect();
+    (tokens, errors)
+}
+
+/// Build the operator tree for the given tokens, each paired with the character offset it starts at.
+/// The offsets are only used to report the positions of errors.
+///
+/// # Examples
+///
+/// ```rust
+/// use evalexpr::*;
+///
+/// // Replace the identifier `double` by the expression `2 *`.
+/// let tokens = tokenize("double 3").unwrap(); // Do proper error handling here
+/// let tokens = tokens
+///     .into_iter()
+///     .flat_map(|(token, position)| match token {
+///         Token::Identifier(identifier) if identifier == "double" => {
+///             vec![(Token::Int(2), position), (Token::Star, position)]
+///         },
+///         token => vec![(token, position)],
+///     })
+///     .collect();
+///
+/// let precomputed = build_operator_tree_from_tokens(tokens).unwrap(); // Do proper error handling here
+/// assert_eq!(precomputed.eval(), Ok(Value::from(6)));
+/// ```
+pub fn build_operator_tree_from_tokens(tokens: Vec<(Token, usize)>) -> EvalexprResult<Node> {
+    tree::tokens_to_operator_tree(tokens)
+}
+
+/// Evaluate the given expression string, treating all integer literals in it as floats.
+/// This way, the arithmetic is done with floats, like in a calculator.
+/// See `Node::convert_int_literals_to_floats` for details.
+///
+/// # Examples
+///
+/// ```rust
+/// use evalexpr::*;
+///
+/// assert_eq!(eval("5 / 2"), Ok(Value::from(2)));
+/// assert_eq!(eval_with_float_literals("5 / 2"), Ok(Value::from(2.5)));
+/// ```
+///
+/// *See the [crate doc](index.html) for more examples and explanations of the expression format.*
+pub fn eval_with_float_literals(string: &str) -> EvalexprResult<Value> {
+    let mut tree = build_operator_tree(string)?;
+    tree.convert_int_literals_to_floats();
+    tree.eval()
+}
+
 /// Evaluate the given expression string into a string.
 ///
 /// *See the [crate doc](index.html) for more examples and explanations of the expression format.*
@@ -110,6 +209,20 @@ pub fn eval_float(string: &str) -> EvalexprResult<FloatType> {
 
 /// Evaluate the given expression string into a float.
 /// If the result of the expression is an integer, it is silently converted into a float.
+/// Other results are reported as `EvalexprError::ExpectedNumber`.
+///
+/// # Examples
+///
+/// ```rust
+/// use evalexpr::*;
+///
+/// assert_eq!(eval_number("2 * 3"), Ok(6.0));
+/// assert_eq!(eval_number("2 * 3.5"), Ok(7.0));
+/// assert_eq!(
+///     eval_number("\"six\""),
+///     Err(EvalexprError::ExpectedNumber { actual: Value::from("six") })
+/// );
+/// ```
 ///
 /// *See the [crate doc](index.html) for more examples and explanations of the expression format.*
 pub fn eval_number(string: &str) -> EvalexprResult<FloatType> {
@@ -21,6 +21,8 @@
 //! assert_eq!(eval("1 - 2 * 3"), Ok(Value::from(-5)));
 //! assert_eq!(eval("1.0 + 2 * 3"), Ok(Value::from(7.0)));
 //! assert_eq!(eval("true && 4 > 2"), Ok(Value::from(true)));
+//! // `eval_number` accepts both integer and float results and returns a float.
+//! assert_eq!(eval_number("1 + 2"), Ok(3.0));
 //! ```
 //!
 //! You can **chain** expressions and **assign** to variables like this:
@@ -100,6 +102,20 @@
 //! assert_eq!(precompiled.eval_boolean_with_context(&context), Ok(false));
 //! ```
 //!
+//! Precompiled expressions that do not depend on a context can be evaluated with `Node::eval` and its typed variants like `Node::eval_int`.
+//!
+//! To precompile expressions automatically, an `ExpressionCache` builds the operator tree of each expression string on first use and reuses it afterwards.
+//! With `ExpressionCache::with_capacity`, it holds at most the given amount of operator trees and evicts the least recently used one when full.
+//!
+//! To preprocess an expression, it can also be split into tokens with `tokenize`.
+//! Its variant `tokenize_collect_errors` does not stop at the first malformed literal, but collects all errors together with the tokens that could be parsed.
+//! The tokens can be inspected and rewritten before building the operator tree from them with `build_operator_tree_from_tokens`.
+//!
+//! To protect against stack overflows caused by deeply nested expressions, operator trees are only evaluated up to a depth of `DEFAULT_MAX_EVALUATION_DEPTH`.
+//! Deeper trees yield an `EvalexprError::RecursionLimitExceeded`.
+//! Each pair of parentheses costs two levels of depth, one for the parentheses and one for the operator inside them, so an expression like `(1 + (2 + (3 + ...)))` can only be nested about 512 times.
+//! A different maximum depth can be given to `Node::eval_with_context_and_max_depth` and `Node::eval_with_context_mut_and_max_depth`, and the depth of a tree can be checked before evaluating it with `Node::depth`.
+//!
 //! ## Features
 //!
 //! ### Operators
@@ -108,25 +124,35 @@
 //! Operators have a precedence to determine their order of evaluation, where operators of higher precedence are evaluated first.
 //! The precedence should resemble that of most common programming languages, especially Rust.
 //! Variables and values have a precedence of 200, and function literals have 190.
+//! For tools that need to reproduce the grouping of the parser, like syntax highlighters, the precedence and associativity of each operator are available as `Operator::precedence` and `Operator::is_left_to_right`.
 //!
 //! Supported binary operators:
 //!
 //! | Operator | Precedence | Description |
 //! |----------|------------|-------------|
+//! | [] | 180 | Tuple and map indexing |
 //! | ^ | 120 | Exponentiation |
 //! | * | 100 | Product |
 //! | / | 100 | Division (integer if both arguments are integers, otherwise float) |
 //! | % | 100 | Modulo (integer if both arguments are integers, otherwise float) |
 //! | + | 95 | Sum or String Concatenation |
 //! | - | 95 | Difference |
+//! | << | 90 | Bitwise left shift |
+//! | \>\> | 90 | Bitwise right shift |
 //! | < | 80 | Lower than |
 //! | \> | 80 | Greater than |
 //! | <= | 80 | Lower than or equal |
 //! | \>= | 80 | Greater than or equal |
 //! | == | 80 | Equal |
 //! | != | 80 | Not equal |
+//! | in | 80 | Membership in a tuple, substring of a string or key of a map |
+//! | & | 79 | Bitwise and |
+//! | ^^ | 78 | Bitwise xor |
+//! | &#124; | 77 | Bitwise or |
 //! | && | 75 | Logical and |
 //! | &#124;&#124; | 70 | Logical or |
+//! | ? | 61 | Conditional |
+//! | : | 60 | Alternative of a conditional |
 //! | = | 50 | Assignment |
 //! | += | 50 | Sum-Assignment or String-Concatenation-Assignment |
 //! | -= | 50 | Difference-Assignment |
@@ -139,6 +165,16 @@
 //! | , | 40 | Aggregation |
 //! | ; | 0 | Expression Chaining |
 //!
+//! The bitwise operators only accept integers.
+//! As `^` already denotes exponentiation, the bitwise xor is written as `^^`.
+//! Exponentiation can also be written as `**`, and the exponentiation-assignment as `**=`.
+//! The logical operators `&&` and `||` short-circuit, meaning that their right operand is only evaluated if the left operand does not already determine the result.
+//! The ordering comparisons `<`, `>`, `<=` and `>=` can be chained, so `a < b <= c` means `a < b && b <= c`, except that `b` is evaluated only once.
+//! Mixed directions like `a < b > c` are allowed and likewise mean the conjunction of the adjacent comparisons.
+//! A chain stops evaluating its operands as soon as one comparison is false, and a parenthesized comparison like `(a < b) < c` is not part of a chain.
+//! The `+` operator concatenates two strings.
+//! Values are never implicitly converted to strings, so adding a string and a number is an error, but the number can be converted explicitly with `str::from`.
+//!
 //! Supported unary operators:
 //!
 //! | Operator | Precedence | Description |
@@ -146,10 +182,18 @@
 //! | - | 110 | Negation |
 //! | ! | 110 | Logical not |
 //!
+//! Unary operators bind tighter than all binary operators except exponentiation.
+//! For example, `!a == b` is evaluated as `(!a) == b`, and `-2 ^ 2` is evaluated as `-(2 ^ 2)`.
+//!
 //! Operators that take numbers as arguments can either take integers or floating point numbers.
 //! If one of the arguments is a floating point number, all others are converted to floating point numbers as well, and the resulting value is a floating point number as well.
 //! Otherwise, the result is an integer.
-//! An exception to this is the exponentiation operator that always returns a floating point number.
+//! The exponentiation operator additionally returns a floating point number if the exponent is negative.
+//! It is right-associative, so `2 ^ 3 ^ 2` is evaluated as `2 ^ (3 ^ 2)`.
+//! Integer arithmetic never overflows silently or panics.
+//! Instead, it yields an error naming the failed operation and its operands, like `EvalexprError::AdditionError` for `+`, while floating point arithmetic follows the IEEE 754 rules.
+//! To treat NaN and infinities as errors instead, forbid non-finite floats in the context with `set_non_finite_floats_forbidden(true)`.
+//! Then, any part of an expression evaluating to a non-finite float yields an `EvalexprError::NonFiniteResult`.
 //! Example:
 //!
 //! ```rust
@@ -157,7 +201,8 @@
 //!
 //! assert_eq!(eval("1 / 2"), Ok(Value::from(0)));
 //! assert_eq!(eval("1.0 / 2"), Ok(Value::from(0.5)));
-//! assert_eq!(eval("2^2"), Ok(Value::from(4.0)));
+//! assert_eq!(eval("2^2"), Ok(Value::from(4)));
+//! assert_eq!(eval("2^-1"), Ok(Value::from(0.5)));
 //! ```
 //!
 //! #### The Aggregation Operator
@@ -189,6 +234,60 @@
 //! ])));
 //! ```
 //!
+//! A trailing comma is allowed, so `(1, 2,)` is the same as `(1, 2)`, and `(1,)` is a tuple with a single element.
+//! Every other comma needs an element before it, so `(, 1)` and `(1,, 2)` yield an `EvalexprError::EmptyTupleElement`.
+//!
+//! #### The Index Operator
+//!
+//! The index operator `tuple[index]` returns the element of a tuple at the given integer index, counting from zero.
+//! Negative indices do not count from the end of the tuple, but like indices beyond its end, they yield an `EvalexprError::IndexOutOfBounds`.
+//! A map is indexed with a string key instead, like in `map["key"]`, and a missing key yields an `EvalexprError::KeyNotFound`.
+//! The index operator binds tighter than unary operators, but weaker than function calls, so `-t[0]` is evaluated as `-(t[0])` and `f x[0]` as `(f x)[0]`.
+//! Example:
+//!
+//! ```rust
+//! use evalexpr::*;
+//!
+//! assert_eq!(eval("(10, 20, 30)[1]"), Ok(Value::from(20)));
+//! assert_eq!(eval("((1, 2), (3, 4))[1][0]"), Ok(Value::from(3)));
+//! assert_eq!(eval("(10, 20, 30)[3]"), Err(EvalexprError::IndexOutOfBounds { index: 3, length: 3 }));
+//! assert_eq!(eval("{\"a\": 1, \"b\": 2}[\"b\"]"), Ok(Value::from(2)));
+//! ```
+//!
+//! #### The In Operator
+//!
+//! The operator `a in b` tests if `a` is an element of the tuple `b`, a substring of the string `b`, or a key of the map `b`.
+//! Elements of tuples are compared with `==`, so `1.0 in (1, 2)` is false.
+//! For any other combination of types, it yields an `EvalexprError::WrongTypeCombination`.
+//!
+//! ```rust
+//! use evalexpr::*;
+//!
+//! assert_eq!(eval("3 in (1, 2, 3)"), Ok(Value::from(true)));
+//! assert_eq!(eval("\"ell\" in \"hello\""), Ok(Value::from(true)));
+//! assert_eq!(eval("\"b\" in {\"a\": 1}"), Ok(Value::from(false)));
+//! ```
+//!
+//! #### The Conditional Operator
+//!
+//! The conditional operator `condition ? a : b` evaluates to `a` if `condition` is true, and to `b` otherwise.
+//! The condition must evaluate to a boolean, and only the selected branch is evaluated.
+//! The operator is right-associative, so conditionals can be chained in the alternative without parentheses.
+//! The first branch extends up to the matching `:`, so it can be any expression, including a nested conditional, an assignment or a tuple.
+//! The conditional binds more loosely than all other operators except the assignments, `,` and `;`.
+//! So `x = a > b ? a : b` assigns the result of the conditional, and `true ? 1 : 2, 3` is a tuple whose first element is the conditional.
+//!
+//! ```rust
+//! use evalexpr::*;
+//!
+//! assert_eq!(eval("5 > 3 ? \"pass\" : \"fail\""), Ok(Value::from("pass")));
+//! assert_eq!(eval("false ? 1 : false ? 2 : 3"), Ok(Value::from(3)));
+//! assert_eq!(eval("true ? false ? 1 : 2 : 3"), Ok(Value::from(2)));
+//! assert_eq!(eval("true ? 1 : 2, 3"), Ok(Value::from(vec![Value::from(1), Value::from(3)])));
+//! // The untaken branch is not evaluated
+//! assert_eq!(eval("true ? 1 : undefined_variable"), Ok(Value::from(1)));
+//! ```
+//!
 //! #### The Assignment Operator
 //!
 //! This crate features the assignment operator, that allows expressions to store their result in a variable in the expression context.
@@ -245,6 +344,19 @@
 //! assert_eq!(healing_script.eval_int_with_context_mut(&mut context), Ok(5));
 //! ```
 //!
+//! #### Comments
+//!
+//! A line comment starts with `//` and reaches until the end of the line.
+//! Within string literals, `//` does not start a comment.
+//!
+//! ```rust
+//! use evalexpr::*;
+//!
+//! assert_eq!(eval("1 + 2 // add them"), Ok(Value::from(3)));
+//! assert_eq!(eval("a = 2; // initialize a\na * 3"), Ok(Value::from(6)));
+//! assert_eq!(eval("\"http://example.com\""), Ok(Value::from("http://example.com")));
+//! ```
+//!
 //! ### Contexts
 //!
 //! An expression evaluator that just evaluates expressions would be useful already, but this crate can do more.
@@ -313,8 +425,52 @@
 //! assert_eq!(eval_int_with_context("f 5", &context), Ok(10));
 //! ```
 //!
+//! Alternatively, a `HashMapContext` can be built with chained method calls by the `HashMapContextBuilder`:
+//!
+//! ```rust
+//! use evalexpr::*;
+//!
+//! let context = HashMapContext::builder()
+//!     .with_variable("x", 5)
+//!     .with_function("f", Function::new(|args| Ok(Value::from(args.as_int()? + 5))))
+//!     .build()
+//!     .unwrap_or_else(|error| panic!("Error creating context: {}", error));
+//! assert_eq!(eval_int_with_context("f x", &context), Ok(10));
+//! ```
+//!
 //! For more information about user-defined functions, refer to the respective [section](#user-defined-functions).
 //!
+//! Contexts can also be layered with the `CombinedContext`, which looks up identifiers in a primary context first and in a fallback context second.
+//! Since it is a context itself, it can be combined again to stack more than two contexts:
+//!
+//! ```rust
+//! use evalexpr::*;
+//!
+//! let constants = context_map! { "pi" => 3.14, "limit" => 10 }.unwrap(); // Do proper error handling here
+//! let defaults = context_map! { "limit" => 5, "scale" => 2 }.unwrap(); // Do proper error handling here
+//! let request = context_map! { "scale" => 3 }.unwrap(); // Do proper error handling here
+//! let defaults_and_constants = CombinedContext::new(&defaults, &constants);
+//! let context = CombinedContext::new(&request, &defaults_and_constants);
+//! assert_eq!(eval_with_context("limit * scale", &context), Ok(Value::from(15)));
+//! assert_eq!(eval_with_context("pi", &context), Ok(Value::from(3.14)));
+//! ```
+//!
+//! The `BTreeMapContext` offers the same API as the `HashMapContext`, but keeps its variables and functions ordered by identifier.
+//! Iterating over it is therefore deterministic, which helps to produce reproducible output.
+//!
+//! If the values of variables should be computed on demand, for example by reading them from a database, the `FnContext` resolves variables by calling a closure with their identifier:
+//!
+//! ```rust
+//! use evalexpr::*;
+//!
+//! let context = FnContext::new(|identifier| match identifier {
+//!     "width" => Some(Value::from(4)),
+//!     "height" => Some(Value::from(3)),
+//!     _ => None,
+//! });
+//! assert_eq!(eval_with_context("width * height", &context), Ok(Value::from(12)));
+//! ```
+//!
 //! ### Builtin Functions
 //!
 //! This crate offers a set of builtin functions (see below for a full list).
@@ -332,26 +488,66 @@
 //! Specifically the `EmptyContext` has builtin functions disabled by default, and they cannot be enabled.
 //! Symmetrically, the `EmptyContextWithBuiltinFunctions` has builtin functions enabled by default, and they cannot be disabled.
 //!
+//! The `EmptyContextWithBuiltinFunctions` is therefore a ready-made context for expressions that only use builtin functions:
+//!
+//! ```rust
+//! use evalexpr::*;
+//!
+//! assert_eq!(eval_with_context("math::sqrt(16.0)", &EmptyContextWithBuiltinFunctions), Ok(Value::from(4.0)));
+//! ```
+//!
+//! To guarantee that an expression calls no function at all, for example when evaluating untrusted arithmetic, wrap the context in a `FunctionsDisabledContext`.
+//! Every function call then fails with `EvalexprError::FunctionsDisabled`, including calls of builtin functions and of functions defined in the wrapped context:
+//!
+//! ```rust
+//! use evalexpr::*;
+//!
+//! let context = FunctionsDisabledContext::new(context_map! { "x" => 4.0 }.unwrap()); // Do proper error handling here
+//! assert_eq!(eval_with_context("x * x", &context), Ok(Value::from(16.0)));
+//! assert_eq!(eval_with_context("math::sqrt(x)", &context), Err(EvalexprError::FunctionsDisabled(String::from("math::sqrt"))));
+//! ```
+//!
 //! | Identifier           | Argument Amount | Argument Types                | Description |
 //! |----------------------|-----------------|-------------------------------|-------------|
 //! | `min`                | >= 1            | Numeric                       | Returns the minimum of the arguments |
 //! | `max`                | >= 1            | Numeric                       | Returns the maximum of the arguments |
-//! | `len`                | 1               | String/Tuple                  | Returns the character length of a string, or the amount of elements in a tuple (not recursively) |
+//! | `sum`                | >= 0            | Numeric                       | Returns the sum of the arguments, `0` if there are none |
+//! | `product`            | >= 0            | Numeric                       | Returns the product of the arguments, `1` if there are none |
+//! | `avg`                | >= 1            | Numeric                       | Returns the arithmetic mean of the arguments as float |
+//! | `clamp`              | 3               | Numeric, Numeric, Numeric     | Returns the first argument limited to the range from the second to the third argument. The result is an integer if all arguments are integers |
+//! | `between`            | 3               | Numeric, Numeric, Numeric     | Returns true if the first argument is within the range from the second to the third argument, including both bounds. Errors if the second argument is greater than the third |
+//! | `len`                | 1               | String/Tuple/Map              | Returns the amount of characters (Unicode scalar values, not bytes) of a string, or the amount of elements in a tuple or map (not recursively) |
+//! | `tuple`              | 0+              | Any                           | Packs its arguments into a tuple. A single tuple argument is returned unchanged |
+//! | `nth`                | 2               | Tuple, Int                    | Returns the element of the tuple at the given zero-based index, like `t[i]`. Errors if the index is out of bounds |
+//! | `map`                | 2               | String, Tuple                 | Calls the function with the given name on each element of the tuple, like `map("double", (1, 2))`, and returns the results as a tuple. Errors of an element are wrapped in an `EvalexprError::ElementError` with its index |
+//! | `filter`             | 2               | String, Tuple                 | Calls the predicate with the given name on each element of the tuple, like `filter("is_even", (1, 2))`, and returns the elements for which it returns true as a tuple. Errors of an element, including a non-boolean result, are wrapped in an `EvalexprError::ElementError` with its index |
+//! | `reduce`             | 3               | String, Any, Tuple            | Folds the tuple from the left, like `reduce("add", 0, (1, 2))`. The function with the given name is called with the accumulator, starting with the second argument, and the current element, and its result becomes the new accumulator. Errors of an element are wrapped in an `EvalexprError::ElementError` with its index |
 //! | `floor`              | 1               | Numeric                       | Returns the largest integer less than or equal to a number |
-//! | `round`              | 1               | Numeric                       | Returns the nearest integer to a number. Rounds half-way cases away from 0.0 |
+//! | `round`              | 1               | Numeric                       | Returns the nearest integer to a number. Rounds half-way cases away from 0.0, not to the nearest even number |
 //! | `ceil`               | 1               | Numeric                       | Returns the smallest integer greater than or equal to a number |
-//! | `if`                 | 3               | Boolean, Any, Any             | If the first argument is true, returns the second argument, otherwise, returns the third  |
-//! | `contains`           | 2               | Tuple, any non-tuple          | Returns true if second argument exists in first tuple argument. |
+//! | `div`                | 2               | Int, Int                      | Divides the first integer by the second and truncates the result towards zero. Errors on float arguments and a zero divisor |
+//! | `div_floor`          | 2               | Int, Int                      | Divides the first integer by the second and rounds the result towards negative infinity. Errors on float arguments and a zero divisor |
+//! | `mod_floor`          | 2               | Int, Int                      | Returns the remainder of `div_floor`, which has the sign of the divisor, unlike `%`. Errors on float arguments and a zero divisor |
+//! | `gcd`                | 2               | Int, Int                      | Returns the non-negative greatest common divisor of the arguments, which is zero if both are zero. Errors on float arguments and overflow |
+//! | `lcm`                | 2               | Int, Int                      | Returns the non-negative least common multiple of the arguments, which is zero if one of them is zero. Errors on float arguments and overflow |
+//! | `pow`                | 2               | Numeric, Numeric              | Raises the first argument to the power of the second. Two integers with a non-negative exponent yield an integer and error on overflow, otherwise the result is a float |
+//! | `if`                 | 3               | Boolean, Any, Any             | If the first argument is true, returns the second argument, otherwise, returns the third. Only the returned argument is evaluated |
+//! | `contains`           | 2               | Tuple, any non-tuple / String, String | Returns true if second argument exists in first tuple argument, or if the second string is a substring of the first string. |
 //! | `contains_any`       | 2               | Tuple, Tuple of any non-tuple | Returns true if one of the values in the second tuple argument exists in first tuple argument. |
-//! | `typeof`             | 1               | Any                           | returns "string", "float", "int", "boolean", "tuple", or "empty" depending on the type of the argument  |
+//! | `typeof`             | 1               | Any                           | returns "string", "float", "int", "boolean", "tuple", "map", or "empty" depending on the type of the argument  |
+//! | `int`                | 1               | String/Float/Int              | Parses a string as integer, or truncates a float towards zero. Fails for non-finite or out-of-range floats |
+//! | `float`              | 1               | String/Float/Int              | Parses a string as float, or converts an integer to a float |
+//! | `str`                | 1               | String/Float/Int/Boolean      | Returns the string representation of the argument. A string is returned unchanged, without quotes |
+//! | `format`             | 2               | Numeric, Int                  | Returns the string representation of the number with the given amount of fractional digits, like `format(3.14159, 2)` for `"3.14"`. Errors if the precision is negative |
+//! | `bool`               | 1               | String/Boolean                | Parses the string `"true"` or `"false"` as boolean |
 //! | `math::is_nan`       | 1               | Numeric                       | Returns true if the argument is the floating-point value NaN, false if it is another floating-point value, and throws an error if it is not a number  |
 //! | `math::is_finite`    | 1               | Numeric                       | Returns true if the argument is a finite floating-point number, false otherwise  |
 //! | `math::is_infinite`  | 1               | Numeric                       | Returns true if the argument is an infinite floating-point number, false otherwise  |
 //! | `math::is_normal`    | 1               | Numeric                       | Returns true if the argument is a floating-point number that is neither zero, infinite, [subnormal](https://en.wikipedia.org/wiki/Subnormal_number), or NaN, false otherwise  |
-//! | `math::ln`           | 1               | Numeric                       | Returns the natural logarithm of the number |
-//! | `math::log`          | 2               | Numeric, Numeric              | Returns the logarithm of the number with respect to an arbitrary base |
-//! | `math::log2`         | 1               | Numeric                       | Returns the base 2 logarithm of the number |
-//! | `math::log10`        | 1               | Numeric                       | Returns the base 10 logarithm of the number |
+//! | `math::ln`           | 1               | Numeric                       | Returns the natural logarithm of the number, which must be positive |
+//! | `math::log`          | 2               | Numeric, Numeric              | Returns the logarithm of the number with respect to an arbitrary base. The number and base must be positive, and the base must not be one |
+//! | `math::log2`         | 1               | Numeric                       | Returns the base 2 logarithm of the number, which must be positive |
+//! | `math::log10`        | 1               | Numeric                       | Returns the base 10 logarithm of the number, which must be positive |
 //! | `math::exp`          | 1               | Numeric                       | Returns `e^(number)`, (the exponential function) |
 //! | `math::exp2`         | 1               | Numeric                       | Returns `2^(number)` |
 //! | `math::pow`          | 2               | Numeric, Numeric              | Raises a number to the power of the other number |
@@ -372,11 +568,21 @@
 //! | `math::cbrt`         | 1               | Numeric                       | Returns the cube root of a number |
 //! | `math::hypot`        | 2               | Numeric                       | Calculates the length of the hypotenuse of a right-angle triangle given legs of length given by the two arguments |
 //! | `math::abs`          | 1               | Numeric                       | Returns the absolute value of a number |
+//! | `math::signum`       | 1               | Numeric                       | Returns -1, 0 or 1 depending on the sign of a number, as an integer if the number is an integer. Returns NaN for NaN |
+//! | `math::copysign`     | 2               | Numeric                       | Returns a float with the magnitude of the first argument and the sign of the second argument |
 //! | `str::regex_matches` | 2               | String, String                | Returns true if the first argument matches the regex in the second argument (Requires `regex_support` feature flag) |
 //! | `str::regex_replace` | 3               | String, String, String        | Returns the first argument with all matches of the regex in the second argument replaced by the third argument (Requires `regex_support` feature flag) |
 //! | `str::to_lowercase`  | 1               | String                        | Returns the lower-case version of the string |
 //! | `str::to_uppercase`  | 1               | String                        | Returns the upper-case version of the string |
 //! | `str::trim`          | 1               | String                        | Strips whitespace from the start and the end of the string |
+//! | `str::trim_start`    | 1               | String                        | Strips whitespace from the start of the string |
+//! | `str::trim_end`      | 1               | String                        | Strips whitespace from the end of the string |
+//! | `str::starts_with`   | 2               | String, String                | Returns true if the first string starts with the second string |
+//! | `str::ends_with`     | 2               | String, String                | Returns true if the first string ends with the second string |
+//! | `str::replace`       | 3               | String, String, String        | Returns the first string with all non-overlapping occurrences of the second string replaced by the third string. Errors if the second string is empty |
+//! | `str::split`         | 2               | String, String                | Returns a tuple of the parts of the first string that are separated by the second string. An empty first string yields a single empty part. Errors if the second string is empty |
+//! | `str::join`          | 2               | Tuple of String, String       | Concatenates the strings in the first argument, separated by the second argument. An empty tuple yields an empty string |
+//! | `str::substring`     | 3               | String, Int, Int              | Returns the substring of the first argument that starts at the character index given by the second argument and contains at most the amount of characters given by the third argument. Errors if the start is negative or beyond the end of the string, or if the amount is negative |
 //! | `str::from`          | >= 0            | Any                           | Returns passed value as string |
 //! | `bitand`             | 2               | Int                           | Computes the bitwise and of the given integers |
 //! | `bitor`              | 2               | Int                           | Computes the bitwise or of the given integers |
@@ -385,33 +591,51 @@
 //! | `shl`                | 2               | Int                           | Computes the given integer bitwise shifted left by the other given integer |
 //! | `shr`                | 2               | Int                           | Computes the given integer bitwise shifted right by the other given integer |
 //! | `random`             | 0               | Empty                         | Return a random float between 0 and 1. Requires the `rand` feature flag. |
+//! | `random_int`         | 2               | Int, Int                      | Returns a random integer between the two arguments, both inclusive. Errors if the first argument is greater than the second. Requires the `rand` feature flag. |
 //!
 //! The `min` and `max` functions can deal with a mixture of integer and floating point arguments.
 //! If the maximum or minimum is an integer, then an integer is returned.
 //! Otherwise, a float is returned.
 //!
+//! The `sum` and `product` functions return an integer if all arguments are integers, and a float otherwise.
+//! Like `min` and `max`, they accept either multiple arguments or a single tuple.
+//!
 //! The regex functions require the feature flag `regex_support`.
 //!
+//! The random functions require the feature flag `rand`.
+//! Unlike all other builtin functions, they are not deterministic, so evaluating the same expression twice may give different results.
+//! For reproducible results, for example in tests, define functions called `random` and `random_int` in the context that draw from a seeded random number generator, as functions in the context take precedence over builtin functions.
+//!
 //! ### Values
 //!
 //! Operators take values as arguments and produce values as results.
-//! Values can be booleans, integer or floating point numbers, strings, tuples or the empty type.
+//! Values can be booleans, integer or floating point numbers, strings, tuples, maps or the empty type.
 //! Values are denoted as displayed in the following table.
 //!
 //! | Value type | Example |
 //! |------------|---------|
-//! | `Value::String` | `"abc"`, `""`, `"a\"b\\c"` |
+//! | `Value::String` | `"abc"`, `""`, `"a\"b\\c"`, `'it\'s'` |
 //! | `Value::Boolean` | `true`, `false` |
-//! | `Value::Int` | `3`, `-9`, `0`, `135412` |
+//! | `Value::Int` | `3`, `-9`, `0`, `135412`, `0xFF`, `0o17`, `0b1010` |
 //! | `Value::Float` | `3.`, `.35`, `1.00`, `0.5`, `123.554`, `23e4`, `-2e-3`, `3.54e+2` |
 //! | `Value::Tuple` | `(3, 55.0, false, ())`, `(1, 2)` |
+//! | `Value::Map` | `{"a": 1, "b": (2, 3)}`, `{}` |
 //! | `Value::Empty` | `()` |
 //!
 //! Integers are internally represented as `i64`, and floating point numbers are represented as `f64`.
-//! Tuples are represented as `Vec<Value>` and empty values are not stored, but represented by Rust's unit type `()` where necessary.
+//! To do all arithmetic on literals with floats, like in a calculator, use `eval_with_float_literals` or `Node::convert_int_literals_to_floats`, which treat integer literals as floats, so `5 / 2` evaluates to `2.5`.
+//! Integers can be written in hexadecimal, octal or binary notation using the prefixes `0x`, `0o` and `0b`.
+//! Floats can be written in scientific notation with an `e` or `E` that has to be followed by the exponent digits.
+//! Underscores can be used to separate digits, like in `1_000_000` or `1.234_567`, but each underscore must be placed between two digits.
+//! Strings can be delimited by double quotes `"` or single quotes `'`, and only the delimiting quote needs to be escaped within the string, like in `"it's"` or `'say "hi"'`.
+//! Within strings, the escape sequences `\"`, `\'`, `\\`, `\n`, `\t` and `\r` are supported, any other escape sequence is an error.
+//! Tuples are represented as `Vec<Value>`, maps as `BTreeMap<String, Value>` and empty values are not stored, but represented by Rust's unit type `()` where necessary.
+//! Map literals consist of entries `key: value` separated by commas within curly braces.
+//! The keys must evaluate to strings, and if a key occurs multiple times, its last value is kept.
+//! Other elements, like the `1` in `{"a": 1, 1}`, yield an `EvalexprError::IllegalMapEntry`, and a conditional as key needs to be surrounded by parentheses.
 //!
 //! There exist type aliases for some of the types.
-//! They include `IntType`, `FloatType`, `TupleType` and `EmptyType`.
+//! They include `IntType`, `FloatType`, `TupleType`, `MapType` and `EmptyType`.
 //!
 //! Values can be constructed either directly or using the `From` trait.
 //! They can be decomposed using the `Value::as_[type]` methods.
@@ -458,6 +682,25 @@
 //! | `123` | no | Expression is interpreted as `Value::Int` |
 //! | `true` | no | Expression is interpreted as `Value::Bool` |
 //! | `.34` | no | Expression is interpreted as `Value::Float` |
+//! | `user.name` | yes | Variables may contain dots, so this is a single variable |
+//! | `in` | no | Expression is interpreted as the operator `in` |
+//!
+//! Since variables may contain dots, structured data can be accessed with compound identifiers like `user.name`.
+//! A context resolves such an identifier as a whole.
+//! The `HashMapContext` simply stores it as a key, while the `JsonContext` also resolves it as a path through nested objects.
+//! If the context does not contain the full identifier, but the part before the first dot is a variable holding a map, the rest is read from that map, so `user.name` then evaluates like `user["name"]`.
+//! If the identifier cannot be resolved, evaluation fails with `EvalexprError::VariableIdentifierNotFound` naming the full identifier:
+//!
+//! ```rust
+//! use evalexpr::*;
+//!
+//! let context = context_map! { "user.name" => "Ada", "user.age" => 36 }.unwrap(); // Do proper error handling here
+//! assert_eq!(eval_with_context("user.name + \" is \" + str::from(user.age)", &context), Ok(Value::from("Ada is 36")));
+//! assert_eq!(
+//!     eval_with_context("user.email", &context),
+//!     Err(EvalexprError::VariableIdentifierNotFound("user.email".into()))
+//! );
+//! ```
 //!
 //! Variables have a precedence of 200.
 //!
@@ -472,6 +715,14 @@
 //! If there is no value behind a function, it is interpreted as a variable instead.
 //! More specifically, a function needs to be followed by either an opening brace `(`, another literal, or a value.
 //! While not including special support for multi-valued functions, they can be realized by requiring a single tuple argument.
+//! Alternatively, a function created with `Function::new_with_arguments` receives its arguments as a slice.
+//! A comma-separated list in the parentheses of a call, like `f(1, 2)`, passes each element as a separate argument, and `f()` passes no arguments.
+//! Any other argument is passed as a single argument, even if it is a tuple.
+//! So `f((1, 2))` passes one tuple argument, and so does `f(t)` if the variable `t` holds a tuple.
+//! Functions created with `Function::new` and the builtin functions cannot distinguish the two cases, as both pass the tuple `(1, 2)` to them.
+//!
+//! Functions that need mutable state shared across calls, like a counter or an accumulator, can be implemented by a custom context instead.
+//! When evaluating with a mutable context, like with `eval_with_context_mut`, function calls go through `ContextWithMutableVariables::call_function_mut`, which receives the context mutably alongside the arguments.
 //!
 //! Be aware that functions need to verify the types of values that are passed to them.
 //! The `error` module contains some shortcuts for verification, and error types for passing a wrong value type.
@@ -491,6 +742,38 @@
 //!
 //! Functions have a precedence of 190.
 //!
+//! ### Error Positions
+//!
+//! Errors caused by a malformed expression, like a missing operand or an unmatched parenthesis, are wrapped into `EvalexprError::AtPosition`.
+//! It contains the offset in characters of the token that caused the error, which can also be retrieved with `EvalexprError::position`.
+//! An opening parenthesis, bracket or curly brace that is never closed is reported at its own position.
+//!
+//! ```rust
+//! use evalexpr::*;
+//!
+//! let error = eval("1 + * 2").unwrap_err();
+//! assert_eq!(error.position(), Some(4)); // The position of `*`
+//! assert_eq!(eval("(1 + 2) * (3").unwrap_err().position(), Some(10)); // The position of the second `(`
+//! ```
+//!
+//! ### `no_std`
+//!
+//! The crate supports `no_std` environments with an allocator by disabling the default feature `std`:
+//!
+//! ```toml
+//! [dependencies]
+//! evalexpr = {version = "10", default-features = false}
+//! ```
+//!
+//! Without `std`, the following functionality is not available:
+//!
+//! * The builtin functions that require floating point math from the standard library, like `math::ln`, `math::sin`, `floor` or `round`.
+//! * Exponentiation with float operands or negative exponents, which returns `EvalexprError::ExponentiationError` instead.
+//! * The implementation of `std::error::Error` for `EvalexprError`.
+//! * The feature flags `serde_support`, `regex_support` and `json_support`, which enable `std` automatically.
+//!
+//! The `HashMapContext` is backed by a `BTreeMap` instead of a `HashMap`, so its variables are iterated in sorted order.
+//!
 //! ### [Serde](https://serde.rs)
 //!
 //! To use this crate with serde, the `serde_support` feature flag has to be set.
@@ -527,9 +810,29 @@
 //!
 //! With `serde`, expressions can be integrated into arbitrarily complex data.
 //!
-//! The crate also implements `Serialize` and `Deserialize` for the `HashMapContext`,
+//! The crate also implements `Serialize` and `Deserialize` for the `HashMapContext` and the `BTreeMapContext`,
 //! but note that only the variables get (de)serialized, not the functions.
 //!
+//! `Value` implements `Serialize` and `Deserialize` as well, mapping each variant to its natural counterpart in the serde data model.
+//! For example in JSON, `Value::Int(3)` becomes `3`, `Value::Float(3.0)` becomes `3.0`, tuples become arrays, maps become objects and `Value::Empty` becomes `null`.
+//!
+//! ### JSON
+//!
+//! With the `json_support` feature flag, the `JsonContext` reads the values of variables from a JSON object of the `serde_json` crate.
+//! Identifiers with dots like `user.age` are looked up as paths through nested objects, and objects are read as maps:
+//!
+//! ```rust
+//! # #[cfg(feature = "json_support")] {
+//! use evalexpr::*;
+//!
+//! let json = serde_json::json!({ "user": { "name": "Ada", "age": 36 }, "tags": ["a", "b"] });
+//! let context = JsonContext::new(json);
+//! assert_eq!(eval_with_context("user.age >= 18", &context), Ok(Value::from(true)));
+//! assert_eq!(eval_with_context("len(tags)", &context), Ok(Value::from(2)));
+//! assert_eq!(eval_with_context("user[\"name\"]", &context), Ok(Value::from("Ada")));
+//! # }
+//! ```
+//!
 //! ## License
 //!
 //! This crate is primarily distributed under the terms of the MIT license.
@@ -538,6 +841,9 @@
 
 #![deny(missing_docs)]
 #![forbid(unsafe_code)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 
 #[cfg(feature = "regex_support")]
 extern crate regex;
@@ -545,24 +851,35 @@ extern crate regex;
 extern crate ron;
 #[cfg(feature = "serde_support")]
 extern crate serde;
+#[cfg(feature = "json_support")]
+extern crate serde_json;
 #[cfg(feature = "serde_support")]
 #[macro_use]
 extern crate serde_derive;
 
+#[cfg(feature = "json_support")]
+pub use crate::context::JsonContext;
 pub use crate::{
+    cache::ExpressionCache,
     context::{
-        Context, ContextWithMutableFunctions, ContextWithMutableVariables, EmptyContext,
-        EmptyContextWithBuiltinFunctions, HashMapContext, IterateVariablesContext,
+        BTreeMapContext, CombinedContext, Context, ContextWithMutableFunctions,
+        ContextWithMutableVariables, EmptyContext, EmptyContextWithBuiltinFunctions, FnContext,
+        FunctionsDisabledContext, GetFunctionContext, HashMapContext, HashMapContextBuilder,
+        IterateVariablesContext,
     },
     error::{EvalexprError, EvalexprResult},
     function::Function,
     interface::*,
     operator::Operator,
-    token::PartialToken,
-    tree::Node,
-    value::{value_type::ValueType, EmptyType, FloatType, IntType, TupleType, Value, EMPTY_VALUE},
+    token::{PartialToken, Token},
+    tree::{Node, DEFAULT_MAX_EVALUATION_DEPTH},
+    value::{
+        value_type::ValueType, EmptyType, FloatType, IntType, MapType, TupleType, Value,
+        EMPTY_VALUE,
+    },
 };
 
+mod cache;
 mod context;
 pub mod error;
 #[cfg(feature = "serde_support")]
@@ -1,6 +1,6 @@
 #![cfg(not(tarpaulin_include))]
 
-use std::fmt::{Display, Error, Formatter};
+use core::fmt::{Display, Error, Formatter};
 
 use crate::operator::*;
 
@@ -26,6 +26,22 @@ impl Display for Operator {
             And => write!(f, "&&"),
             Or => write!(f, "||"),
             Not => write!(f, "!"),
+            In => write!(f, "in"),
+            ChainedComparison { comparisons } => {
+                for (index, comparison) in comparisons.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, " ")?;
+                    }
+                    write!(f, "{}", comparison)?;
+                }
+                Ok(())
+            },
+
+            BitAnd => write!(f, "&"),
+            BitOr => write!(f, "|"),
+            BitXor => write!(f, "^^"),
+            Shl => write!(f, "<<"),
+            Shr => write!(f, ">>"),
 
             Assign => write!(f, " = "),
             AddAssign => write!(f, " += "),
@@ -37,8 +53,14 @@ impl Display for Operator {
             AndAssign => write!(f, " &&= "),
             OrAssign => write!(f, " ||= "),
 
+            Conditional => write!(f, " ? "),
+            ConditionalElse => write!(f, " : "),
+
+            Index => write!(f, "[]"),
+
             Tuple => write!(f, ", "),
             Chain => write!(f, "; "),
+            Map => write!(f, "{{}}"),
 
             Const { value } => write!(f, "{}", value),
             VariableIdentifierWrite { identifier } | VariableIdentifierRead { identifier } => {
@@ -1,6 +1,16 @@
-use crate::function::builtin::builtin_function;
-
-use crate::{context::Context, error::*, value::Value, ContextWithMutableVariables};
+use crate::function::{
+    builtin::{builtin_function, call_contextual_builtin_function},
+    pack_arguments,
+};
+use alloc::{string::String, vec, vec::Vec};
+use core::{cmp::Ordering, convert::TryFrom};
+
+use crate::{
+    context::Context,
+    error::*,
+    value::{value_type::ValueType, MapType, Value},
+    ContextWithMutableVariables,
+};
 
 mod display;
 
@@ -44,6 +54,26 @@ pub enum Operator {
     Or,
     /// A binary logical not operator.
     Not,
+    /// A binary membership operator `a in b`.
+    /// It tests if the tuple `b` contains the element `a`, if the string `b` contains the substring `a`, or if the map `b` contains the key `a`.
+    In,
+    /// An n-ary chain of ordering comparisons like `a < b <= c`, which means `a < b && b <= c`.
+    /// Its arguments are the operands, and each operand is evaluated at most once.
+    ChainedComparison {
+        /// The comparisons between each two adjacent operands, each one of `Gt`, `Lt`, `Geq` and `Leq`.
+        comparisons: Vec<Operator>,
+    },
+
+    /// A binary bitwise and operator.
+    BitAnd,
+    /// A binary bitwise or operator.
+    BitOr,
+    /// A binary bitwise xor operator.
+    BitXor,
+    /// A binary bitwise left shift operator.
+    Shl,
+    /// A binary bitwise right shift operator.
+    Shr,
 
     /// A binary assignment operator.
     Assign,
@@ -64,8 +94,22 @@ pub enum Operator {
     /// A binary or-assign operator.
     OrAssign,
 
+    /// A binary conditional operator `?`.
+    /// Its first argument is the condition, and its second argument is the result if the condition is true.
+    Conditional,
+    /// A binary conditional-else operator `:`.
+    /// Its first argument must be a `Conditional`, and its second argument is the result if the condition is false.
+    ConditionalElse,
+
+    /// A binary index operator `tuple[index]` or `map[key]`.
+    /// Its first argument is the indexed tuple or map, and its second argument is the index or key.
+    Index,
+
     /// An n-ary tuple constructor.
     Tuple,
+    /// An n-ary map constructor `{key: value, ...}`.
+    /// Its arguments are alternately the keys and the values of the entries.
+    Map,
     /// An n-ary subexpression chain.
     Chain,
 
@@ -110,7 +154,21 @@ impl Operator {
 
     /// Returns the precedence of the operator.
     /// A high precedence means that the operator has priority to be deeper in the tree.
-    pub(crate) const fn precedence(&self) -> i32 {
+    ///
+    /// These are the precedences the parser uses to group the operators of an expression,
+    /// so tools like syntax highlighters can reproduce its grouping.
+    /// Note that the token `-` is parsed as either `Operator::Sub` or `Operator::Neg`, depending on whether it has a left operand.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use evalexpr::*;
+    ///
+    /// assert_eq!(Operator::Mul.precedence(), 100);
+    /// assert!(Operator::Mul.precedence() > Operator::Add.precedence());
+    /// assert!(Operator::Neg.precedence() > Operator::Sub.precedence());
+    /// ```
+    pub const fn precedence(&self) -> i32 {
         use crate::operator::Operator::*;
         match self {
             RootNode => 200,
@@ -120,17 +178,29 @@ impl Operator {
             Mul | Div | Mod => 100,
             Exp => 120,
 
-            Eq | Neq | Gt | Lt | Geq | Leq => 80,
+            Eq | Neq | Gt | Lt | Geq | Leq | In | ChainedComparison { .. } => 80,
             And => 75,
             Or => 70,
             Not => 110,
 
+            BitAnd => 79,
+            BitXor => 78,
+            BitOr => 77,
+            Shl | Shr => 90,
+
             Assign | AddAssign | SubAssign | MulAssign | DivAssign | ModAssign | ExpAssign
             | AndAssign | OrAssign => 50,
 
+            Conditional => 61,
+            ConditionalElse => 60,
+
+            Index => 180,
+
             Tuple => 40,
             Chain => 0,
 
+            Map => 200,
+
             Const { .. } => 200,
             VariableIdentifierWrite { .. } | VariableIdentifierRead { .. } => 200,
             FunctionIdentifier { .. } => 190,
@@ -140,9 +210,23 @@ impl Operator {
     /// Returns true if chains of operators with the same precedence as this one should be evaluated left-to-right,
     /// and false if they should be evaluated right-to-left.
     /// Left-to-right chaining has priority if operators with different order but same precedence are chained.
-    pub(crate) const fn is_left_to_right(&self) -> bool {
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use evalexpr::*;
+    ///
+    /// // `1 - 2 - 3` is grouped as `(1 - 2) - 3`.
+    /// assert!(Operator::Sub.is_left_to_right());
+    /// // `2 ^ 3 ^ 2` is grouped as `2 ^ (3 ^ 2)`.
+    /// assert!(!Operator::Exp.is_left_to_right());
+    /// ```
+    pub const fn is_left_to_right(&self) -> bool {
         use crate::operator::Operator::*;
-        !matches!(self, Assign | FunctionIdentifier { .. })
+        !matches!(
+            self,
+            Assign | Exp | Conditional | ConditionalElse | FunctionIdentifier { .. }
+        )
     }
 
     /// Returns true if chains of this operator should be flattened into one operator with many arguments.
@@ -161,10 +245,11 @@ impl Operator {
     pub(crate) const fn max_argument_amount(&self) -> Option<usize> {
         use crate::operator::Operator::*;
         match self {
-            Add | Sub | Mul | Div | Mod | Exp | Eq | Neq | Gt | Lt | Geq | Leq | And | Or
+            Add | Sub | Mul | Div | Mod | Exp | Eq | Neq | Gt | Lt | Geq | Leq | In | And | Or
+            | BitAnd | BitOr | BitXor | Shl | Shr | Conditional | ConditionalElse | Index
             | Assign | AddAssign | SubAssign | MulAssign | DivAssign | ModAssign | ExpAssign
             | AndAssign | OrAssign => Some(2),
-            Tuple | Chain => None,
+            Tuple | Chain | Map | ChainedComparison { .. } => None,
             Not | Neg | RootNode => Some(1),
             Const { .. } => Some(0),
             VariableIdentifierWrite { .. } | VariableIdentifierRead { .. } => Some(0),
@@ -172,26 +257,81 @@ impl Operator {
         }
     }
 
+    /// Returns true if this operator is an ordering comparison that can be part of a `ChainedComparison`.
+    pub(crate) const fn is_ordering_comparison(&self) -> bool {
+        use crate::operator::Operator::*;
+        matches!(self, Gt | Lt | Geq | Leq)
+    }
+
     /// Returns true if this operator is unary, i.e. it requires exactly one argument.
     pub(crate) fn is_unary(&self) -> bool {
         self.max_argument_amount() == Some(1) && *self != Operator::RootNode
     }
 
+    /// Returns the result of this operator if it is already determined by the given arguments,
+    /// such that the remaining arguments do not need to be evaluated.
+    pub(crate) fn short_circuit(&self, arguments: &[Value]) -> Option<EvalexprResult<Value>> {
+        use crate::operator::Operator::*;
+        match (self, arguments) {
+            (And, [Value::Boolean(false)]) => Some(Ok(Value::Boolean(false))),
+            (Or, [Value::Boolean(true)]) => Some(Ok(Value::Boolean(true))),
+            // Each comparison is checked as soon as its right operand is known.
+            // The chain is false as soon as two operands are not in order, and true once the last operand is in order.
+            (ChainedComparison { comparisons }, [.., a, b]) => {
+                match comparisons.get(arguments.len() - 2)?.compare(a, b) {
+                    Ok(true) if arguments.len() == comparisons.len() + 1 => {
+                        Some(Ok(Value::Boolean(true)))
+                    },
+                    Ok(true) => None,
+                    Ok(false) => Some(Ok(Value::Boolean(false))),
+                    Err(error) => Some(Err(error)),
+                }
+            },
+            _ => None,
+        }
+    }
+
+    /// Compares the two values with this operator, which must be one of `Gt`, `Lt`, `Geq` and `Leq`.
+    /// Strings are compared lexicographically, and numbers as integers if both are integers, otherwise as floats.
+    fn compare(&self, a: &Value, b: &Value) -> EvalexprResult<bool> {
+        use crate::operator::Operator::*;
+        expect_number_or_string(a)?;
+        expect_number_or_string(b)?;
+
+        let ordering = match (a, b) {
+            (Value::String(a), Value::String(b)) => a.partial_cmp(b),
+            (Value::Int(a), Value::Int(b)) => a.partial_cmp(b),
+            _ => match (a.as_number(), b.as_number()) {
+                (Ok(a), Ok(b)) => a.partial_cmp(&b),
+                _ => {
+                    return Err(EvalexprError::wrong_type_combination(
+                        self.clone(),
+                        vec![a.into(), b.into()],
+                    ))
+                },
+            },
+        };
+
+        // Comparisons with NaN are always false.
+        Ok(ordering.map_or(false, |ordering| match self {
+            Gt => ordering == Ordering::Greater,
+            Lt => ordering == Ordering::Less,
+            Geq => ordering != Ordering::Less,
+            Leq => ordering != Ordering::Greater,
+            _ => unreachable!("only ordering comparisons compare values"),
+        }))
+    }
+
     /// Evaluates the operator with the given arguments and context.
+    /// The arguments are taken by value, such that operators like tuples can reuse them without cloning.
     pub(crate) fn eval<C: Context>(
         &self,
-        arguments: &[Value],
+        mut arguments: Vec<Value>,
         context: &C,
     ) -> EvalexprResult<Value> {
         use crate::operator::Operator::*;
         match self {
-            RootNode => {
-                if let Some(first) = arguments.first() {
-                    Ok(first.clone())
-                } else {
-                    Ok(Value::Empty)
-                }
-            },
+            RootNode => Ok(arguments.into_iter().next().unwrap_or(Value::Empty)),
             Add => {
                 expect_operator_argument_amount(arguments.len(), 2)?;
                 expect_number_or_string(&arguments[0])?;
@@ -219,7 +359,7 @@ impl Operator {
                     Err(EvalexprError::wrong_type_combination(
                         self.clone(),
                         vec![
-                            arguments.get(0).unwrap().into(),
+                            arguments.first().unwrap().into(),
                             arguments.get(1).unwrap().into(),
                         ],
                     ))
@@ -329,9 +469,29 @@ impl Operator {
                 arguments[0].as_number()?;
                 arguments[1].as_number()?;
 
-                Ok(Value::Float(
-                    arguments[0].as_number()?.powf(arguments[1].as_number()?),
-                ))
+                match (arguments[0].as_int(), arguments[1].as_int()) {
+                    (Ok(a), Ok(b)) if b >= 0 => {
+                        let result = u32::try_from(b).ok().and_then(|b| a.checked_pow(b));
+                        if let Some(result) = result {
+                            Ok(Value::Int(result))
+                        } else {
+                            Err(EvalexprError::exponentiation_error(
+                                arguments[0].clone(),
+                                arguments[1].clone(),
+                            ))
+                        }
+                    },
+                    #[cfg(feature = "std")]
+                    _ => Ok(Value::Float(
+                        arguments[0].as_number()?.powf(arguments[1].as_number()?),
+                    )),
+                    // Float exponentiation requires the standard library.
+                    #[cfg(not(feature = "std"))]
+                    _ => Err(EvalexprError::exponentiation_error(
+                        arguments[0].clone(),
+                        arguments[1].clone(),
+                    )),
+                }
             },
             Eq => {
                 expect_operator_argument_amount(arguments.len(), 2)?;
@@ -343,65 +503,35 @@ impl Operator {
 
                 Ok(Value::Boolean(arguments[0] != arguments[1]))
             },
-            Gt => {
+            In => {
                 expect_operator_argument_amount(arguments.len(), 2)?;
-                expect_number_or_string(&arguments[0])?;
-                expect_number_or_string(&arguments[1])?;
 
-                if let (Ok(a), Ok(b)) = (arguments[0].as_string(), arguments[1].as_string()) {
-                    Ok(Value::Boolean(a > b))
-                } else if let (Ok(a), Ok(b)) = (arguments[0].as_int(), arguments[1].as_int()) {
-                    Ok(Value::Boolean(a > b))
-                } else {
-                    Ok(Value::Boolean(
-                        arguments[0].as_number()? > arguments[1].as_number()?,
-                    ))
-                }
-            },
-            Lt => {
-                expect_operator_argument_amount(arguments.len(), 2)?;
-                expect_number_or_string(&arguments[0])?;
-                expect_number_or_string(&arguments[1])?;
-
-                if let (Ok(a), Ok(b)) = (arguments[0].as_string(), arguments[1].as_string()) {
-                    Ok(Value::Boolean(a < b))
-                } else if let (Ok(a), Ok(b)) = (arguments[0].as_int(), arguments[1].as_int()) {
-                    Ok(Value::Boolean(a < b))
-                } else {
-                    Ok(Value::Boolean(
-                        arguments[0].as_number()? < arguments[1].as_number()?,
-                    ))
+                match (&arguments[0], &arguments[1]) {
+                    (element, Value::Tuple(tuple)) => Ok(Value::Boolean(tuple.contains(element))),
+                    (Value::String(a), Value::String(b)) => {
+                        Ok(Value::Boolean(b.contains(a.as_str())))
+                    },
+                    (Value::String(key), Value::Map(map)) => {
+                        Ok(Value::Boolean(map.contains_key(key)))
+                    },
+                    (a, b) => Err(EvalexprError::wrong_type_combination(
+                        self.clone(),
+                        vec![a.into(), b.into()],
+                    )),
                 }
             },
-            Geq => {
-                expect_operator_argument_amount(arguments.len(), 2)?;
-                expect_number_or_string(&arguments[0])?;
-                expect_number_or_string(&arguments[1])?;
-
-                if let (Ok(a), Ok(b)) = (arguments[0].as_string(), arguments[1].as_string()) {
-                    Ok(Value::Boolean(a >= b))
-                } else if let (Ok(a), Ok(b)) = (arguments[0].as_int(), arguments[1].as_int()) {
-                    Ok(Value::Boolean(a >= b))
-                } else {
-                    Ok(Value::Boolean(
-                        arguments[0].as_number()? >= arguments[1].as_number()?,
-                    ))
+            ChainedComparison { comparisons } => {
+                expect_operator_argument_amount(arguments.len(), comparisons.len() + 1)?;
+                for (comparison, operands) in comparisons.iter().zip(arguments.windows(2)) {
+                    if !comparison.compare(&operands[0], &operands[1])? {
+                        return Ok(Value::Boolean(false));
+                    }
                 }
+                Ok(Value::Boolean(true))
             },
-            Leq => {
+            Gt | Lt | Geq | Leq => {
                 expect_operator_argument_amount(arguments.len(), 2)?;
-                expect_number_or_string(&arguments[0])?;
-                expect_number_or_string(&arguments[1])?;
-
-                if let (Ok(a), Ok(b)) = (arguments[0].as_string(), arguments[1].as_string()) {
-                    Ok(Value::Boolean(a <= b))
-                } else if let (Ok(a), Ok(b)) = (arguments[0].as_int(), arguments[1].as_int()) {
-                    Ok(Value::Boolean(a <= b))
-                } else {
-                    Ok(Value::Boolean(
-                        arguments[0].as_number()? <= arguments[1].as_number()?,
-                    ))
-                }
+                Ok(Value::Boolean(self.compare(&arguments[0], &arguments[1])?))
             },
             And => {
                 expect_operator_argument_amount(arguments.len(), 2)?;
@@ -423,15 +553,104 @@ impl Operator {
 
                 Ok(Value::Boolean(!a))
             },
+            BitAnd => {
+                expect_operator_argument_amount(arguments.len(), 2)?;
+                let a = arguments[0].as_int()?;
+                let b = arguments[1].as_int()?;
+
+                Ok(Value::Int(a & b))
+            },
+            BitOr => {
+                expect_operator_argument_amount(arguments.len(), 2)?;
+                let a = arguments[0].as_int()?;
+                let b = arguments[1].as_int()?;
+
+                Ok(Value::Int(a | b))
+            },
+            BitXor => {
+                expect_operator_argument_amount(arguments.len(), 2)?;
+                let a = arguments[0].as_int()?;
+                let b = arguments[1].as_int()?;
+
+                Ok(Value::Int(a ^ b))
+            },
+            Shl => {
+                expect_operator_argument_amount(arguments.len(), 2)?;
+                let a = arguments[0].as_int()?;
+                let b = arguments[1].as_int()?;
+
+                let result = u32::try_from(b).ok().and_then(|b| a.checked_shl(b));
+                if let Some(result) = result {
+                    Ok(Value::Int(result))
+                } else {
+                    Err(EvalexprError::shift_left_error(
+                        arguments[0].clone(),
+                        arguments[1].clone(),
+                    ))
+                }
+            },
+            Shr => {
+                expect_operator_argument_amount(arguments.len(), 2)?;
+                let a = arguments[0].as_int()?;
+                let b = arguments[1].as_int()?;
+
+                let result = u32::try_from(b).ok().and_then(|b| a.checked_shr(b));
+                if let Some(result) = result {
+                    Ok(Value::Int(result))
+                } else {
+                    Err(EvalexprError::shift_right_error(
+                        arguments[0].clone(),
+                        arguments[1].clone(),
+                    ))
+                }
+            },
             Assign | AddAssign | SubAssign | MulAssign | DivAssign | ModAssign | ExpAssign
             | AndAssign | OrAssign => Err(EvalexprError::ContextNotMutable),
-            Tuple => Ok(Value::Tuple(arguments.into())),
+            Conditional => Err(EvalexprError::UnmatchedQuestionMark),
+            ConditionalElse => Err(EvalexprError::UnmatchedColon),
+            Index => {
+                expect_operator_argument_amount(arguments.len(), 2)?;
+                let mut tuple = match arguments.swap_remove(0) {
+                    Value::Tuple(tuple) => tuple,
+                    Value::Map(mut map) => {
+                        // `swap_remove` moved the key to the front.
+                        let key = arguments[0].as_string()?;
+                        return map.remove(&key).ok_or(EvalexprError::KeyNotFound(key));
+                    },
+                    value => {
+                        return Err(EvalexprError::type_error(
+                            value,
+                            vec![ValueType::Tuple, ValueType::Map],
+                        ))
+                    },
+                };
+                // `swap_remove` moved the index to the front.
+                let index = arguments[0].as_int()?;
+
+                // Negative indices do not count from the end, but are out of bounds.
+                match usize::try_from(index) {
+                    Ok(position) if position < tuple.len() => Ok(tuple.swap_remove(position)),
+                    _ => Err(EvalexprError::index_out_of_bounds(index, tuple.len())),
+                }
+            },
+            Tuple => Ok(Value::Tuple(arguments)),
+            Map => {
+                let mut map = MapType::new();
+                let mut arguments = arguments.into_iter();
+                while let (Some(key), Some(value)) = (arguments.next(), arguments.next()) {
+                    match key {
+                        Value::String(key) => map.insert(key, value),
+                        key => return Err(EvalexprError::expected_string(key)),
+                    };
+                }
+                Ok(Value::Map(map))
+            },
             Chain => {
                 if arguments.is_empty() {
                     return Err(EvalexprError::wrong_operator_argument_amount(0, 1));
                 }
 
-                Ok(arguments.last().cloned().unwrap_or(Value::Empty))
+                Ok(arguments.pop().unwrap_or(Value::Empty))
             },
             Const { value } => {
                 expect_operator_argument_amount(arguments.len(), 0)?;
@@ -446,7 +665,7 @@ impl Operator {
             VariableIdentifierRead { identifier } => {
                 expect_operator_argument_amount(arguments.len(), 0)?;
 
-                if let Some(value) = context.get_value(identifier).cloned() {
+                if let Some(value) = read_variable(context, identifier) {
                     Ok(value)
                 } else {
                     Err(EvalexprError::VariableIdentifierNotFound(
@@ -454,32 +673,15 @@ impl Operator {
                     ))
                 }
             },
-            FunctionIdentifier { identifier } => {
-                expect_operator_argument_amount(arguments.len(), 1)?;
-                let arguments = &arguments[0];
-
-                match context.call_function(identifier, arguments) {
-                    Err(EvalexprError::FunctionIdentifierNotFound(_))
-                        if !context.are_builtin_functions_disabled() =>
-                    {
-                        if let Some(builtin_function) = builtin_function(identifier) {
-                            builtin_function.call(arguments)
-                        } else {
-                            Err(EvalexprError::FunctionIdentifierNotFound(
-                                identifier.clone(),
-                            ))
-                        }
-                    },
-                    result => result,
-                }
-            },
+            // The arguments are the argument list of the call, see `Node::argument_nodes`.
+            FunctionIdentifier { identifier } => call_function(context, identifier, arguments),
         }
     }
 
     /// Evaluates the operator with the given arguments and mutable context.
     pub(crate) fn eval_mut<C: ContextWithMutableVariables>(
         &self,
-        arguments: &[Value],
+        mut arguments: Vec<Value>,
         context: &mut C,
     ) -> EvalexprResult<Value> {
         use crate::operator::Operator::*;
@@ -487,7 +689,7 @@ impl Operator {
             Assign => {
                 expect_operator_argument_amount(arguments.len(), 2)?;
                 let target = arguments[0].as_string()?;
-                context.set_value(target, arguments[1].clone())?;
+                context.set_value(target, arguments.swap_remove(1))?;
 
                 Ok(Value::Empty)
             },
@@ -499,18 +701,18 @@ impl Operator {
                 let left_value = Operator::VariableIdentifierRead {
                     identifier: target.clone(),
                 }
-                .eval(&Vec::new(), context)?;
-                let arguments = vec![left_value, arguments[1].clone()];
+                .eval(Vec::new(), context)?;
+                let arguments = vec![left_value, arguments.swap_remove(1)];
 
                 let result = match self {
-                    AddAssign => Operator::Add.eval(&arguments, context),
-                    SubAssign => Operator::Sub.eval(&arguments, context),
-                    MulAssign => Operator::Mul.eval(&arguments, context),
-                    DivAssign => Operator::Div.eval(&arguments, context),
-                    ModAssign => Operator::Mod.eval(&arguments, context),
-                    ExpAssign => Operator::Exp.eval(&arguments, context),
-                    AndAssign => Operator::And.eval(&arguments, context),
-                    OrAssign => Operator::Or.eval(&arguments, context),
+                    AddAssign => Operator::Add.eval(arguments, context),
+                    SubAssign => Operator::Sub.eval(arguments, context),
+                    MulAssign => Operator::Mul.eval(arguments, context),
+                    DivAssign => Operator::Div.eval(arguments, context),
+                    ModAssign => Operator::Mod.eval(arguments, context),
+                    ExpAssign => Operator::Exp.eval(arguments, context),
+                    AndAssign => Operator::And.eval(arguments, context),
+                    OrAssign => Operator::Or.eval(arguments, context),
                     _ => unreachable!(
                         "Forgot to add a match arm for an assign operation: {}",
                         self
@@ -520,7 +722,65 @@ impl Operator {
 
                 Ok(Value::Empty)
             },
+            // The arguments are the argument list of the call, see `Node::argument_nodes`.
+            FunctionIdentifier { identifier } => {
+                let result = context.call_function_mut(identifier, &arguments);
+                fall_back_to_builtin_function(result, identifier, arguments, &*context)
+            },
             _ => self.eval(arguments, context),
         }
     }
 }
+
+/// Calls the function with the given identifier with the given arguments, like a function call in an expression.
+/// If the context does not contain the function, the builtin function with the given identifier is called, unless builtin functions are disabled.
+pub(crate) fn call_function<C: Context>(
+    context: &C,
+    identifier: &str,
+    arguments: Vec<Value>,
+) -> EvalexprResult<Value> {
+    let result = context.call_function_with_arguments(identifier, &arguments);
+    fall_back_to_builtin_function(result, identifier, arguments, context)
+}
+
+/// Returns the result of calling a function of the context,
+/// unless the context does not contain the function and builtin functions are enabled.
+/// In that case, the builtin function with the given identifier is called with the given arguments instead.
+fn fall_back_to_builtin_function<C: Context>(
+    result: EvalexprResult<Value>,
+    identifier: &str,
+    arguments: Vec<Value>,
+    context: &C,
+) -> EvalexprResult<Value> {
+    match result {
+        Err(EvalexprError::FunctionIdentifierNotFound(_))
+            if !context.are_builtin_functions_disabled() =>
+        {
+            let argument = pack_arguments(arguments);
+            if let Some(result) = call_contextual_builtin_function(identifier, &argument, context) {
+                result
+            } else if let Some(builtin_function) = builtin_function(identifier) {
+                builtin_function.call(&argument)
+            } else {
+                Err(EvalexprError::FunctionIdentifierNotFound(identifier.into()))
+            }
+        },
+        result => result,
+    }
+}
+
+/// Reads the value of the variable with the given identifier from the context.
+/// If the context does not contain the identifier, but it has the form `variable.key`,
+/// the value of `key` in the map stored in `variable` is read instead, where `key` may again contain dots to access nested maps.
+fn read_variable<C: Context>(context: &C, identifier: &str) -> Option<Value> {
+    context.get_owned_value(identifier).or_else(|| {
+        let (variable, keys) = identifier.split_once('.')?;
+        keys.split('.').try_fold(
+            context.get_owned_value(variable)?,
+            |value, key| match value {
+                Value::Map(mut map) => map.remove(key),
+                _ => None,
+            },
+        )
+    })
+}
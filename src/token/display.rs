@@ -1,4 +1,4 @@
-use std::fmt;
+use core::fmt;
 
 use crate::token::{PartialToken, Token};
 
@@ -23,11 +23,27 @@ impl fmt::Display for Token {
             And => write!(f, "&&"),
             Or => write!(f, "||"),
             Not => write!(f, "!"),
+            In => write!(f, "in"),
+
+            // Bitwise
+            Ampersand => write!(f, "&"),
+            VerticalBar => write!(f, "|"),
+            DoubleHat => write!(f, "^^"),
+            Shl => write!(f, "<<"),
+            Shr => write!(f, ">>"),
 
             // Precedence
             LBrace => write!(f, "("),
             RBrace => write!(f, ")"),
 
+            // Indexing
+            LBracket => write!(f, "["),
+            RBracket => write!(f, "]"),
+
+            // Maps
+            LCurlyBrace => write!(f, "{{"),
+            RCurlyBrace => write!(f, "}}"),
+
             // Assignment
             Assign => write!(f, "="),
             PlusAssign => write!(f, "+="),
@@ -42,6 +58,8 @@ impl fmt::Display for Token {
             // Special
             Comma => write!(f, ","),
             Semicolon => write!(f, ";"),
+            QuestionMark => write!(f, "?"),
+            Colon => write!(f, ":"),
 
             // Values => write!(f, ""), Variables and Functions
             Identifier(identifier) => identifier.fmt(f),
@@ -2,54 +2,126 @@ use crate::{
     error::{EvalexprError, EvalexprResult},
     value::{FloatType, IntType},
 };
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::num::ParseIntError;
 
 mod display;
 
+/// A token of an expression, as produced by [`tokenize`](crate::tokenize).
+///
+/// Tokens can be inspected and rewritten before they are parsed into an operator tree with [`build_operator_tree_from_tokens`](crate::build_operator_tree_from_tokens).
 #[derive(Clone, PartialEq, Debug)]
 pub enum Token {
     // Arithmetic
+    /// A plus sign '+'.
     Plus,
+    /// A minus sign '-', used both for subtraction and negation.
     Minus,
+    /// A star '*'.
     Star,
+    /// A slash '/'.
     Slash,
+    /// A percent sign '%'.
     Percent,
+    /// A hat '^'.
     Hat,
 
     // Logic
+    /// An equality sign '=='.
     Eq,
+    /// An inequality sign '!='.
     Neq,
+    /// A greater-than sign '>'.
     Gt,
+    /// A lower-than sign '<'.
     Lt,
+    /// A greater-than-or-equal sign '>='.
     Geq,
+    /// A lower-than-or-equal sign '<='.
     Leq,
+    /// A logical and '&&'.
     And,
+    /// A logical or '||'.
     Or,
+    /// A logical not '!'.
     Not,
+    /// A membership test 'in'.
+    In,
+
+    // Bitwise
+    /// An ampersand '&' for the bitwise and.
+    Ampersand,
+    /// A vertical bar '|' for the bitwise or.
+    VerticalBar,
+    /// A double hat '^^' for the bitwise xor.
+    DoubleHat,
+    /// A left shift '<<'.
+    Shl,
+    /// A right shift '>>'.
+    Shr,
 
     // Precedence
+    /// An opening parenthesis '('.
     LBrace,
+    /// A closing parenthesis ')'.
     RBrace,
 
+    // Indexing
+    /// An opening bracket '['.
+    LBracket,
+    /// A closing bracket ']'.
+    RBracket,
+
+    // Maps
+    /// An opening curly brace '{'.
+    LCurlyBrace,
+    /// A closing curly brace '}'.
+    RCurlyBrace,
+
     // Assignment
+    /// An assignment '='.
     Assign,
+    /// An addition assignment '+='.
     PlusAssign,
+    /// A subtraction assignment '-='.
     MinusAssign,
+    /// A multiplication assignment '*='.
     StarAssign,
+    /// A division assignment '/='.
     SlashAssign,
+    /// A modulo assignment '%='.
     PercentAssign,
+    /// An exponentiation assignment '^='.
     HatAssign,
+    /// A logical and assignment '&&='.
     AndAssign,
+    /// A logical or assignment '||='.
     OrAssign,
 
     // Special
+    /// A comma ',' that separates the elements of a tuple.
     Comma,
+    /// A semicolon ';' that separates the expressions of a chain.
     Semicolon,
+    /// A question mark '?' of a conditional.
+    QuestionMark,
+    /// A colon ':' of a conditional.
+    Colon,
 
     // Values, Variables and Functions
+    /// An identifier of a variable or function.
     Identifier(String),
+    /// A float literal.
     Float(FloatType),
+    /// An integer literal.
     Int(IntType),
+    /// A boolean literal.
     Boolean(bool),
+    /// A string literal, with its escape sequences already resolved.
     String(String),
 }
 
@@ -101,9 +173,18 @@ fn char_to_partial_token(c: char) -> PartialToken {
         '(' => PartialToken::Token(Token::LBrace),
         ')' => PartialToken::Token(Token::RBrace),
 
+        '[' => PartialToken::Token(Token::LBracket),
+        ']' => PartialToken::Token(Token::RBracket),
+
+        '{' => PartialToken::Token(Token::LCurlyBrace),
+        '}' => PartialToken::Token(Token::RCurlyBrace),
+
         ',' => PartialToken::Token(Token::Comma),
         ';' => PartialToken::Token(Token::Semicolon),
 
+        '?' => PartialToken::Token(Token::QuestionMark),
+        ':' => PartialToken::Token(Token::Colon),
+
         '=' => PartialToken::Eq,
         '!' => PartialToken::ExclamationMark,
         '>' => PartialToken::Gt,
@@ -141,12 +222,27 @@ impl Token {
             Token::And => false,
             Token::Or => false,
             Token::Not => false,
+            Token::In => false,
+
+            Token::Ampersand => false,
+            Token::VerticalBar => false,
+            Token::DoubleHat => false,
+            Token::Shl => false,
+            Token::Shr => false,
 
             Token::LBrace => true,
             Token::RBrace => false,
 
+            Token::LBracket => false,
+            Token::RBracket => false,
+
+            Token::LCurlyBrace => true,
+            Token::RCurlyBrace => false,
+
             Token::Comma => false,
             Token::Semicolon => false,
+            Token::QuestionMark => false,
+            Token::Colon => false,
 
             Token::Assign => false,
             Token::PlusAssign => false,
@@ -185,12 +281,27 @@ impl Token {
             Token::And => false,
             Token::Or => false,
             Token::Not => false,
+            Token::In => false,
+
+            Token::Ampersand => false,
+            Token::VerticalBar => false,
+            Token::DoubleHat => false,
+            Token::Shl => false,
+            Token::Shr => false,
 
             Token::LBrace => false,
             Token::RBrace => true,
 
+            Token::LBracket => false,
+            Token::RBracket => true,
+
+            Token::LCurlyBrace => false,
+            Token::RCurlyBrace => true,
+
             Token::Comma => false,
             Token::Semicolon => false,
+            Token::QuestionMark => false,
+            Token::Colon => false,
 
             Token::Assign => false,
             Token::PlusAssign => false,
@@ -232,7 +343,11 @@ impl Token {
 fn parse_escape_sequence<Iter: Iterator<Item = char>>(iter: &mut Iter) -> EvalexprResult<char> {
     match iter.next() {
         Some('"') => Ok('"'),
+        Some('\'') => Ok('\''),
         Some('\\') => Ok('\\'),
+        Some('n') => Ok('\n'),
+        Some('t') => Ok('\t'),
+        Some('r') => Ok('\r'),
         Some(c) => Err(EvalexprError::IllegalEscapeSequence(format!("\\{}", c))),
         None => Err(EvalexprError::IllegalEscapeSequence("\\".to_string())),
     }
@@ -241,38 +356,129 @@ fn parse_escape_sequence<Iter: Iterator<Item = char>>(iter: &mut Iter) -> Evalex
 /// Parses a string value from the given character iterator.
 ///
 /// The first character from the iterator is interpreted as first character of the string.
-/// The string is terminated by a double quote `"`.
-/// Occurrences of `"` within the string can be escaped with `\`.
+/// The string is terminated by the given delimiter, which is either a double quote `"` or a single quote `'`.
+/// Occurrences of the delimiter within the string can be escaped with `\`.
+/// Both `\"` and `\'` are accepted as escape sequences, regardless of the delimiter.
 /// The backslash needs to be escaped with another backslash `\`.
+/// Newlines, tabs and carriage returns can be written as `\n`, `\t` and `\r`.
+///
+/// If the string contains an illegal escape sequence, the string is still consumed up to its end,
+/// and the error of the first illegal escape sequence is returned.
 fn parse_string_literal<Iter: Iterator<Item = char>>(
     mut iter: &mut Iter,
+    delimiter: char,
 ) -> EvalexprResult<PartialToken> {
     let mut result = String::new();
+    let mut error = None;
 
     while let Some(c) = iter.next() {
         match c {
-            '"' => break,
-            '\\' => result.push(parse_escape_sequence(&mut iter)?),
+            c if c == delimiter => break,
+            '\\' => match parse_escape_sequence(&mut iter) {
+                Ok(c) => result.push(c),
+                Err(escape_error) => {
+                    error.get_or_insert(escape_error);
+                },
+            },
             c => result.push(c),
         }
     }
 
-    Ok(PartialToken::Token(Token::String(result)))
+    match error {
+        Some(error) => Err(error),
+        None => Ok(PartialToken::Token(Token::String(result))),
+    }
 }
 
-/// Converts a string to a vector of partial tokens.
-fn str_to_partial_tokens(string: &str) -> EvalexprResult<Vec<PartialToken>> {
-    let mut result = Vec::new();
-    let mut iter = string.chars().peekable();
+/// Parses an integer literal prefixed with `0x`, `0o` or `0b`.
+/// Returns `None` if the literal has none of these prefixes.
+fn parse_prefixed_int_literal(literal: &str) -> Option<Result<IntType, ParseIntError>> {
+    let radix = match literal.get(..2) {
+        Some("0x") => 16,
+        Some("0o") => 8,
+        Some("0b") => 2,
+        _ => return None,
+    };
+
+    Some(IntType::from_str_radix(&literal[2..], radix))
+}
 
-    while let Some(c) = iter.next() {
-        if c == '"' {
-            result.push(parse_string_literal(&mut iter)?);
+/// Removes the underscores used as digit separators from a literal that starts with a digit, like `1_000`.
+/// Underscores must be placed between two digits, otherwise an error is returned if the literal is a number.
+/// Literals that are not numbers are returned unchanged.
+fn remove_digit_separators(literal: &str) -> EvalexprResult<String> {
+    if !literal.starts_with(|c: char| c.is_ascii_digit()) || !literal.contains('_') {
+        return Ok(literal.to_string());
+    }
+
+    let stripped = literal.replace('_', "");
+    let is_radix_prefixed = parse_prefixed_int_literal(literal).is_some();
+    let chars: Vec<char> = literal.chars().collect();
+    let is_well_formed = !literal.ends_with('_')
+        && chars
+            .windows(3)
+            .enumerate()
+            .all(|(index, window)| match window {
+                [previous, '_', next] if is_radix_prefixed => {
+                    index >= 2 && previous.is_ascii_hexdigit() && next.is_ascii_hexdigit()
+                },
+                [previous, '_', next] => previous.is_ascii_digit() && next.is_ascii_digit(),
+                _ => true,
+            });
+
+    if is_well_formed {
+        Ok(stripped)
+    } else if parse_prefixed_int_literal(&stripped).is_some()
+        || stripped.parse::<FloatType>().is_ok()
+        || is_missing_exponent_digits(&stripped)
+    {
+        Err(EvalexprError::IllegalNumberLiteral(literal.to_string()))
+    } else {
+        Ok(literal.to_string())
+    }
+}
+
+/// Returns true if the literal is a number followed by an exponent marker without exponent digits, like `1.5e`.
+fn is_missing_exponent_digits(literal: &str) -> bool {
+    let starts_like_number = literal
+        .chars()
+        .next()
+        .map_or(false, |c| c.is_ascii_digit() || c == '.');
+
+    starts_like_number
+        && (literal.ends_with('e') || literal.ends_with('E'))
+        && literal[..literal.len() - 1].parse::<FloatType>().is_ok()
+}
+
+/// Converts a string to a vector of partial tokens, each paired with the character offset it starts at.
+/// String literals that cannot be parsed are left out, and their errors are added to `errors` together with their offset.
+fn str_to_partial_tokens(
+    string: &str,
+    errors: &mut Vec<(EvalexprError, usize)>,
+) -> Vec<(PartialToken, usize)> {
+    let mut result: Vec<(PartialToken, usize)> = Vec::new();
+    let mut iter = string.chars().enumerate().peekable();
+
+    while let Some((position, c)) = iter.next() {
+        if c == '"' || c == '\'' {
+            match parse_string_literal(&mut iter.by_ref().map(|(_, c)| c), c) {
+                Ok(partial_token) => result.push((partial_token, position)),
+                Err(error) => errors.push((error, position)),
+            }
+        } else if c == '/' && matches!(iter.peek(), Some((_, '/'))) {
+            // A line comment reaches until the next newline, which is kept as whitespace.
+            while iter.next_if(|&(_, c)| c != '\n').is_some() {}
         } else {
-            let partial_token = char_to_partial_token(c);
+            // A double colon is part of an identifier like `math::ln`, while a single colon is a token.
+            let partial_token = if c == ':' && matches!(iter.peek(), Some((_, ':'))) {
+                iter.next();
+                PartialToken::Literal("::".to_string())
+            } else {
+                char_to_partial_token(c)
+            };
 
             let if_let_successful =
-                if let (Some(PartialToken::Literal(last)), PartialToken::Literal(literal)) =
+                if let (Some((PartialToken::Literal(last), _)), PartialToken::Literal(literal)) =
                     (result.last_mut(), &partial_token)
                 {
                     last.push_str(literal);
@@ -282,175 +488,235 @@ fn str_to_partial_tokens(string: &str) -> EvalexprResult<Vec<PartialToken>> {
                 };
 
             if !if_let_successful {
-                result.push(partial_token);
+                result.push((partial_token, position));
             }
         }
     }
-    Ok(result)
+    result
 }
 
 /// Resolves all partial tokens by converting them to complex tokens.
-fn partial_tokens_to_tokens(mut tokens: &[PartialToken]) -> EvalexprResult<Vec<Token>> {
+/// Each token keeps the character offset of its first partial token.
+/// Literals that cannot be converted are left out, and their errors are added to `errors` together with their offset.
+fn partial_tokens_to_tokens(
+    mut tokens: &[(PartialToken, usize)],
+    errors: &mut Vec<(EvalexprError, usize)>,
+) -> Vec<(Token, usize)> {
     let mut result = Vec::new();
     while !tokens.is_empty() {
-        let first = tokens[0].clone();
-        let second = tokens.get(1).cloned();
-        let third = tokens.get(2).cloned();
+        let (first, position) = tokens[0].clone();
+        let second = tokens.get(1).map(|(token, _)| token.clone());
+        let third = tokens.get(2).map(|(token, _)| token.clone());
         let mut cutoff = 2;
 
-        result.extend(
-            match first {
-                PartialToken::Token(token) => {
+        let token = match first {
+            PartialToken::Token(token) => {
+                cutoff = 1;
+                Some(token)
+            },
+            PartialToken::Plus => match second {
+                Some(PartialToken::Eq) => Some(Token::PlusAssign),
+                _ => {
                     cutoff = 1;
-                    Some(token)
+                    Some(Token::Plus)
                 },
-                PartialToken::Plus => match second {
-                    Some(PartialToken::Eq) => Some(Token::PlusAssign),
-                    _ => {
-                        cutoff = 1;
-                        Some(Token::Plus)
-                    },
-                },
-                PartialToken::Minus => match second {
-                    Some(PartialToken::Eq) => Some(Token::MinusAssign),
-                    _ => {
-                        cutoff = 1;
-                        Some(Token::Minus)
-                    },
+            },
+            PartialToken::Minus => match second {
+                Some(PartialToken::Eq) => Some(Token::MinusAssign),
+                _ => {
+                    cutoff = 1;
+                    Some(Token::Minus)
                 },
-                PartialToken::Star => match second {
-                    Some(PartialToken::Eq) => Some(Token::StarAssign),
-                    _ => {
-                        cutoff = 1;
-                        Some(Token::Star)
+            },
+            PartialToken::Star => match second {
+                Some(PartialToken::Eq) => Some(Token::StarAssign),
+                // `**` is a synonym for `^`
+                Some(PartialToken::Star) => match third {
+                    Some(PartialToken::Eq) => {
+                        cutoff = 3;
+                        Some(Token::HatAssign)
                     },
+                    _ => Some(Token::Hat),
                 },
-                PartialToken::Slash => match second {
-                    Some(PartialToken::Eq) => Some(Token::SlashAssign),
-                    _ => {
-                        cutoff = 1;
-                        Some(Token::Slash)
-                    },
+                _ => {
+                    cutoff = 1;
+                    Some(Token::Star)
                 },
-                PartialToken::Percent => match second {
-                    Some(PartialToken::Eq) => Some(Token::PercentAssign),
-                    _ => {
-                        cutoff = 1;
-                        Some(Token::Percent)
-                    },
+            },
+            PartialToken::Slash => match second {
+                Some(PartialToken::Eq) => Some(Token::SlashAssign),
+                _ => {
+                    cutoff = 1;
+                    Some(Token::Slash)
                 },
-                PartialToken::Hat => match second {
-                    Some(PartialToken::Eq) => Some(Token::HatAssign),
-                    _ => {
-                        cutoff = 1;
-                        Some(Token::Hat)
-                    },
+            },
+            PartialToken::Percent => match second {
+                Some(PartialToken::Eq) => Some(Token::PercentAssign),
+                _ => {
+                    cutoff = 1;
+                    Some(Token::Percent)
                 },
-                PartialToken::Literal(literal) => {
+            },
+            PartialToken::Hat => match second {
+                Some(PartialToken::Eq) => Some(Token::HatAssign),
+                Some(PartialToken::Hat) => Some(Token::DoubleHat),
+                _ => {
                     cutoff = 1;
-                    if let Ok(number) = literal.parse::<IntType>() {
-                        Some(Token::Int(number))
-                    } else if let Ok(number) = literal.parse::<FloatType>() {
+                    Some(Token::Hat)
+                },
+            },
+            PartialToken::Literal(literal) => {
+                cutoff = 1;
+                let number_literal = match remove_digit_separators(&literal) {
+                    Ok(number_literal) => number_literal,
+                    Err(error) => {
+                        errors.push((error, position));
+                        tokens = &tokens[cutoff..];
+                        continue;
+                    },
+                };
+                if let Some(number) = parse_prefixed_int_literal(&number_literal) {
+                    match number {
+                        Ok(number) => Some(Token::Int(number)),
+                        Err(_) => {
+                            errors.push((EvalexprError::IllegalNumberLiteral(literal), position));
+                            None
+                        },
+                    }
+                } else if let Ok(number) = number_literal.parse::<IntType>() {
+                    Some(Token::Int(number))
+                } else if let Ok(number) = number_literal.parse::<FloatType>() {
+                    Some(Token::Float(number))
+                } else if let Ok(boolean) = literal.parse::<bool>() {
+                    Some(Token::Boolean(boolean))
+                } else if literal == "in" {
+                    Some(Token::In)
+                } else {
+                    // If there are two tokens following this one, check if the next one is
+                    // a plus or a minus. If so, then attempt to parse all three tokens as a
+                    // scientific notation number of the form `<coefficient>e{+,-}<exponent>`,
+                    // for example [Literal("10e"), Minus, Literal("3")] => "1e-3".parse().
+                    let scientific = match (second, third) {
+                        (Some(second), Some(third))
+                            if second == PartialToken::Minus || second == PartialToken::Plus =>
+                        {
+                            format!("{}{}{}", number_literal, second, third)
+                                .parse::<FloatType>()
+                                .ok()
+                        },
+                        _ => None,
+                    };
+
+                    if let Some(number) = scientific {
+                        cutoff = 3;
                         Some(Token::Float(number))
-                    } else if let Ok(boolean) = literal.parse::<bool>() {
-                        Some(Token::Boolean(boolean))
+                    } else if is_missing_exponent_digits(&number_literal) {
+                        errors.push((EvalexprError::IllegalNumberLiteral(literal), position));
+                        None
                     } else {
-                        // If there are two tokens following this one, check if the next one is
-                        // a plus or a minus. If so, then attempt to parse all three tokens as a
-                        // scientific notation number of the form `<coefficient>e{+,-}<exponent>`,
-                        // for example [Literal("10e"), Minus, Literal("3")] => "1e-3".parse().
-                        match (second, third) {
-                            (Some(second), Some(third))
-                                if second == PartialToken::Minus
-                                    || second == PartialToken::Plus =>
-                            {
-                                if let Ok(number) =
-                                    format!("{}{}{}", literal, second, third).parse::<FloatType>()
-                                {
-                                    cutoff = 3;
-                                    Some(Token::Float(number))
-                                } else {
-                                    Some(Token::Identifier(literal.to_string()))
-                                }
-                            },
-                            _ => Some(Token::Identifier(literal.to_string())),
-                        }
+                        Some(Token::Identifier(literal.to_string()))
                     }
+                }
+            },
+            PartialToken::Whitespace => {
+                cutoff = 1;
+                None
+            },
+            PartialToken::Eq => match second {
+                Some(PartialToken::Eq) => Some(Token::Eq),
+                _ => {
+                    cutoff = 1;
+                    Some(Token::Assign)
                 },
-                PartialToken::Whitespace => {
+            },
+            PartialToken::ExclamationMark => match second {
+                Some(PartialToken::Eq) => Some(Token::Neq),
+                _ => {
                     cutoff = 1;
-                    None
+                    Some(Token::Not)
                 },
-                PartialToken::Eq => match second {
-                    Some(PartialToken::Eq) => Some(Token::Eq),
-                    _ => {
-                        cutoff = 1;
-                        Some(Token::Assign)
-                    },
+            },
+            PartialToken::Gt => match second {
+                Some(PartialToken::Eq) => Some(Token::Geq),
+                Some(PartialToken::Gt) => Some(Token::Shr),
+                _ => {
+                    cutoff = 1;
+                    Some(Token::Gt)
                 },
-                PartialToken::ExclamationMark => match second {
-                    Some(PartialToken::Eq) => Some(Token::Neq),
-                    _ => {
-                        cutoff = 1;
-                        Some(Token::Not)
-                    },
+            },
+            PartialToken::Lt => match second {
+                Some(PartialToken::Eq) => Some(Token::Leq),
+                Some(PartialToken::Lt) => Some(Token::Shl),
+                _ => {
+                    cutoff = 1;
+                    Some(Token::Lt)
                 },
-                PartialToken::Gt => match second {
-                    Some(PartialToken::Eq) => Some(Token::Geq),
-                    _ => {
-                        cutoff = 1;
-                        Some(Token::Gt)
+            },
+            PartialToken::Ampersand => match second {
+                Some(PartialToken::Ampersand) => match third {
+                    Some(PartialToken::Eq) => {
+                        cutoff = 3;
+                        Some(Token::AndAssign)
                     },
+                    _ => Some(Token::And),
                 },
-                PartialToken::Lt => match second {
-                    Some(PartialToken::Eq) => Some(Token::Leq),
-                    _ => {
-                        cutoff = 1;
-                        Some(Token::Lt)
-                    },
+                _ => {
+                    cutoff = 1;
+                    Some(Token::Ampersand)
                 },
-                PartialToken::Ampersand => match second {
-                    Some(PartialToken::Ampersand) => match third {
-                        Some(PartialToken::Eq) => {
-                            cutoff = 3;
-                            Some(Token::AndAssign)
-                        },
-                        _ => Some(Token::And),
+            },
+            PartialToken::VerticalBar => match second {
+                Some(PartialToken::VerticalBar) => match third {
+                    Some(PartialToken::Eq) => {
+                        cutoff = 3;
+                        Some(Token::OrAssign)
                     },
-                    _ => return Err(EvalexprError::unmatched_partial_token(first, second)),
+                    _ => Some(Token::Or),
                 },
-                PartialToken::VerticalBar => match second {
-                    Some(PartialToken::VerticalBar) => match third {
-                        Some(PartialToken::Eq) => {
-                            cutoff = 3;
-                            Some(Token::OrAssign)
-                        },
-                        _ => Some(Token::Or),
-                    },
-                    _ => return Err(EvalexprError::unmatched_partial_token(first, second)),
+                _ => {
+                    cutoff = 1;
+                    Some(Token::VerticalBar)
                 },
-            }
-            .into_iter(),
-        );
+            },
+        };
+        result.extend(token.map(|token| (token, position)));
 
         tokens = &tokens[cutoff..];
     }
-    Ok(result)
+    result
 }
 
-pub(crate) fn tokenize(string: &str) -> EvalexprResult<Vec<Token>> {
-    partial_tokens_to_tokens(&str_to_partial_tokens(string)?)
+/// Converts a string to a vector of tokens, each paired with the character offset it starts at.
+/// Tokens that cannot be parsed are left out, and their errors are added to `errors` together with their offset.
+pub(crate) fn tokenize_collecting_errors(
+    string: &str,
+    errors: &mut Vec<(EvalexprError, usize)>,
+) -> Vec<(Token, usize)> {
+    let partial_tokens = str_to_partial_tokens(string, errors);
+    partial_tokens_to_tokens(&partial_tokens, errors)
+}
+
+/// Converts a string to a vector of tokens, each paired with the character offset it starts at.
+/// Fails with the first error that occurs.
+pub(crate) fn tokenize(string: &str) -> EvalexprResult<Vec<(Token, usize)>> {
+    let mut errors = Vec::new();
+    let tokens = tokenize_collecting_errors(string, &mut errors);
+    match errors.into_iter().next() {
+        Some((error, _)) => Err(error),
+        None => Ok(tokens),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::token::{char_to_partial_token, tokenize, Token};
-    use std::fmt::Write;
+    use core::fmt::Write;
 
     #[test]
     fn test_partial_token_display() {
         let chars = vec![
-            '+', '-', '*', '/', '%', '^', '(', ')', ',', ';', '=', '!', '>', '<', '&', '|', ' ',
+            '+', '-', '*', '/', '%', '^', '(', ')', '[', ']', '{', '}', ',', ';', '?', ':', '=',
+            '!', '>', '<', '&', '|', ' ',
         ];
 
         for char in chars {
@@ -464,27 +730,68 @@ mod tests {
     #[test]
     fn test_token_display() {
         let token_string =
-            "+ - * / % ^ == != > < >= <= && || ! ( ) = += -= *= /= %= ^= &&= ||= , ; ";
+            "+ - * / % ^ == != > < >= <= && || ! in & | ^^ << >> ( ) [ ] { } = += -= *= /= %= ^= &&= ||= , ; ? : ";
         let tokens = tokenize(token_string).unwrap();
         let mut result_string = String::new();
 
-        for token in tokens {
+        for (token, _) in tokens {
             write!(result_string, "{} ", token).unwrap();
         }
 
         assert_eq!(token_string, result_string);
     }
 
+    #[test]
+    fn double_star_is_hat() {
+        assert_eq!(
+            tokenize("2**3").unwrap(),
+            [(Token::Int(2), 0), (Token::Hat, 1), (Token::Int(3), 3)]
+        );
+        assert_eq!(
+            tokenize("a **= 2").unwrap(),
+            [
+                (Token::Identifier("a".to_string()), 0),
+                (Token::HatAssign, 2),
+                (Token::Int(2), 6)
+            ]
+        );
+        assert_eq!(
+            tokenize("2 * *3").unwrap(),
+            [
+                (Token::Int(2), 0),
+                (Token::Star, 2),
+                (Token::Star, 4),
+                (Token::Int(3), 5)
+            ]
+        );
+        assert_eq!(
+            tokenize("2 ***3").unwrap(),
+            [
+                (Token::Int(2), 0),
+                (Token::Hat, 2),
+                (Token::Star, 4),
+                (Token::Int(3), 5)
+            ]
+        );
+    }
+
     #[test]
     fn assignment_lhs_is_identifier() {
         let tokens = tokenize("a = 1").unwrap();
         assert_eq!(
             tokens.as_slice(),
             [
-                Token::Identifier("a".to_string()),
-                Token::Assign,
-                Token::Int(1)
+                (Token::Identifier("a".to_string()), 0),
+                (Token::Assign, 2),
+                (Token::Int(1), 4)
             ]
         );
     }
+
+    #[test]
+    fn token_positions_count_characters() {
+        let tokens = tokenize("\"äö\" + x // comment\n* 2").unwrap();
+        let positions: Vec<usize> = tokens.iter().map(|(_, position)| *position).collect();
+        assert_eq!(positions, [0, 5, 7, 20, 22]);
+    }
 }
@@ -1,12 +1,193 @@
-use crate::Node;
-use std::fmt::{Display, Error, Formatter};
+use crate::{operator::Operator, Node, Value};
+use alloc::string::{String, ToString};
+use core::fmt::{Display, Error, Formatter, Write};
 
+/// Displays the operator tree as an expression string that is parsed back into an equivalent tree.
 impl Display for Node {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
-        self.operator.fmt(f)?;
+        self.fmt_infix(f, false)
+    }
+}
+
+impl Node {
+    /// Returns the structure of the operator tree rooted at this node as an indented outline, for diagnostic purposes.
+    /// Each line shows the operator of a node and its position within the expression string, if it has one, followed by the children of the node indented by two more spaces.
+    ///
+    /// Unlike the `Display` implementation, the outline does not reconstruct the expression, but shows every node, including the root nodes created by parentheses.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use evalexpr::*;
+    ///
+    /// let tree = build_operator_tree("1 + a").unwrap(); // Do proper error handling here
+    /// assert_eq!(
+    ///     tree.debug_tree(),
+    ///     "RootNode\n  \
+    ///        Add at 2\n    \
+    ///          Const { value: Int(1) } at 0\n    \
+    ///          VariableIdentifierRead { identifier: \"a\" } at 4\n"
+    /// );
+    /// ```
+    pub fn debug_tree(&self) -> String {
+        let mut result = String::new();
+        self.write_debug_tree(&mut result, 0);
+        result
+    }
+
+    fn write_debug_tree(&self, result: &mut String, depth: usize) {
+        for _ in 0..depth {
+            result.push_str("  ");
+        }
+        // Writing into a string cannot fail.
+        let _ = write!(result, "{:?}", self.operator());
+        if let Some(position) = self.position() {
+            let _ = write!(result, " at {}", position);
+        }
+        result.push('\n');
+
         for child in self.children() {
-            write!(f, " {}", child)?;
+            child.write_debug_tree(result, depth + 1);
+        }
+    }
+
+    /// Writes this node in infix notation.
+    /// Root nodes other than the outermost one stem from parentheses, so they are written with parentheses if `parenthesize_root` is true.
+    fn fmt_infix(&self, f: &mut Formatter, parenthesize_root: bool) -> Result<(), Error> {
+        match self.operator() {
+            Operator::RootNode => {
+                if parenthesize_root {
+                    write!(f, "(")?;
+                }
+                for child in self.children() {
+                    child.fmt_infix(f, true)?;
+                }
+                if parenthesize_root {
+                    write!(f, ")")?;
+                }
+                Ok(())
+            },
+            Operator::Const {
+                value: Value::String(string),
+            } => {
+                write!(f, "\"")?;
+                for c in string.chars() {
+                    match c {
+                        '"' => write!(f, "\\\"")?,
+                        '\\' => write!(f, "\\\\")?,
+                        '\n' => write!(f, "\\n")?,
+                        '\t' => write!(f, "\\t")?,
+                        '\r' => write!(f, "\\r")?,
+                        c => write!(f, "{}", c)?,
+                    }
+                }
+                write!(f, "\"")
+            },
+            // The debug representation always contains a decimal point or an exponent, so it is parsed as float again.
+            Operator::Const {
+                value: Value::Float(float),
+            } => write!(f, "{:?}", float),
+            operator if operator.is_sequence() => {
+                for (index, child) in self.children().iter().enumerate() {
+                    if index > 0 {
+                        write!(f, "{}", operator.to_string().trim())?;
+                    }
+                    // An empty root node is the implicit last element after a trailing separator.
+                    let is_empty =
+                        child.operator() == &Operator::RootNode && child.children().is_empty();
+                    if index > 0 && !is_empty {
+                        write!(f, " ")?;
+                    }
+                    child.fmt_infix(f, false)?;
+                }
+                // A tuple with a single element needs a trailing comma to be parsed as tuple again.
+                if operator == &Operator::Tuple && self.children().len() == 1 {
+                    write!(f, ",")?;
+                }
+                Ok(())
+            },
+            Operator::FunctionIdentifier { identifier } => {
+                write!(f, "{}", identifier)?;
+                match self.children().first() {
+                    Some(argument) if argument.operator() == &Operator::RootNode => {
+                        argument.fmt_infix(f, true)
+                    },
+                    // An argument without parentheses, like in `f 4`.
+                    Some(argument) => {
+                        write!(f, " ")?;
+                        argument.fmt_infix(f, true)
+                    },
+                    None => write!(f, "()"),
+                }
+            },
+            Operator::Map => {
+                write!(f, "{{")?;
+                for (index, entry) in self.children().chunks(2).enumerate() {
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+                    for (index, child) in entry.iter().enumerate() {
+                        if index > 0 {
+                            write!(f, ": ")?;
+                        }
+                        child.fmt_infix(f, true)?;
+                    }
+                }
+                write!(f, "}}")
+            },
+            Operator::ChainedComparison { comparisons } => {
+                for (index, child) in self.children().iter().enumerate() {
+                    if let Some(comparison) = index.checked_sub(1).and_then(|i| comparisons.get(i))
+                    {
+                        write!(f, " {} ", comparison)?;
+                    }
+                    child.fmt_infix(f, true)?;
+                }
+                Ok(())
+            },
+            // The middle operand of a conditional is delimited by `?` and `:`, so its root node is written without parentheses.
+            Operator::Conditional => {
+                for (index, child) in self.children().iter().enumerate() {
+                    if index > 0 {
+                        write!(f, " ? ")?;
+                    }
+                    child.fmt_infix(f, index == 0)?;
+                }
+                Ok(())
+            },
+            Operator::Index => {
+                if let Some(tuple) = self.children().first() {
+                    tuple.fmt_infix(f, true)?;
+                }
+                write!(f, "[")?;
+                if let Some(index) = self.children().get(1) {
+                    index.fmt_infix(f, false)?;
+                }
+                write!(f, "]")
+            },
+            operator if operator.is_unary() => {
+                write!(f, "{}", operator)?;
+                for child in self.children() {
+                    child.fmt_infix(f, true)?;
+                }
+                Ok(())
+            },
+            operator if operator.is_leaf() => write!(f, "{}", operator),
+            operator => {
+                for (index, child) in self.children().iter().enumerate() {
+                    if index > 0 {
+                        write!(f, " {} ", operator.to_string().trim())?;
+                    }
+                    child.fmt_infix(f, true)?;
+                }
+                // An operator with missing operands, like the first `*` in `2 * * 3`, is written after its operands.
+                // This is parsed into the same malformed tree again.
+                match self.children().len() {
+                    0 => write!(f, "{}", operator.to_string().trim()),
+                    1 => write!(f, " {}", operator.to_string().trim()),
+                    _ => Ok(()),
+                }
+            },
         }
-        Ok(())
     }
 }
@@ -1,5 +1,6 @@
 use crate::Node;
-use std::slice::Iter;
+use alloc::{vec, vec::Vec};
+use core::slice::Iter;
 
 /// An iterator that traverses an operator tree in pre-order.
 pub struct NodeIter<'a> {
@@ -46,4 +47,44 @@ impl Node {
     pub fn iter(&self) -> impl Iterator<Item = &Node> {
         NodeIter::new(self)
     }
+
+    /// Returns the amount of nodes in this tree, including this node and the root nodes created by parentheses.
+    ///
+    /// Together with `Node::depth`, this can be used to reject overly complex expressions before evaluating them.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use evalexpr::*;
+    ///
+    /// let tree = build_operator_tree("1 + 2").unwrap(); // Do proper error handling here
+    /// // The outermost root node, the addition and its two operands.
+    /// assert_eq!(tree.node_count(), 4);
+    /// ```
+    pub fn node_count(&self) -> usize {
+        1 + self.iter().count()
+    }
+
+    /// Returns the maximum nesting depth of this tree, which is one for a node without children.
+    /// The depth is computed without recursion, so it is safe to call on arbitrarily deep trees.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use evalexpr::*;
+    ///
+    /// let tree = build_operator_tree("1 + 2").unwrap(); // Do proper error handling here
+    /// assert_eq!(tree.depth(), 3);
+    /// let tree = build_operator_tree("1 + (2 * 3)").unwrap(); // Do proper error handling here
+    /// assert_eq!(tree.depth(), 5);
+    /// ```
+    pub fn depth(&self) -> usize {
+        let mut max_depth = 0;
+        let mut stack = vec![(self, 1)];
+        while let Some((node, depth)) = stack.pop() {
+            max_depth = max_depth.max(depth);
+            stack.extend(node.children.iter().map(|child| (child, depth + 1)));
+        }
+        max_depth
+    }
 }
@@ -5,23 +5,32 @@ use crate::{
 };
 
 use crate::{
-    error::{EvalexprError, EvalexprResult},
+    error::{expect_operator_argument_amount, EvalexprError, EvalexprResult},
     operator::*,
     value::Value,
 };
-use std::mem;
+use alloc::{string::String, vec, vec::Vec};
+use core::mem;
 
-// Exclude display module from coverage, as it prints not well-defined prefix notation.
-#[cfg(not(tarpaulin_include))]
 mod display;
 mod iter;
 
+/// The maximum depth up to which operator trees are evaluated, unless a different maximum is given.
+/// It is low enough to not overflow the stack of a thread spawned with the default stack size of two MiB, even in debug builds.
+///
+/// A pair of parentheses is a level of its own on top of the operator inside it,
+/// so an expression like `(1 + (2 + (3 + ...)))` exceeds this depth at about 512 nested parentheses.
+pub const DEFAULT_MAX_EVALUATION_DEPTH: usize = 1024;
+
 /// A node in the operator tree.
 /// The operator tree is created by the crate-level `build_operator_tree` method.
 /// It can be evaluated for a given context with the `Node::eval` method.
 ///
 /// The advantage of constructing the operator tree separately from the actual evaluation is that it can be evaluated arbitrarily often with different contexts.
 ///
+/// A node can be displayed as an expression string that is parsed back into an equivalent operator tree, e.g. `1+2*3` is displayed as `1 + 2 * 3`.
+/// The structure of the operator tree itself can be shown with `Node::debug_tree`.
+///
 /// # Examples
 ///
 /// ```rust
@@ -33,10 +42,12 @@ mod iter;
 /// assert_eq!(node.eval_with_context(&context), Ok(Value::from(3)));
 /// ```
 ///
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, Clone)]
 pub struct Node {
     operator: Operator,
     children: Vec<Node>,
+    /// The offset in characters of the token this node was created from, if any.
+    position: Option<usize>,
 }
 
 impl Node {
@@ -44,6 +55,39 @@ impl Node {
         Self {
             children: Vec::new(),
             operator,
+            position: None,
+        }
+    }
+
+    /// Returns the offset in characters of the token this node was created from within the expression string.
+    /// Root nodes that do not correspond to a token have no position.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use evalexpr::*;
+    ///
+    /// let tree = build_operator_tree("1 + 2").unwrap(); // Do proper error handling here
+    /// let add = &tree.children()[0];
+    /// assert_eq!(add.position(), Some(2));
+    /// assert_eq!(add.children()[1].position(), Some(4));
+    /// ```
+    pub fn position(&self) -> Option<usize> {
+        self.position
+    }
+
+    /// Attaches the position of this node to errors that are caused by a malformed expression.
+    fn with_position<T>(&self, result: EvalexprResult<T>) -> EvalexprResult<T> {
+        match (result, self.position) {
+            (
+                Err(
+                    error @ (EvalexprError::WrongOperatorArgumentAmount { .. }
+                    | EvalexprError::UnmatchedQuestionMark
+                    | EvalexprError::UnmatchedColon),
+                ),
+                Some(position),
+            ) => Err(error.at_position(position)),
+            (result, _) => result,
         }
     }
 
@@ -99,6 +143,27 @@ impl Node {
         })
     }
 
+    /// Returns all variable identifiers in this expression without duplicates, in the order of their first occurrence.
+    /// Function identifiers are not included.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use evalexpr::*;
+    ///
+    /// let tree = build_operator_tree("a + b * a + f(c)").unwrap(); // Do proper error handling here
+    /// assert_eq!(tree.variable_identifiers(), vec!["a", "b", "c"]);
+    /// ```
+    pub fn variable_identifiers(&self) -> Vec<&str> {
+        let mut identifiers = Vec::new();
+        for identifier in self.iter_variable_identifiers() {
+            if !identifiers.contains(&identifier) {
+                identifiers.push(identifier);
+            }
+        }
+        identifiers
+    }
+
     /// Returns an iterator over all read variable identifiers in this expression.
     /// Each occurrence of a variable identifier is returned separately.
     ///
@@ -161,39 +226,290 @@ impl Node {
         })
     }
 
+    /// Returns all function identifiers in this expression without duplicates, in the order of their first occurrence.
+    /// Variable identifiers are not included, but calls of builtin functions are.
+    ///
+    /// This can be used to check that an untrusted expression only calls allowed functions before evaluating it.
+    /// Note that functions called by name, like `double` in `map("double", t)`, are not included, as their names are only known during evaluation.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use evalexpr::*;
+    ///
+    /// let tree = build_operator_tree("f(a) + g(f(b), max(c, 1))").unwrap(); // Do proper error handling here
+    /// assert_eq!(tree.function_identifiers(), vec!["f", "g", "max"]);
+    /// ```
+    pub fn function_identifiers(&self) -> Vec<&str> {
+        let mut identifiers = Vec::new();
+        for identifier in self.iter_function_identifiers() {
+            if !identifiers.contains(&identifier) {
+                identifiers.push(identifier);
+            }
+        }
+        identifiers
+    }
+
+    /// Converts all integer literals in this expression into float literals, such that arithmetic on them is done with floats.
+    /// For example, `5 / 2` evaluates to `2.5` instead of `2` after the conversion.
+    ///
+    /// Only literals are converted, integers that are stored in variables or returned by functions are left as they are.
+    /// Operators and functions that require integers, like the index operator or the bitwise operators, fail for converted literals.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use evalexpr::*;
+    ///
+    /// let mut tree = build_operator_tree("5 / 2").unwrap(); // Do proper error handling here
+    /// assert_eq!(tree.eval(), Ok(Value::from(2)));
+    /// tree.convert_int_literals_to_floats();
+    /// assert_eq!(tree.eval(), Ok(Value::from(2.5)));
+    /// assert_eq!(tree.to_string(), "5.0 / 2.0");
+    /// ```
+    pub fn convert_int_literals_to_floats(&mut self) {
+        let mut stack = vec![self];
+        while let Some(node) = stack.pop() {
+            if let Operator::Const {
+                value: Value::Int(int),
+            } = node.operator
+            {
+                node.operator = Operator::value(Value::Float(int as FloatType));
+            }
+            stack.extend(node.children.iter_mut());
+        }
+    }
+
+    /// Merges unparenthesized sequences of ordering comparisons like `a < b <= c` into a single `Operator::ChainedComparison`.
+    /// The parser builds such a sequence as a left-leaning tree `(a < b) <= c` of binary comparisons.
+    fn chain_comparisons(&mut self) {
+        let mut stack = vec![self];
+        while let Some(node) = stack.pop() {
+            if node.operator.is_ordering_comparison()
+                && node.children.len() == 2
+                && node.children[0].operator.is_ordering_comparison()
+            {
+                let mut operands = Vec::new();
+                let mut comparisons = Vec::new();
+                let position = node.position;
+                let mut current = mem::replace(node, Node::root_node());
+                while current.operator.is_ordering_comparison() && current.children.len() == 2 {
+                    let right = current.children.pop().expect("the node has two children");
+                    let left = current.children.pop().expect("the node has two children");
+                    operands.push(right);
+                    comparisons.push(mem::replace(&mut current.operator, Operator::RootNode));
+                    current = left;
+                }
+                operands.push(current);
+                operands.reverse();
+                comparisons.reverse();
+                node.operator = Operator::ChainedComparison { comparisons };
+                node.children = operands;
+                node.position = position;
+            }
+            stack.extend(node.children.iter_mut());
+        }
+    }
+
     /// Evaluates the operator tree rooted at this node with the given context.
+    /// The right operand of `&&` and `||` is only evaluated if the left operand does not determine the result.
+    /// Likewise, only the selected branch of a conditional `condition ? a : b` or a call `if(condition, a, b)` is evaluated.
     ///
-    /// Fails, if one of the operators in the expression tree fails.
+    /// Fails, if one of the operators in the expression tree fails,
+    /// or with `EvalexprError::RecursionLimitExceeded` if the tree is deeper than `DEFAULT_MAX_EVALUATION_DEPTH`.
     pub fn eval_with_context<C: Context>(&self, context: &C) -> EvalexprResult<Value> {
-        let mut arguments = Vec::new();
-        for child in self.children() {
-            arguments.push(child.eval_with_context(context)?);
+        self.eval_with_context_and_max_depth(context, DEFAULT_MAX_EVALUATION_DEPTH)
+    }
+
+    /// Evaluates the operator tree rooted at this node with the given context, descending at most `max_depth` levels into the tree.
+    ///
+    /// Fails, if one of the operators in the expression tree fails,
+    /// or with `EvalexprError::RecursionLimitExceeded` if the evaluation needs to descend more than `max_depth` levels.
+    /// This never happens if `Node::depth` is at most `max_depth`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use evalexpr::*;
+    ///
+    /// let tree = build_operator_tree("((1 + 2))").unwrap(); // Do proper error handling here
+    /// assert_eq!(tree.depth(), 5);
+    /// assert_eq!(tree.eval_with_context_and_max_depth(&EmptyContext, 5), Ok(Value::from(3)));
+    /// assert_eq!(
+    ///     tree.eval_with_context_and_max_depth(&EmptyContext, 4),
+    ///     Err(EvalexprError::RecursionLimitExceeded)
+    /// );
+    /// ```
+    pub fn eval_with_context_and_max_depth<C: Context>(
+        &self,
+        context: &C,
+        max_depth: usize,
+    ) -> EvalexprResult<Value> {
+        let max_depth = max_depth
+            .checked_sub(1)
+            .ok_or(EvalexprError::RecursionLimitExceeded)?;
+        let eval = |node: &Node| node.eval_with_context_and_max_depth(context, max_depth);
+        if self.operator() == &Operator::ConditionalElse {
+            return self.eval_conditional(eval);
         }
-        self.operator().eval(&arguments, context)
+        if let Some(arguments) = self.if_arguments(context) {
+            return Self::eval_if(arguments, eval);
+        }
+
+        let children = self.argument_nodes();
+        let mut arguments = Vec::with_capacity(children.len());
+        for child in children {
+            arguments.push(eval(child)?);
+            if let Some(result) = self.operator().short_circuit(&arguments) {
+                return result;
+            }
+        }
+        self.with_position(self.operator().eval(arguments, context))
+            .and_then(|value| expect_finite_float(value, context))
     }
 
     /// Evaluates the operator tree rooted at this node with the given mutable context.
+    /// The right operand of `&&` and `||` is only evaluated if the left operand does not determine the result.
+    /// Likewise, only the selected branch of a conditional `condition ? a : b` or a call `if(condition, a, b)` is evaluated.
     ///
-    /// Fails, if one of the operators in the expression tree fails.
+    /// Fails, if one of the operators in the expression tree fails,
+    /// or with `EvalexprError::RecursionLimitExceeded` if the tree is deeper than `DEFAULT_MAX_EVALUATION_DEPTH`.
     pub fn eval_with_context_mut<C: ContextWithMutableVariables>(
         &self,
         context: &mut C,
     ) -> EvalexprResult<Value> {
-        let mut arguments = Vec::new();
-        for child in self.children() {
-            arguments.push(child.eval_with_context_mut(context)?);
+        self.eval_with_context_mut_and_max_depth(context, DEFAULT_MAX_EVALUATION_DEPTH)
+    }
+
+    /// Evaluates the operator tree rooted at this node with the given mutable context, descending at most `max_depth` levels into the tree.
+    ///
+    /// Fails, if one of the operators in the expression tree fails,
+    /// or with `EvalexprError::RecursionLimitExceeded` if the evaluation needs to descend more than `max_depth` levels.
+    /// This never happens if `Node::depth` is at most `max_depth`.
+    pub fn eval_with_context_mut_and_max_depth<C: ContextWithMutableVariables>(
+        &self,
+        context: &mut C,
+        max_depth: usize,
+    ) -> EvalexprResult<Value> {
+        let max_depth = max_depth
+            .checked_sub(1)
+            .ok_or(EvalexprError::RecursionLimitExceeded)?;
+        if self.operator() == &Operator::ConditionalElse {
+            return self.eval_conditional(|node| {
+                node.eval_with_context_mut_and_max_depth(context, max_depth)
+            });
+        }
+        if let Some(arguments) = self.if_arguments(context) {
+            return Self::eval_if(arguments, |node| {
+                node.eval_with_context_mut_and_max_depth(context, max_depth)
+            });
+        }
+
+        let children = self.argument_nodes();
+        let mut arguments = Vec::with_capacity(children.len());
+        for child in children {
+            arguments.push(child.eval_with_context_mut_and_max_depth(context, max_depth)?);
+            if let Some(result) = self.operator().short_circuit(&arguments) {
+                return result;
+            }
+        }
+        self.with_position(self.operator().eval_mut(arguments, context))
+            .and_then(|value| expect_finite_float(value, &*context))
+    }
+
+    /// Evaluates a conditional `condition ? a : b` rooted at this `ConditionalElse` node using the given evaluation function.
+    /// Only the branch selected by the condition is evaluated.
+    fn eval_conditional<F: FnMut(&Node) -> EvalexprResult<Value>>(
+        &self,
+        mut eval: F,
+    ) -> EvalexprResult<Value> {
+        self.with_position(expect_operator_argument_amount(self.children().len(), 2))?;
+        let conditional = &self.children()[0];
+        if conditional.operator() != &Operator::Conditional {
+            return self.with_position(Err(EvalexprError::UnmatchedColon));
+        }
+        conditional.with_position(expect_operator_argument_amount(
+            conditional.children().len(),
+            2,
+        ))?;
+
+        if eval(&conditional.children()[0])?.as_boolean()? {
+            eval(&conditional.children()[1])
+        } else {
+            eval(&self.children()[1])
+        }
+    }
+
+    /// Returns the nodes whose values are passed to the operator of this node when evaluating it.
+    ///
+    /// For a function call with an argument list in parentheses, like `f(a, b)` or `f()`, these are the elements of the list.
+    /// Any other argument, like in `f((a, b))`, `f(a)` or `f a`, is a single argument, even if it evaluates to a tuple.
+    /// For all other operators, these are the children of this node.
+    fn argument_nodes(&self) -> &[Node] {
+        if let (Operator::FunctionIdentifier { .. }, [argument]) =
+            (self.operator(), self.children())
+        {
+            match (argument.operator(), argument.children()) {
+                (Operator::RootNode, [tuple]) if tuple.operator() == &Operator::Tuple => {
+                    return tuple.children();
+                },
+                (Operator::RootNode, []) => return &[],
+                _ => {},
+            }
+        }
+        self.children()
+    }
+
+    /// Returns the condition and the branches of a call `if(condition, a, b)` rooted at this node.
+    /// Returns `None` if this node is not such a call, if builtin functions are disabled in the given context,
+    /// or if the context has its own `if` function, which takes precedence over the builtin.
+    fn if_arguments<C: Context>(&self, context: &C) -> Option<&[Node]> {
+        match self.operator() {
+            Operator::FunctionIdentifier { identifier }
+                if identifier == "if"
+                    && !context.are_builtin_functions_disabled()
+                    && !context.has_function(identifier) =>
+            {
+                Some(self.argument_nodes()).filter(|arguments| arguments.len() == 3)
+            },
+            _ => None,
+        }
+    }
+
+    /// Evaluates a call `if(condition, a, b)` with the given arguments using the given evaluation function.
+    /// Only the branch selected by the condition is evaluated.
+    fn eval_if<F: FnMut(&Node) -> EvalexprResult<Value>>(
+        arguments: &[Node],
+        mut eval: F,
+    ) -> EvalexprResult<Value> {
+        if eval(&arguments[0])?.as_boolean()? {
+            eval(&arguments[1])
+        } else {
+            eval(&arguments[2])
         }
-        self.operator().eval_mut(&arguments, context)
     }
 
     /// Evaluates the operator tree rooted at this node.
+    /// The evaluation uses a fresh `HashMapContext`, so the expression may assign variables, but cannot read any it did not assign.
     ///
     /// Fails, if one of the operators in the expression tree fails.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use evalexpr::*;
+    ///
+    /// let precomputed = build_operator_tree("a = 3; a * 2 + 1").unwrap(); // Do proper error handling here
+    /// assert_eq!(precomputed.eval(), Ok(Value::from(7)));
+    /// // Like `eval_[type]`, `Node::eval_[type]` returns the respective type directly.
+    /// assert_eq!(precomputed.eval_int(), Ok(7));
+    /// assert_eq!(precomputed.eval_number(), Ok(7.0));
+    /// ```
     pub fn eval(&self) -> EvalexprResult<Value> {
         self.eval_with_context_mut(&mut HashMapContext::new())
     }
 
-    /// Evaluates the operator tree rooted at this node into a string with an the given context.
+    /// Evaluates the operator tree rooted at this node into a string with the given context.
     ///
     /// Fails, if one of the operators in the expression tree fails.
     pub fn eval_string_with_context<C: Context>(&self, context: &C) -> EvalexprResult<String> {
@@ -204,7 +520,7 @@ impl Node {
         }
     }
 
-    /// Evaluates the operator tree rooted at this node into a float with an the given context.
+    /// Evaluates the operator tree rooted at this node into a float with the given context.
     ///
     /// Fails, if one of the operators in the expression tree fails.
     pub fn eval_float_with_context<C: Context>(&self, context: &C) -> EvalexprResult<FloatType> {
@@ -215,9 +531,22 @@ impl Node {
         }
     }
 
-    /// Evaluates the operator tree rooted at this node into an integer with an the given context.
+    /// Evaluates the operator tree rooted at this node into an integer with the given context.
     ///
     /// Fails, if one of the operators in the expression tree fails.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use evalexpr::*;
+    ///
+    /// let precomputed = build_operator_tree("x * x").unwrap(); // Do proper error handling here
+    /// let mut context = HashMapContext::new();
+    /// for x in 1..4 {
+    ///     context.set_value("x".into(), x.into()).unwrap(); // Do proper error handling here
+    ///     assert_eq!(precomputed.eval_int_with_context(&context), Ok(x * x));
+    /// }
+    /// ```
     pub fn eval_int_with_context<C: Context>(&self, context: &C) -> EvalexprResult<IntType> {
         match self.eval_with_context(context) {
             Ok(Value::Int(int)) => Ok(int),
@@ -226,7 +555,7 @@ impl Node {
         }
     }
 
-    /// Evaluates the operator tree rooted at this node into a float with an the given context.
+    /// Evaluates the operator tree rooted at this node into a float with the given context.
     /// If the result of the expression is an integer, it is silently converted into a float.
     ///
     /// Fails, if one of the operators in the expression tree fails.
@@ -239,7 +568,7 @@ impl Node {
         }
     }
 
-    /// Evaluates the operator tree rooted at this node into a boolean with an the given context.
+    /// Evaluates the operator tree rooted at this node into a boolean with the given context.
     ///
     /// Fails, if one of the operators in the expression tree fails.
     pub fn eval_boolean_with_context<C: Context>(&self, context: &C) -> EvalexprResult<bool> {
@@ -250,7 +579,7 @@ impl Node {
         }
     }
 
-    /// Evaluates the operator tree rooted at this node into a tuple with an the given context.
+    /// Evaluates the operator tree rooted at this node into a tuple with the given context.
     ///
     /// Fails, if one of the operators in the expression tree fails.
     pub fn eval_tuple_with_context<C: Context>(&self, context: &C) -> EvalexprResult<TupleType> {
@@ -261,7 +590,7 @@ impl Node {
         }
     }
 
-    /// Evaluates the operator tree rooted at this node into an empty value with an the given context.
+    /// Evaluates the operator tree rooted at this node into an empty value with the given context.
     ///
     /// Fails, if one of the operators in the expression tree fails.
     pub fn eval_empty_with_context<C: Context>(&self, context: &C) -> EvalexprResult<EmptyType> {
@@ -272,7 +601,7 @@ impl Node {
         }
     }
 
-    /// Evaluates the operator tree rooted at this node into a string with an the given mutable context.
+    /// Evaluates the operator tree rooted at this node into a string with the given mutable context.
     ///
     /// Fails, if one of the operators in the expression tree fails.
     pub fn eval_string_with_context_mut<C: ContextWithMutableVariables>(
@@ -286,7 +615,7 @@ impl Node {
         }
     }
 
-    /// Evaluates the operator tree rooted at this node into a float with an the given mutable context.
+    /// Evaluates the operator tree rooted at this node into a float with the given mutable context.
     ///
     /// Fails, if one of the operators in the expression tree fails.
     pub fn eval_float_with_context_mut<C: ContextWithMutableVariables>(
@@ -300,7 +629,7 @@ impl Node {
         }
     }
 
-    /// Evaluates the operator tree rooted at this node into an integer with an the given mutable context.
+    /// Evaluates the operator tree rooted at this node into an integer with the given mutable context.
     ///
     /// Fails, if one of the operators in the expression tree fails.
     pub fn eval_int_with_context_mut<C: ContextWithMutableVariables>(
@@ -314,7 +643,7 @@ impl Node {
         }
     }
 
-    /// Evaluates the operator tree rooted at this node into a float with an the given mutable context.
+    /// Evaluates the operator tree rooted at this node into a float with the given mutable context.
     /// If the result of the expression is an integer, it is silently converted into a float.
     ///
     /// Fails, if one of the operators in the expression tree fails.
@@ -330,7 +659,7 @@ impl Node {
         }
     }
 
-    /// Evaluates the operator tree rooted at this node into a boolean with an the given mutable context.
+    /// Evaluates the operator tree rooted at this node into a boolean with the given mutable context.
     ///
     /// Fails, if one of the operators in the expression tree fails.
     pub fn eval_boolean_with_context_mut<C: ContextWithMutableVariables>(
@@ -344,7 +673,7 @@ impl Node {
         }
     }
 
-    /// Evaluates the operator tree rooted at this node into a tuple with an the given mutable context.
+    /// Evaluates the operator tree rooted at this node into a tuple with the given mutable context.
     ///
     /// Fails, if one of the operators in the expression tree fails.
     pub fn eval_tuple_with_context_mut<C: ContextWithMutableVariables>(
@@ -358,7 +687,7 @@ impl Node {
         }
     }
 
-    /// Evaluates the operator tree rooted at this node into an empty value with an the given mutable context.
+    /// Evaluates the operator tree rooted at this node into an empty value with the given mutable context.
     ///
     /// Fails, if one of the operators in the expression tree fails.
     pub fn eval_empty_with_context_mut<C: ContextWithMutableVariables>(
@@ -458,87 +787,117 @@ impl Node {
         }
     }
 
-    fn insert_back_prioritized(&mut self, node: Node, is_root_node: bool) -> EvalexprResult<()> {
-        // println!(
-        //     "Inserting {:?} into {:?}, is_root_node = {is_root_node}",
-        //     node.operator(),
-        //     self.operator()
-        // );
-        // println!("Self is {:?}", self);
-        if self.operator().precedence() < node.operator().precedence() || node.operator().is_unary() || is_root_node
-            // Right-to-left chaining
-            || (self.operator().precedence() == node.operator().precedence() && !self.operator().is_left_to_right() && !node.operator().is_left_to_right())
-        {
-            if self.operator().is_leaf() {
-                Err(EvalexprError::AppendedToLeafNode)
-            } else if self.has_enough_children() {
-                // Unwrap cannot fail because is_leaf being false and has_enough_children being true implies that the operator wants and has at least one child
-                let last_child_operator = self.children.last().unwrap().operator();
-
-                if last_child_operator.precedence()
-                    < node.operator().precedence() || node.operator().is_unary()
-                    // Right-to-left chaining
-                    || (last_child_operator.precedence()
-                    == node.operator().precedence() && !last_child_operator.is_left_to_right() && !node.operator().is_left_to_right())
-                {
-                    // println!(
-                    //     "Recursing into {:?}",
-                    //     self.children.last().unwrap().operator()
-                    // );
+    fn insert_back_prioritized(
+        &mut self,
+        node: Node,
+        mut is_root_node: bool,
+    ) -> EvalexprResult<()> {
+        // Descends in a loop rather than recursively, since long chains of unary operators nest arbitrarily deep.
+        let mut target = self;
+        loop {
+            if target.operator().precedence() < node.operator().precedence() || node.operator().is_unary() || is_root_node
+                // Right-to-left chaining
+                || (target.operator().precedence() == node.operator().precedence() && !target.operator().is_left_to_right() && !node.operator().is_left_to_right())
+            {
+                if target.operator().is_leaf() {
+                    return Err(EvalexprError::AppendedToLeafNode);
+                } else if target.has_enough_children() {
                     // Unwrap cannot fail because is_leaf being false and has_enough_children being true implies that the operator wants and has at least one child
-                    self.children
-                        .last_mut()
-                        .unwrap()
-                        .insert_back_prioritized(node, false)
-                } else {
-                    // println!("Rotating");
-                    if node.operator().is_leaf() {
-                        return Err(EvalexprError::AppendedToLeafNode);
-                    }
+                    let last_child_operator = target.children.last().unwrap().operator();
 
-                    // Unwrap cannot fail because is_leaf being false and has_enough_children being true implies that the operator wants and has at least one child
-                    let last_child = self.children.pop().unwrap();
-                    // Root nodes have at most one child
-                    // TODO I am not sure if this is the correct error
-                    if self.operator() == &Operator::RootNode && !self.children().is_empty() {
-                        return Err(EvalexprError::MissingOperatorOutsideOfBrace);
-                    }
-                    // Do not insert root nodes into root nodes.
-                    // TODO I am not sure if this is the correct error
-                    if self.operator() == &Operator::RootNode
-                        && node.operator() == &Operator::RootNode
+                    if last_child_operator.precedence()
+                        < node.operator().precedence() || node.operator().is_unary()
+                        // Right-to-left chaining
+                        || (last_child_operator.precedence()
+                        == node.operator().precedence() && !last_child_operator.is_left_to_right() && !node.operator().is_left_to_right())
                     {
-                        return Err(EvalexprError::MissingOperatorOutsideOfBrace);
-                    }
-                    self.children.push(node);
-                    let node = self.children.last_mut().unwrap();
+                        // Unwrap cannot fail because is_leaf being false and has_enough_children being true implies that the operator wants and has at least one child
+                        target = target.children.last_mut().unwrap();
+                        is_root_node = false;
+                    } else {
+                        if node.operator().is_leaf() {
+                            return Err(EvalexprError::AppendedToLeafNode);
+                        }
 
-                    // Root nodes have at most one child
-                    // TODO I am not sure if this is the correct error
-                    if node.operator() == &Operator::RootNode && !node.children().is_empty() {
-                        return Err(EvalexprError::MissingOperatorOutsideOfBrace);
-                    }
-                    // Do not insert root nodes into root nodes.
-                    // TODO I am not sure if this is the correct error
-                    if node.operator() == &Operator::RootNode
-                        && last_child.operator() == &Operator::RootNode
-                    {
-                        return Err(EvalexprError::MissingOperatorOutsideOfBrace);
+                        // Unwrap cannot fail because is_leaf being false and has_enough_children being true implies that the operator wants and has at least one child
+                        let last_child = target.children.pop().unwrap();
+                        // Root nodes have at most one child
+                        // TODO I am not sure if this is the correct error
+                        if target.operator() == &Operator::RootNode && !target.children().is_empty()
+                        {
+                            return Err(EvalexprError::MissingOperatorOutsideOfBrace);
+                        }
+                        // Do not insert root nodes into root nodes.
+                        // TODO I am not sure if this is the correct error
+                        if target.operator() == &Operator::RootNode
+                            && node.operator() == &Operator::RootNode
+                        {
+                            return Err(EvalexprError::MissingOperatorOutsideOfBrace);
+                        }
+                        target.children.push(node);
+                        let node = target.children.last_mut().unwrap();
+
+                        // Root nodes have at most one child
+                        // TODO I am not sure if this is the correct error
+                        if node.operator() == &Operator::RootNode && !node.children().is_empty() {
+                            return Err(EvalexprError::MissingOperatorOutsideOfBrace);
+                        }
+                        // Do not insert root nodes into root nodes.
+                        // TODO I am not sure if this is the correct error
+                        if node.operator() == &Operator::RootNode
+                            && last_child.operator() == &Operator::RootNode
+                        {
+                            return Err(EvalexprError::MissingOperatorOutsideOfBrace);
+                        }
+                        node.children.push(last_child);
+                        return Ok(());
                     }
-                    node.children.push(last_child);
-                    Ok(())
+                } else {
+                    target.children.push(node);
+                    return Ok(());
                 }
             } else {
-                // println!("Inserting as specified");
-                self.children.push(node);
-                Ok(())
+                return Err(EvalexprError::PrecedenceViolation);
             }
-        } else {
-            Err(EvalexprError::PrecedenceViolation)
         }
     }
 }
 
+impl PartialEq for Node {
+    /// Compares the operators and children of the two trees, ignoring the positions of their nodes.
+    /// So trees that are parsed from differently formatted expressions, like `1+2` and `1 + 2`, are equal.
+    fn eq(&self, other: &Self) -> bool {
+        let mut stack = vec![(self, other)];
+        while let Some((node, other)) = stack.pop() {
+            if node.operator != other.operator || node.children.len() != other.children.len() {
+                return false;
+            }
+            stack.extend(node.children.iter().zip(&other.children));
+        }
+        true
+    }
+}
+
+impl Drop for Node {
+    /// Drops the descendants of this node iteratively, since deeply nested trees would otherwise overflow the stack.
+    fn drop(&mut self) {
+        let mut stack = mem::take(&mut self.children);
+        while let Some(mut node) = stack.pop() {
+            stack.append(&mut node.children);
+        }
+    }
+}
+
+/// Returns the given value, unless it is a non-finite float and the context forbids non-finite floats.
+fn expect_finite_float<C: Context>(value: Value, context: &C) -> EvalexprResult<Value> {
+    match value {
+        Value::Float(float) if !float.is_finite() && context.are_non_finite_floats_forbidden() => {
+            Err(EvalexprError::NonFiniteResult(float))
+        },
+        value => Ok(value),
+    }
+}
+
 fn collapse_root_stack_to(
     root_stack: &mut Vec<Node>,
     mut root: Node,
@@ -609,13 +968,52 @@ fn collapse_all_sequences(root_stack: &mut Vec<Node>) -> EvalexprResult<()> {
     Ok(())
 }
 
-pub(crate) fn tokens_to_operator_tree(tokens: Vec<Token>) -> EvalexprResult<Node> {
+/// Inserts the given node into the given root, or into the last element of the root if the root is a sequence.
+fn insert_into_root(root: &mut Node, node: Node) -> EvalexprResult<()> {
+    if root.operator().is_sequence() {
+        // Once a sequence has been pushed on top of the stack, it also gets a child
+        let last_root_child = root.children.last_mut().unwrap();
+        last_root_child.insert_back_prioritized(node, true)
+    } else {
+        root.insert_back_prioritized(node, true)
+    }
+}
+
+/// Converts the root node of the entries of a map literal `{key: value, ...}` into a map constructor.
+/// Each entry is parsed as a `ConditionalElse` node, whose children become the key and the value.
+fn map_literal(mut root: Node) -> EvalexprResult<Node> {
+    let mut entries = mem::take(&mut root.children);
+    if let [tuple] = entries.as_slice() {
+        if tuple.operator() == &Operator::Tuple {
+            entries = mem::take(&mut entries.pop().unwrap().children);
+        }
+    }
+
+    let mut map = Node::new(Operator::Map);
+    for mut entry in entries {
+        // The elements of a tuple are wrapped in root nodes.
+        if entry.operator() == &Operator::RootNode && entry.children().len() == 1 {
+            entry = entry.children.pop().unwrap();
+        }
+        match (entry.operator(), entry.children()) {
+            (Operator::ConditionalElse, [key, _]) if key.operator() != &Operator::Conditional => {
+                map.children.append(&mut entry.children)
+            },
+            _ => return Err(EvalexprError::IllegalMapEntry),
+        }
+    }
+    Ok(map)
+}
+
+pub(crate) fn tokens_to_operator_tree(tokens: Vec<(Token, usize)>) -> EvalexprResult<Node> {
     let mut root_stack = vec![Node::root_node()];
+    // The opening braces and brackets that are not closed yet, with their positions.
+    let mut open_brackets: Vec<(Token, usize)> = Vec::new();
     let mut last_token_is_rightsided_value = false;
     let mut token_iter = tokens.iter().peekable();
 
-    while let Some(token) = token_iter.next().cloned() {
-        let next = token_iter.peek().cloned();
+    while let Some((token, position)) = token_iter.next().cloned() {
+        let next = token_iter.peek().map(|(token, _)| token.clone());
 
         let node = match token.clone() {
             Token::Plus => Some(Node::new(Operator::Add)),
@@ -640,20 +1038,82 @@ pub(crate) fn tokens_to_operator_tree(tokens: Vec<Token>) -> EvalexprResult<Node
             Token::And => Some(Node::new(Operator::And)),
             Token::Or => Some(Node::new(Operator::Or)),
             Token::Not => Some(Node::new(Operator::Not)),
+            Token::In => Some(Node::new(Operator::In)),
+
+            Token::Ampersand => Some(Node::new(Operator::BitAnd)),
+            Token::VerticalBar => Some(Node::new(Operator::BitOr)),
+            Token::DoubleHat => Some(Node::new(Operator::BitXor)),
+            Token::Shl => Some(Node::new(Operator::Shl)),
+            Token::Shr => Some(Node::new(Operator::Shr)),
 
             Token::LBrace => {
+                open_brackets.push((Token::LBrace, position));
                 root_stack.push(Node::root_node());
                 None
             },
             Token::RBrace => {
-                if root_stack.len() <= 1 {
-                    return Err(EvalexprError::UnmatchedRBrace);
+                if let Some(&(Token::QuestionMark, question_mark_position)) = open_brackets.last() {
+                    return Err(
+                        EvalexprError::UnmatchedQuestionMark.at_position(question_mark_position)
+                    );
+                } else if root_stack.len() <= 1
+                    || open_brackets.pop().map(|(token, _)| token) != Some(Token::LBrace)
+                {
+                    return Err(EvalexprError::UnmatchedRBrace.at_position(position));
                 } else {
-                    collapse_all_sequences(&mut root_stack)?;
+                    collapse_all_sequences(&mut root_stack)
+                        .map_err(|error| error.at_position(position))?;
                     root_stack.pop()
                 }
             },
 
+            // The index is parsed like a subexpression in braces, which becomes the second argument of the index operator.
+            Token::LBracket => {
+                open_brackets.push((Token::LBracket, position));
+                Some(Node::new(Operator::Index))
+            },
+            Token::RBracket => {
+                if let Some(&(Token::QuestionMark, question_mark_position)) = open_brackets.last() {
+                    return Err(
+                        EvalexprError::UnmatchedQuestionMark.at_position(question_mark_position)
+                    );
+                } else if root_stack.len() <= 1
+                    || open_brackets.pop().map(|(token, _)| token) != Some(Token::LBracket)
+                {
+                    return Err(EvalexprError::UnmatchedRBracket.at_position(position));
+                } else {
+                    collapse_all_sequences(&mut root_stack)
+                        .map_err(|error| error.at_position(position))?;
+                    root_stack.pop()
+                }
+            },
+
+            // The entries are parsed like a subexpression in braces, which is then converted into a map constructor.
+            Token::LCurlyBrace => {
+                open_brackets.push((Token::LCurlyBrace, position));
+                root_stack.push(Node::root_node());
+                None
+            },
+            Token::RCurlyBrace => {
+                if let Some(&(Token::QuestionMark, question_mark_position)) = open_brackets.last() {
+                    return Err(
+                        EvalexprError::UnmatchedQuestionMark.at_position(question_mark_position)
+                    );
+                } else if root_stack.len() <= 1
+                    || open_brackets.pop().map(|(token, _)| token) != Some(Token::LCurlyBrace)
+                {
+                    return Err(EvalexprError::UnmatchedRCurlyBrace.at_position(position));
+                } else {
+                    collapse_all_sequences(&mut root_stack)
+                        .map_err(|error| error.at_position(position))?;
+                    root_stack
+                        .pop()
+                        .map(map_literal)
+                        .transpose()
+                        .map_err(|error| error.at_position(position))?
+                }
+            },
+
             Token::Assign => Some(Node::new(Operator::Assign)),
             Token::PlusAssign => Some(Node::new(Operator::AddAssign)),
             Token::MinusAssign => Some(Node::new(Operator::SubAssign)),
@@ -664,14 +1124,48 @@ pub(crate) fn tokens_to_operator_tree(tokens: Vec<Token>) -> EvalexprResult<Node
             Token::AndAssign => Some(Node::new(Operator::AndAssign)),
             Token::OrAssign => Some(Node::new(Operator::OrAssign)),
 
-            Token::Comma => Some(Node::new(Operator::Tuple)),
+            Token::Comma => {
+                // Every comma must follow an element, but a trailing comma like in `(1, 2,)` does not need one after it.
+                if !last_token_is_rightsided_value {
+                    return Err(EvalexprError::EmptyTupleElement.at_position(position));
+                }
+                Some(Node::new(Operator::Tuple))
+            },
             Token::Semicolon => Some(Node::new(Operator::Chain)),
+            // The middle operand of a conditional is parsed like a subexpression in braces, which is closed by the matching colon.
+            Token::QuestionMark => {
+                open_brackets.push((Token::QuestionMark, position));
+                Some(Node::new(Operator::Conditional))
+            },
+            // A colon that does not close the middle operand of a conditional separates a key from a value in a map literal.
+            Token::Colon => {
+                if let Some(&(Token::QuestionMark, question_mark_position)) = open_brackets.last() {
+                    open_brackets.pop();
+                    collapse_all_sequences(&mut root_stack)
+                        .map_err(|error| error.at_position(position))?;
+                    let middle = root_stack
+                        .pop()
+                        .ok_or_else(|| EvalexprError::UnmatchedColon.at_position(position))?;
+                    if middle.children().is_empty() {
+                        return Err(EvalexprError::WrongOperatorArgumentAmount {
+                            expected: 2,
+                            actual: 1,
+                        }
+                        .at_position(question_mark_position));
+                    }
+                    let root = root_stack
+                        .last_mut()
+                        .ok_or_else(|| EvalexprError::UnmatchedColon.at_position(position))?;
+                    insert_into_root(root, middle).map_err(|error| error.at_position(position))?;
+                }
+                Some(Node::new(Operator::ConditionalElse))
+            },
 
             Token::Identifier(identifier) => {
                 let mut result = Some(Node::new(Operator::variable_identifier_read(
                     identifier.clone(),
                 )));
-                if let Some(next) = next {
+                if let Some(next) = &next {
                     if next.is_assignment() {
                         result = Some(Node::new(Operator::variable_identifier_write(
                             identifier.clone(),
@@ -689,6 +1183,10 @@ pub(crate) fn tokens_to_operator_tree(tokens: Vec<Token>) -> EvalexprResult<Node
         };
 
         if let Some(mut node) = node {
+            if node.operator() != &Operator::RootNode {
+                node.position = Some(position);
+            }
+
             // Need to pop and then repush here, because Rust 1.33.0 cannot release the mutable borrow of root_stack before the end of this complete if-statement
             if let Some(mut root) = root_stack.pop() {
                 if node.operator().is_sequence() {
@@ -721,41 +1219,71 @@ pub(crate) fn tokens_to_operator_tree(tokens: Vec<Token>) -> EvalexprResult<Node
                             }
                         } else {
                             // If the new sequence doesn't have a higher precedence, then all sequences with a higher precedence are collapsed below this one
-                            root = collapse_root_stack_to(&mut root_stack, root, &node)?;
+                            root = collapse_root_stack_to(&mut root_stack, root, &node)
+                                .map_err(|error| error.at_position(position))?;
                             node.children.push(root);
                             root_stack.push(node);
                         }
                     }
                 // println!("Stack after sequence operation: {:?}", root_stack);
-                } else if root.operator().is_sequence() {
-                    if let Some(mut last_root_child) = root.children.pop() {
-                        last_root_child.insert_back_prioritized(node, true)?;
-                        root.children.push(last_root_child);
-                        root_stack.push(root);
-                    } else {
-                        // Once a sequence has been pushed on top of the stack, it also gets a child
-                        unreachable!()
-                    }
                 } else {
-                    root.insert_back_prioritized(node, true)?;
+                    insert_into_root(&mut root, node)
+                        .map_err(|error| error.at_position(position))?;
                     root_stack.push(root);
                 }
             } else {
-                return Err(EvalexprError::UnmatchedRBrace);
+                return Err(EvalexprError::UnmatchedRBrace.at_position(position));
+            }
+        }
+
+        if token == Token::LBracket || token == Token::QuestionMark {
+            root_stack.push(Node::root_node());
+        }
+
+        // A trailing comma does not start a new element.
+        if token == Token::Comma
+            && matches!(
+                next,
+                None | Some(Token::RBrace) | Some(Token::RBracket) | Some(Token::RCurlyBrace)
+            )
+        {
+            if let Some(tuple) = root_stack.last_mut() {
+                if tuple.operator() == &Operator::Tuple
+                    && tuple.children.last() == Some(&Node::root_node())
+                {
+                    tuple.children.pop();
+                }
             }
         }
 
         last_token_is_rightsided_value = token.is_rightsided_value();
     }
 
+    // Errors at the end of the expression are reported at the position of the last token.
+    let end_position = tokens.last().map_or(0, |(_, position)| *position);
     // In the end, all sequences are implicitly terminated
-    collapse_all_sequences(&mut root_stack)?;
-
-    if root_stack.len() > 1 {
-        Err(EvalexprError::UnmatchedLBrace)
-    } else if let Some(root) = root_stack.pop() {
-        Ok(root)
-    } else {
-        Err(EvalexprError::UnmatchedRBrace)
+    collapse_all_sequences(&mut root_stack).map_err(|error| error.at_position(end_position))?;
+
+    match open_brackets.last() {
+        Some((Token::LBracket, position)) => {
+            Err(EvalexprError::UnmatchedLBracket.at_position(*position))
+        },
+        Some((Token::LCurlyBrace, position)) => {
+            Err(EvalexprError::UnmatchedLCurlyBrace.at_position(*position))
+        },
+        Some((Token::QuestionMark, position)) => {
+            Err(EvalexprError::UnmatchedQuestionMark.at_position(*position))
+        },
+        Some((_, position)) => Err(EvalexprError::UnmatchedLBrace.at_position(*position)),
+        None if root_stack.len() > 1 => {
+            Err(EvalexprError::UnmatchedLBrace.at_position(end_position))
+        },
+        None => root_stack
+            .pop()
+            .map(|mut root| {
+                root.chain_comparisons();
+                root
+            })
+            .ok_or_else(|| EvalexprError::UnmatchedRBrace.at_position(end_position)),
     }
 }
@@ -1,4 +1,4 @@
-use std::fmt::{Display, Error, Formatter};
+use core::fmt::{Display, Error, Formatter};
 
 use crate::Value;
 
@@ -22,6 +22,20 @@ impl Display for Value {
                 }
                 write!(f, ")")
             },
+            Value::Map(map) => {
+                write!(f, "{{")?;
+                let mut once = false;
+                for (key, value) in map {
+                    if once {
+                        write!(f, ", ")?;
+                    } else {
+                        once = true;
+                    }
+                    write!(f, "\"{}\": ", key)?;
+                    value.fmt(f)?;
+                }
+                write!(f, "}}")
+            },
             Value::Empty => write!(f, "()"),
         }
     }
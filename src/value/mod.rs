@@ -1,5 +1,15 @@
 use crate::error::{EvalexprError, EvalexprResult};
-use std::convert::TryFrom;
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{
+    cmp::Ordering,
+    convert::TryFrom,
+    hash::{Hash, Hasher},
+    mem,
+};
 
 mod display;
 pub mod value_type;
@@ -13,6 +23,10 @@ pub type FloatType = f64;
 /// The type used to represent tuples in `Value::Tuple`.
 pub type TupleType = Vec<Value>;
 
+/// The type used to represent maps in `Value::Map`.
+/// A `BTreeMap` is used such that maps are iterated and displayed in the order of their keys.
+pub type MapType = BTreeMap<String, Value>;
+
 /// The type used to represent empty values in `Value::Empty`.
 pub type EmptyType = ();
 
@@ -21,8 +35,10 @@ pub const EMPTY_VALUE: () = ();
 
 /// The value type used by the parser.
 /// Values can be of different subtypes that are the variants of this enum.
-#[derive(Clone, Debug, PartialEq)]
-#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+///
+/// With the `serde_support` feature, values are (de)serialized as their natural counterparts in the serde data model:
+/// tuples become sequences, maps become maps and `Value::Empty` becomes the unit value.
+#[derive(Clone, Debug)]
 pub enum Value {
     /// A string value.
     String(String),
@@ -34,6 +50,8 @@ pub enum Value {
     Boolean(bool),
     /// A tuple value.
     Tuple(TupleType),
+    /// A map value from string keys to values.
+    Map(MapType),
     /// An empty value.
     Empty,
 }
@@ -68,11 +86,66 @@ impl Value {
         matches!(self, Value::Tuple(_))
     }
 
+    /// Returns true if `self` is a `Value::Map`.
+    pub fn is_map(&self) -> bool {
+        matches!(self, Value::Map(_))
+    }
+
     /// Returns true if `self` is a `Value::Empty`.
     pub fn is_empty(&self) -> bool {
         matches!(self, Value::Empty)
     }
 
+    /// Returns the name of the type of `self`.
+    ///
+    /// The names are `"string"`, `"float"`, `"int"`, `"boolean"`, `"tuple"`, `"map"` and `"empty"`, the same that the builtin function `typeof` returns.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::String(_) => "string",
+            Value::Float(_) => "float",
+            Value::Int(_) => "int",
+            Value::Boolean(_) => "boolean",
+            Value::Tuple(_) => "tuple",
+            Value::Map(_) => "map",
+            Value::Empty => "empty",
+        }
+    }
+
+    /// Returns true if `self` and `other` are equal, treating integers and floats with the same numeric value as equal.
+    ///
+    /// This is the same as `==`, which considers e.g. `Value::Int(2)` and `Value::Float(2.0)` equal to stay consistent with `PartialOrd`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use evalexpr::*;
+    ///
+    /// assert_eq!(Value::from(2), Value::from(2.0));
+    /// assert!(Value::from(2).numeric_eq(&Value::from(2.0)));
+    /// assert!(eval("(1, 2.0)").unwrap().numeric_eq(&eval("(1.0, 2)").unwrap()));
+    /// assert!(!Value::from(2).numeric_eq(&Value::from("2")));
+    /// ```
+    pub fn numeric_eq(&self, other: &Value) -> bool {
+        self == other
+    }
+
+    /// Returns true if `self` is considered true in a boolean context.
+    ///
+    /// `false`, zero, NaN, the empty string, the empty tuple, the empty map and `Value::Empty` are falsy, and all other values are truthy.
+    /// Unlike `Value::as_boolean`, this method never fails.
+    /// The operators of this crate do not use it and still require `Value::Boolean` operands.
+    pub fn is_truthy(&self) -> bool {
+        match self {
+            Value::String(string) => !string.is_empty(),
+            Value::Float(float) => *float != 0.0 && !float.is_nan(),
+            Value::Int(int) => *int != 0,
+            Value::Boolean(boolean) => *boolean,
+            Value::Tuple(tuple) => !tuple.is_empty(),
+            Value::Map(map) => !map.is_empty(),
+            Value::Empty => false,
+        }
+    }
+
     /// Clones the value stored in `self` as `String`, or returns `Err` if `self` is not a `Value::String`.
     pub fn as_string(&self) -> EvalexprResult<String> {
         match self {
@@ -90,6 +163,7 @@ impl Value {
     }
 
     /// Clones the value stored in  `self` as `FloatType`, or returns `Err` if `self` is not a `Value::Float`.
+    /// Integers are not converted, use `Value::as_number` to also accept a `Value::Int`.
     pub fn as_float(&self) -> EvalexprResult<FloatType> {
         match self {
             Value::Float(f) => Ok(*f),
@@ -137,6 +211,14 @@ impl Value {
         }
     }
 
+    /// Clones the value stored in `self` as `MapType`, or returns `Err` if `self` is not a `Value::Map`.
+    pub fn as_map(&self) -> EvalexprResult<MapType> {
+        match self {
+            Value::Map(map) => Ok(map.clone()),
+            value => Err(EvalexprError::expected_map(value.clone())),
+        }
+    }
+
     /// Returns `()`, or returns`Err` if `self` is not a `Value::Tuple`.
     pub fn as_empty(&self) -> EvalexprResult<()> {
         match self {
@@ -182,6 +264,12 @@ impl From<TupleType> for Value {
     }
 }
 
+impl From<MapType> for Value {
+    fn from(map: MapType) -> Self {
+        Value::Map(map)
+    }
+}
+
 impl From<Value> for EvalexprResult<Value> {
     fn from(value: Value) -> Self {
         Ok(value)
@@ -254,6 +342,18 @@ impl TryFrom<Value> for TupleType {
     }
 }
 
+impl TryFrom<Value> for MapType {
+    type Error = EvalexprError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        if let Value::Map(value) = value {
+            Ok(value)
+        } else {
+            Err(EvalexprError::ExpectedMap { actual: value })
+        }
+    }
+}
+
 impl TryFrom<Value> for () {
     type Error = EvalexprError;
 
@@ -266,9 +366,104 @@ impl TryFrom<Value> for () {
     }
 }
 
+/// Returns the integer that is exactly equal to `float`, if any.
+fn float_to_exact_int(float: FloatType) -> Option<IntType> {
+    if (IntType::MIN as FloatType..-(IntType::MIN as FloatType)).contains(&float)
+        && (float as IntType) as FloatType == float
+    {
+        Some(float as IntType)
+    } else {
+        None
+    }
+}
+
+/// Compares an int and a float exactly, even if the int cannot be represented as a float.
+fn compare_int_to_float(int: IntType, float: FloatType) -> Option<Ordering> {
+    match (int as FloatType).partial_cmp(&float)? {
+        // The float is a whole number, but it may be `-IntType::MIN`, which does not fit into an int.
+        Ordering::Equal if float >= -(IntType::MIN as FloatType) => Some(Ordering::Less),
+        Ordering::Equal => Some(int.cmp(&(float as IntType))),
+        ordering => Some(ordering),
+    }
+}
+
+/// Ints and floats are equal if they have exactly the same numeric value, so `Value::Int(1)` equals `Value::Float(1.0)`.
+/// All other values are only equal to values of the same variant.
+impl PartialEq for Value {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Value::String(a), Value::String(b)) => a == b,
+            (Value::Float(a), Value::Float(b)) => a == b,
+            (Value::Int(a), Value::Int(b)) => a == b,
+            (Value::Int(a), Value::Float(b)) | (Value::Float(b), Value::Int(a)) => {
+                float_to_exact_int(*b) == Some(*a)
+            },
+            (Value::Boolean(a), Value::Boolean(b)) => a == b,
+            (Value::Tuple(a), Value::Tuple(b)) => a == b,
+            (Value::Map(a), Value::Map(b)) => a == b,
+            (Value::Empty, Value::Empty) => true,
+            _ => false,
+        }
+    }
+}
+
+/// Floats are compared with the IEEE 754 equality, so a `Value::Float` containing NaN is not equal to itself,
+/// and neither is a tuple or map containing it.
+/// Hence `Eq` is not reflexive for such values, and they can be inserted into a hash map, but can never be looked up again.
+/// The operators `==`, `!=` and `in` use the same equality, so `x == x` is false if `x` is NaN.
+impl Eq for Value {}
+
+/// Floats are hashed by their bits, except that floats equal to an int are hashed like that int to stay consistent with `PartialEq`.
+/// Tuples hash their elements in order, and maps their entries in the order of their keys.
+impl Hash for Value {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        if let Value::Float(float) = self {
+            if let Some(int) = float_to_exact_int(*float) {
+                return Value::Int(int).hash(state);
+            }
+        }
+
+        mem::discriminant(self).hash(state);
+        match self {
+            Value::String(string) => string.hash(state),
+            Value::Float(float) => float.to_bits().hash(state),
+            Value::Int(int) => int.hash(state),
+            Value::Boolean(boolean) => boolean.hash(state),
+            Value::Tuple(tuple) => tuple.hash(state),
+            Value::Map(map) => map.hash(state),
+            Value::Empty => {},
+        }
+    }
+}
+
+/// Ints and floats are compared by value, also across the two types, such that e.g. `Value::Int(1)` and `Value::Float(1.0)` are equal.
+/// Strings are compared lexicographically, booleans with `false < true`, and tuples element-wise in lexicographic order.
+///
+/// All other pairs are incomparable, including NaN and any number, and two maps unless they are equal.
+impl PartialOrd for Value {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (Value::String(a), Value::String(b)) => a.partial_cmp(b),
+            (Value::Int(a), Value::Int(b)) => a.partial_cmp(b),
+            (Value::Float(a), Value::Float(b)) => a.partial_cmp(b),
+            (Value::Int(a), Value::Float(b)) => compare_int_to_float(*a, *b),
+            (Value::Float(a), Value::Int(b)) => compare_int_to_float(*b, *a).map(Ordering::reverse),
+            (Value::Boolean(a), Value::Boolean(b)) => a.partial_cmp(b),
+            (Value::Tuple(a), Value::Tuple(b)) => a.partial_cmp(b),
+            (Value::Map(a), Value::Map(b)) if a == b => Some(Ordering::Equal),
+            (Value::Empty, Value::Empty) => Some(Ordering::Equal),
+            _ => None,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::value::{TupleType, Value};
+    use crate::{
+        value::{FloatType, IntType, MapType, TupleType, Value},
+        EvalexprError,
+    };
+    use core::cmp::Ordering;
 
     #[test]
     fn test_value_conversions() {
@@ -283,6 +478,36 @@ mod tests {
             Value::from(TupleType::new()).as_tuple(),
             Ok(TupleType::new())
         );
+        assert_eq!(Value::from(MapType::new()).as_map(), Ok(MapType::new()));
+    }
+
+    #[test]
+    fn test_value_conversion_errors() {
+        assert_eq!(
+            Value::from(3).as_float(),
+            Err(EvalexprError::expected_float(Value::from(3)))
+        );
+        assert_eq!(Value::from(3).as_number(), Ok(3.0));
+        assert_eq!(
+            Value::from(3.3).as_int(),
+            Err(EvalexprError::expected_int(Value::from(3.3)))
+        );
+        assert_eq!(
+            Value::from(true).as_string(),
+            Err(EvalexprError::expected_string(Value::from(true)))
+        );
+        assert_eq!(
+            Value::from("true").as_boolean(),
+            Err(EvalexprError::expected_boolean(Value::from("true")))
+        );
+        assert_eq!(
+            Value::Empty.as_tuple(),
+            Err(EvalexprError::expected_tuple(Value::Empty))
+        );
+        assert_eq!(
+            Value::from(TupleType::new()).as_map(),
+            Err(EvalexprError::expected_map(Value::from(TupleType::new())))
+        );
     }
 
     #[test]
@@ -292,5 +517,142 @@ mod tests {
         assert!(Value::from(3.3).is_float());
         assert!(Value::from(true).is_boolean());
         assert!(Value::from(TupleType::new()).is_tuple());
+        assert!(Value::from(MapType::new()).is_map());
+    }
+
+    #[test]
+    fn test_value_type_name() {
+        assert_eq!(Value::from("string").type_name(), "string");
+        assert_eq!(Value::from(3.3).type_name(), "float");
+        assert_eq!(Value::from(3).type_name(), "int");
+        assert_eq!(Value::from(true).type_name(), "boolean");
+        assert_eq!(Value::from(TupleType::new()).type_name(), "tuple");
+        assert_eq!(Value::from(MapType::new()).type_name(), "map");
+        assert_eq!(Value::Empty.type_name(), "empty");
+    }
+
+    #[test]
+    fn test_value_numeric_eq() {
+        assert!(Value::from(2).numeric_eq(&Value::from(2.0)));
+        assert!(Value::from(-0.0).numeric_eq(&Value::from(0)));
+        assert!(Value::from(2.5).numeric_eq(&Value::from(2.5)));
+        assert!(!Value::from(2).numeric_eq(&Value::from(2.5)));
+        assert!(!Value::from(f64::NAN).numeric_eq(&Value::from(f64::NAN)));
+        assert!(Value::from("a").numeric_eq(&Value::from("a")));
+        assert!(!Value::from(1).numeric_eq(&Value::from(true)));
+        assert!(!Value::from("2").numeric_eq(&Value::from(2)));
+        assert!(Value::Empty.numeric_eq(&Value::Empty));
+        assert!(
+            Value::from(vec![Value::from(1), Value::from(vec![Value::from(2.0)])]).numeric_eq(
+                &Value::from(vec![Value::from(1.0), Value::from(vec![Value::from(2)])])
+            )
+        );
+        assert!(!Value::from(vec![Value::from(1)])
+            .numeric_eq(&Value::from(vec![Value::from(1), Value::from(1)])));
+
+        let map = |key: &str, value: Value| Value::from(MapType::from([(key.to_owned(), value)]));
+        assert!(map("a", Value::from(1)).numeric_eq(&map("a", Value::from(1.0))));
+        assert!(!map("a", Value::from(1)).numeric_eq(&map("b", Value::from(1))));
+    }
+
+    #[test]
+    fn test_value_truthiness() {
+        assert!(Value::from(true).is_truthy());
+        assert!(Value::from(-1).is_truthy());
+        assert!(Value::from(0.5).is_truthy());
+        assert!(Value::from("false").is_truthy());
+        assert!(Value::from(vec![Value::from(false)]).is_truthy());
+        assert!(!Value::from(false).is_truthy());
+        assert!(!Value::from(0).is_truthy());
+        assert!(!Value::from(0.0).is_truthy());
+        assert!(!Value::from(-0.0).is_truthy());
+        assert!(!Value::from(f64::NAN).is_truthy());
+        assert!(!Value::from("").is_truthy());
+        assert!(!Value::from(TupleType::new()).is_truthy());
+        assert!(!Value::from(MapType::new()).is_truthy());
+        assert!(!Value::Empty.is_truthy());
+    }
+
+    #[test]
+    fn test_value_ordering() {
+        assert!(Value::from(1) < Value::from(2));
+        assert!(Value::from(1) < Value::from(1.5));
+        assert!(Value::from(2.5) > Value::from(2));
+        assert!(Value::from(-0.5) < Value::from(0.0));
+        assert!(Value::from("abc") < Value::from("abd"));
+        assert!(Value::from("Z") < Value::from("a"));
+        assert!(Value::from(false) < Value::from(true));
+        assert!(
+            Value::from(vec![Value::from(1), Value::from("b")])
+                < Value::from(vec![Value::from(1), Value::from("c")])
+        );
+        assert!(
+            Value::from(vec![Value::from(1)]) < Value::from(vec![Value::from(1), Value::from(0)])
+        );
+        assert_eq!(
+            Value::Empty.partial_cmp(&Value::Empty),
+            Some(Ordering::Equal)
+        );
+        assert_eq!(
+            Value::from(3).partial_cmp(&Value::from(3)),
+            Some(Ordering::Equal)
+        );
+
+        assert_eq!(Value::from("1").partial_cmp(&Value::from(1)), None);
+        assert_eq!(Value::from(true).partial_cmp(&Value::from(1)), None);
+        assert_eq!(
+            Value::from(1).partial_cmp(&Value::from(1.0)),
+            Some(Ordering::Equal)
+        );
+        assert_eq!(
+            Value::from(1.0).partial_cmp(&Value::from(1)),
+            Some(Ordering::Equal)
+        );
+        assert!(Value::from(1) <= Value::from(1.0));
+        assert!(Value::from(1.5) > Value::from(1));
+        assert_eq!(
+            Value::from(IntType::MAX).partial_cmp(&Value::from(IntType::MAX as FloatType)),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            Value::from(IntType::MIN).partial_cmp(&Value::from(IntType::MIN as FloatType)),
+            Some(Ordering::Equal)
+        );
+        assert_eq!(
+            Value::from((1 << 53) + 1).partial_cmp(&Value::from((1u64 << 53) as FloatType)),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(Value::from(f64::NAN).partial_cmp(&Value::from(1)), None);
+        assert_eq!(
+            Value::Empty.partial_cmp(&Value::from(TupleType::new())),
+            None
+        );
+        assert_eq!(
+            Value::from(MapType::new()).partial_cmp(&Value::from(MapType::new())),
+            Some(Ordering::Equal)
+        );
+        assert_eq!(
+            Value::from(vec![Value::from(1)]).partial_cmp(&Value::from(vec![Value::from("1")])),
+            None
+        );
+
+        let mut values = vec![
+            Value::from(3),
+            Value::from(0.5),
+            Value::from(-2),
+            Value::from(2.5),
+            Value::from(3.0),
+        ];
+        values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(
+            values,
+            vec![
+                Value::from(-2),
+                Value::from(0.5),
+                Value::from(2.5),
+                Value::from(3),
+                Value::from(3.0)
+            ]
+        );
     }
 }
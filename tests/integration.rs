@@ -23,6 +23,27 @@ fn test_unary_examples() {
     assert_eq!(eval("10e3"), Ok(Value::Float(10000.0)));
     assert_eq!(eval("10e+3"), Ok(Value::Float(10000.0)));
     assert_eq!(eval("10e-3"), Ok(Value::Float(0.01)));
+    assert_eq!(eval("1.5e3"), Ok(Value::Float(1500.0)));
+    assert_eq!(eval("2E-4"), Ok(Value::Float(0.0002)));
+    assert_eq!(eval("6.02e23"), Ok(Value::Float(6.02e23)));
+    assert_eq!(eval("2e3+1"), Ok(Value::Float(2001.0)));
+    assert_eq!(
+        eval("1.5e"),
+        Err(EvalexprError::IllegalNumberLiteral("1.5e".to_string()))
+    );
+    assert_eq!(
+        eval("2E"),
+        Err(EvalexprError::IllegalNumberLiteral("2E".to_string()))
+    );
+    assert_eq!(
+        eval("2e-"),
+        Err(EvalexprError::IllegalNumberLiteral("2e".to_string()))
+    );
+    assert_eq!(
+        eval("2e+a"),
+        Err(EvalexprError::IllegalNumberLiteral("2e".to_string()))
+    );
+    assert_eq!(eval("0xE"), Ok(Value::Int(14)));
 }
 
 #[test]
@@ -66,6 +87,35 @@ fn test_arithmetic_precedence_examples() {
     assert_eq!(eval("-15.0/7/-2"), Ok(Value::Float(-15.0 / 7.0 / -2.0)));
 }
 
+#[test]
+fn test_operator_precedence() {
+    assert_eq!(Operator::Index.precedence(), 180);
+    assert_eq!(Operator::Exp.precedence(), 120);
+    assert_eq!(Operator::Neg.precedence(), 110);
+    assert_eq!(Operator::Add.precedence(), 95);
+    assert_eq!(Operator::In.precedence(), 80);
+    assert_eq!(Operator::Tuple.precedence(), 40);
+    assert_eq!(Operator::Chain.precedence(), 0);
+    assert_eq!(
+        Operator::FunctionIdentifier {
+            identifier: "f".into()
+        }
+        .precedence(),
+        190
+    );
+    assert!(Operator::Add.is_left_to_right());
+    assert!(Operator::Eq.is_left_to_right());
+    assert!(!Operator::Exp.is_left_to_right());
+    assert!(!Operator::Assign.is_left_to_right());
+
+    // Operators of higher precedence end up deeper in the tree.
+    let tree = build_operator_tree("1 + 2 * 3").unwrap();
+    let add = &tree.children()[0];
+    assert_eq!(add.operator(), &Operator::Add);
+    assert_eq!(add.children()[1].operator(), &Operator::Mul);
+    assert!(Operator::Mul.precedence() > Operator::Add.precedence());
+}
+
 #[test]
 fn test_braced_examples() {
     assert_eq!(eval("(1)"), Ok(Value::Int(1)));
@@ -84,17 +134,92 @@ fn test_mod_examples() {
     assert_eq!(eval("1 % 4"), Ok(Value::Int(1)));
     assert_eq!(eval("6 % 4"), Ok(Value::Int(2)));
     assert_eq!(eval("1 % 4 + 2"), Ok(Value::Int(3)));
+    assert_eq!(eval("7 % 2.5"), Ok(Value::Float(2.0)));
+    assert_eq!(eval("7.5 % 2"), Ok(Value::Float(1.5)));
+    assert_eq!(
+        eval("7 % 0"),
+        Err(EvalexprError::ModulationError {
+            dividend: Value::Int(7),
+            divisor: Value::Int(0)
+        })
+    );
 }
 
 #[test]
-fn test_pow_examples() {
-    assert_eq!(eval("1 ^ 4"), Ok(Value::Float(1.0)));
+fn test_float_literals() {
+    assert_eq!(eval_with_float_literals("5 / 2"), Ok(Value::Float(2.5)));
+    assert_eq!(eval_with_float_literals("7 % 4"), Ok(Value::Float(3.0)));
+    assert_eq!(eval_with_float_literals("2 ^ -1"), Ok(Value::Float(0.5)));
     assert_eq!(
-        eval("6 ^ 4"),
+        eval_with_float_literals("-(1 / 4)"),
+        Ok(Value::Float(-0.25))
+    );
+    assert_eq!(
+        eval_with_float_literals("1 / 0"),
+        Ok(Value::Float(FloatType::INFINITY))
+    );
+    assert_eq!(
+        eval_with_float_literals("(1, (2, \"3\"))"),
+        Ok(Value::from(vec![
+            Value::Float(1.0),
+            Value::from(vec![Value::Float(2.0), Value::from("3")])
+        ]))
+    );
+    assert_eq!(
+        eval_with_float_literals("(1, 2)[0]"),
+        Err(EvalexprError::expected_int(Value::Float(0.0)))
+    );
+
+    let mut tree = build_operator_tree("a / 2 + b / c").unwrap();
+    tree.convert_int_literals_to_floats();
+    let context = context_map! { "a" => 3, "b" => 5, "c" => 2 }.unwrap();
+    assert_eq!(tree.eval_with_context(&context), Ok(Value::Float(3.5)));
+    assert_eq!(tree.to_string(), "a / 2.0 + b / c");
+}
+
+#[test]
+fn test_pow_examples() {
+    assert_eq!(eval("1 ^ 4"), Ok(Value::Int(1)));
+    assert_eq!(eval("6 ^ 4"), Ok(Value::Int(1296)));
+    assert_eq!(eval("1 ^ 4 + 2"), Ok(Value::Int(3)));
+    assert_eq!(eval("2 ^ (4 + 2)"), Ok(Value::Int(64)));
+    assert_eq!(eval("2 ^ 10"), Ok(Value::Int(1024)));
+    assert_eq!(eval("2 ^ 3 ^ 2"), Ok(Value::Int(512)));
+    assert_eq!(eval("(2 ^ 3) ^ 2"), Ok(Value::Int(64)));
+    assert_eq!(eval("2 * 3 ^ 2"), Ok(Value::Int(18)));
+    assert_eq!(
+        eval("6.0 ^ 4"),
         Ok(Value::Float((6.0 as FloatType).powf(4.0)))
     );
-    assert_eq!(eval("1 ^ 4 + 2"), Ok(Value::Float(3.0)));
-    assert_eq!(eval("2 ^ (4 + 2)"), Ok(Value::Float(64.0)));
+    assert_eq!(eval("4 ^ 0.5"), Ok(Value::Float(2.0)));
+    assert_eq!(eval("2 ^ -1"), Ok(Value::Float(0.5)));
+    assert_eq!(
+        eval("2 ^ 63"),
+        Err(EvalexprError::ExponentiationError {
+            base: Value::Int(2),
+            exponent: Value::Int(63)
+        })
+    );
+}
+
+#[test]
+fn test_double_star_pow() {
+    assert_eq!(eval("2 ** 8"), Ok(Value::Int(256)));
+    assert_eq!(eval("2**8"), Ok(Value::Int(256)));
+    assert_eq!(eval("2 ** 3 ** 2"), Ok(Value::Int(512)));
+    assert_eq!(eval("2 * 3 ** 2"), Ok(Value::Int(18)));
+    assert_eq!(eval("-2 ** 2"), Ok(Value::Int(-4)));
+    assert_eq!(eval("2 ** -1"), Ok(Value::Float(0.5)));
+    assert_eq!(eval("a = 3; a **= 2; a"), Ok(Value::Int(9)));
+    assert_eq!(build_operator_tree("2 ** 8").unwrap().to_string(), "2 ^ 8");
+    assert_eq!(
+        eval("2 * *3"),
+        Err(EvalexprError::WrongOperatorArgumentAmount {
+            expected: 2,
+            actual: 1
+        }
+        .at_position(2))
+    );
 }
 
 #[test]
@@ -106,1452 +231,3815 @@ fn test_boolean_examples() {
     );
     assert_eq!(eval("5 > 4 && 1 <= 1"), Ok(Value::Boolean(true)));
     assert_eq!(eval("5.0 <= 4.9 || !(4 > 3.5)"), Ok(Value::Boolean(false)));
+    assert_eq!(eval("!true == false"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("!!true"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("!false && false"), Ok(Value::Boolean(false)));
+    assert_eq!(eval("!(1 == 1)"), Ok(Value::Boolean(false)));
+    assert_eq!(
+        eval("!1"),
+        Err(EvalexprError::expected_boolean(Value::Int(1)))
+    );
+    assert_eq!(
+        eval("!1 == 1"),
+        Err(EvalexprError::expected_boolean(Value::Int(1)))
+    );
+
+    let context = context_map! {
+        "done" => false,
+    }
+    .unwrap();
+    assert_eq!(eval_boolean_with_context("!done", &context), Ok(true));
 }
 
 #[test]
-fn test_with_context() {
+fn test_short_circuit_examples() {
+    assert_eq!(eval("false && (1 / 0 == 0)"), Ok(Value::Boolean(false)));
+    assert_eq!(eval("true || (1 / 0 == 0)"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("false && undefined"), Ok(Value::Boolean(false)));
+    assert_eq!(eval_boolean("true || 5"), Ok(true));
+    assert_eq!(
+        eval("true && (1 / 0 == 0)"),
+        Err(EvalexprError::DivisionError {
+            dividend: Value::Int(1),
+            divisor: Value::Int(0)
+        })
+    );
+    assert_eq!(
+        eval("true && 5"),
+        Err(EvalexprError::expected_boolean(Value::Int(5)))
+    );
+    assert_eq!(
+        eval("5 && false"),
+        Err(EvalexprError::expected_boolean(Value::Int(5)))
+    );
+
     let mut context = HashMapContext::new();
-    context
-        .set_value("tr".into(), Value::Boolean(true))
-        .unwrap();
-    context
-        .set_value("fa".into(), Value::Boolean(false))
-        .unwrap();
-    context.set_value("five".into(), Value::Int(5)).unwrap();
-    context.set_value("six".into(), Value::Int(6)).unwrap();
-    context.set_value("half".into(), Value::Float(0.5)).unwrap();
-    context.set_value("zero".into(), Value::Int(0)).unwrap();
+    assert_eq!(
+        eval_boolean_with_context_mut("false && (a = 5; true)", &mut context),
+        Ok(false)
+    );
+    assert_eq!(context.get_value("a"), None);
+    assert_eq!(
+        eval_boolean_with_context_mut("true && (a = 5; true)", &mut context),
+        Ok(true)
+    );
+    assert_eq!(context.get_value("a"), Some(&Value::Int(5)));
+}
 
-    assert_eq!(eval_with_context("tr", &context), Ok(Value::Boolean(true)));
-    assert_eq!(eval_with_context("fa", &context), Ok(Value::Boolean(false)));
+#[test]
+fn test_bitwise_examples() {
+    assert_eq!(eval("6 & 3"), Ok(Value::Int(2)));
+    assert_eq!(eval("6 | 3"), Ok(Value::Int(7)));
+    assert_eq!(eval("6 ^^ 3"), Ok(Value::Int(5)));
+    assert_eq!(eval("6^^3"), Ok(Value::Int(5)));
+    assert_eq!(eval("2 ^ 3 ^^ 1"), Ok(Value::Int(9)));
+    assert_eq!(eval("1 | 2 & 3"), Ok(Value::Int(3)));
+    assert_eq!(eval("1 | 6 ^^ 3"), Ok(Value::Int(5)));
     assert_eq!(
-        eval_with_context("tr && false", &context),
-        Ok(Value::Boolean(false))
+        eval("4 & 4 == 4"),
+        Err(EvalexprError::expected_int(Value::Boolean(true)))
     );
+    assert_eq!(eval("(4 & 4) == 4 && true"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("true && false || true"), Ok(Value::Boolean(true)));
     assert_eq!(
-        eval_with_context("five + six", &context),
-        Ok(Value::Int(11))
+        eval("a = 12; a &&= true; a"),
+        Err(EvalexprError::expected_boolean(Value::Int(12)))
     );
     assert_eq!(
-        eval_with_context("five * half", &context),
-        Ok(Value::Float(2.5))
+        eval("6 & 3.0"),
+        Err(EvalexprError::expected_int(Value::Float(3.0)))
     );
     assert_eq!(
-        eval_with_context("five < six && true", &context),
-        Ok(Value::Boolean(true))
+        eval("\"a\" | 3"),
+        Err(EvalexprError::expected_int(Value::from("a")))
+    );
+    assert_eq!(
+        eval("true ^^ 3"),
+        Err(EvalexprError::expected_int(Value::Boolean(true)))
     );
 }
 
 #[test]
-fn test_functions() {
-    let mut context = HashMapContext::new();
-    context
-        .set_function(
-            "sub2".to_string(),
-            Function::new(|argument| {
-                if let Value::Int(int) = argument {
-                    Ok(Value::Int(int - 2))
-                } else if let Value::Float(float) = argument {
-                    Ok(Value::Float(float - 2.0))
-                } else {
-                    Err(EvalexprError::expected_number(argument.clone()))
-                }
-            }),
-        )
-        .unwrap();
-    context
-        .set_value("five".to_string(), Value::Int(5))
-        .unwrap();
-
-    assert_eq!(eval_with_context("sub2 5", &context), Ok(Value::Int(3)));
-    assert_eq!(eval_with_context("sub2(5)", &context), Ok(Value::Int(3)));
-    assert_eq!(eval_with_context("sub2 five", &context), Ok(Value::Int(3)));
-    assert_eq!(eval_with_context("sub2(five)", &context), Ok(Value::Int(3)));
+fn test_radix_literals() {
+    assert_eq!(eval("0xFF"), Ok(Value::Int(255)));
+    assert_eq!(eval("0xff"), Ok(Value::Int(255)));
+    assert_eq!(eval("0o17"), Ok(Value::Int(15)));
+    assert_eq!(eval("0b1010"), Ok(Value::Int(10)));
+    assert_eq!(eval("-0x10"), Ok(Value::Int(-16)));
+    assert_eq!(eval("0xF0 | 0b1111"), Ok(Value::Int(255)));
+    assert_eq!(eval("0x7FFFFFFFFFFFFFFF"), Ok(Value::Int(IntType::MAX)));
+    assert_eq!(
+        eval("0x8000000000000000"),
+        Err(EvalexprError::IllegalNumberLiteral(
+            "0x8000000000000000".to_string()
+        ))
+    );
     assert_eq!(
-        eval_with_context("sub2(3) + five", &context),
-        Ok(Value::Int(6))
+        eval("0b102"),
+        Err(EvalexprError::IllegalNumberLiteral("0b102".to_string()))
+    );
+    assert_eq!(
+        eval("0o8"),
+        Err(EvalexprError::IllegalNumberLiteral("0o8".to_string()))
+    );
+    assert_eq!(
+        eval("0xFG"),
+        Err(EvalexprError::IllegalNumberLiteral("0xFG".to_string()))
+    );
+    assert_eq!(
+        eval("0x"),
+        Err(EvalexprError::IllegalNumberLiteral("0x".to_string()))
     );
 }
 
 #[test]
-fn test_n_ary_functions() {
-    let mut context = HashMapContext::new();
-    context
-        .set_function(
-            "sub2".into(),
-            Function::new(|argument| {
-                if let Value::Int(int) = argument {
-                    Ok(Value::Int(int - 2))
-                } else if let Value::Float(float) = argument {
-                    Ok(Value::Float(float - 2.0))
-                } else {
-                    Err(EvalexprError::expected_number(argument.clone()))
-                }
-            }),
-        )
-        .unwrap();
-    context
-        .set_function(
-            "avg".into(),
-            Function::new(|argument| {
-                let arguments = argument.as_tuple()?;
-                arguments[0].as_number()?;
-                arguments[1].as_number()?;
-
-                if let (Value::Int(a), Value::Int(b)) = (&arguments[0], &arguments[1]) {
-                    Ok(Value::Int((a + b) / 2))
-                } else {
-                    Ok(Value::Float(
-                        (arguments[0].as_float()? + arguments[1].as_float()?) / 2.0,
-                    ))
-                }
-            }),
-        )
-        .unwrap();
-    context
-        .set_function(
-            "muladd".into(),
-            Function::new(|argument| {
-                let arguments = argument.as_tuple()?;
-                arguments[0].as_number()?;
-                arguments[1].as_number()?;
-                arguments[2].as_number()?;
-
-                if let (Value::Int(a), Value::Int(b), Value::Int(c)) =
-                    (&arguments[0], &arguments[1], &arguments[2])
-                {
-                    Ok(Value::Int(a * b + c))
-                } else {
-                    Ok(Value::Float(
-                        arguments[0].as_float()? * arguments[1].as_float()?
-                            + arguments[2].as_float()?,
-                    ))
-                }
-            }),
-        )
-        .unwrap();
-    context
-        .set_function(
-            "count".into(),
-            Function::new(|arguments| match arguments {
-                Value::Tuple(tuple) => Ok(Value::from(tuple.len() as IntType)),
-                Value::Empty => Ok(Value::from(0)),
-                _ => Ok(Value::from(1)),
-            }),
-        )
-        .unwrap();
-    context
-        .set_value("five".to_string(), Value::Int(5))
-        .unwrap();
-    context
-        .set_function("function_four".into(), Function::new(|_| Ok(Value::Int(4))))
-        .unwrap();
-
-    assert_eq!(eval_with_context("avg(7, 5)", &context), Ok(Value::Int(6)));
+fn test_digit_separators() {
+    assert_eq!(eval("1_000_000"), Ok(Value::Int(1_000_000)));
+    assert_eq!(eval("1.234_567"), Ok(Value::Float(1.234_567)));
+    assert_eq!(eval("1_0e-3"), Ok(Value::Float(0.01)));
+    assert_eq!(eval("1_0e3"), Ok(Value::Float(10e3)));
+    assert_eq!(eval("0xFF_FF"), Ok(Value::Int(0xFFFF)));
+    assert_eq!(eval("0b1010_1010"), Ok(Value::Int(0b1010_1010)));
+    assert_eq!(eval("-1_000"), Ok(Value::Int(-1000)));
     assert_eq!(
-        eval_with_context("avg(sub2 5, 5)", &context),
-        Ok(Value::Int(4))
+        eval("5_"),
+        Err(EvalexprError::IllegalNumberLiteral("5_".to_string()))
     );
     assert_eq!(
-        eval_with_context("sub2(avg(3, 6))", &context),
-        Ok(Value::Int(2))
+        eval("1__0"),
+        Err(EvalexprError::IllegalNumberLiteral("1__0".to_string()))
     );
     assert_eq!(
-        eval_with_context("sub2 avg(3, 6)", &context),
-        Ok(Value::Int(2))
+        eval("1_.5"),
+        Err(EvalexprError::IllegalNumberLiteral("1_.5".to_string()))
     );
     assert_eq!(
-        eval_with_context("muladd(3, 6, -4)", &context),
-        Ok(Value::Int(14))
+        eval("0x_FF"),
+        Err(EvalexprError::IllegalNumberLiteral("0x_FF".to_string()))
     );
-    assert_eq!(eval_with_context("count()", &context), Ok(Value::Int(0)));
     assert_eq!(
-        eval_with_context("count((1, 2, 3))", &context),
-        Ok(Value::Int(3))
+        eval("0b_1"),
+        Err(EvalexprError::IllegalNumberLiteral("0b_1".to_string()))
     );
     assert_eq!(
-        eval_with_context("count(3, 5.5, 2)", &context),
-        Ok(Value::Int(3))
+        eval("1_0xyz"),
+        Err(EvalexprError::VariableIdentifierNotFound(
+            "1_0xyz".to_string()
+        ))
     );
-    assert_eq!(eval_with_context("count 5", &context), Ok(Value::Int(1)));
+    // Identifiers may start with an underscore.
     assert_eq!(
-        eval_with_context("function_four()", &context),
-        Ok(Value::Int(4))
+        eval("_5"),
+        Err(EvalexprError::VariableIdentifierNotFound("_5".to_string()))
     );
 }
 
 #[test]
-fn test_capturing_functions() {
-    let mut context = HashMapContext::new();
-    // this variable is captured by the function
-    let three = 3;
-    context
-        .set_function(
-            "mult_3".into(),
-            Function::new(move |argument| {
-                if let Value::Int(int) = argument {
-                    Ok(Value::Int(int * three))
-                } else if let Value::Float(float) = argument {
-                    Ok(Value::Float(float * three as FloatType))
-                } else {
-                    Err(EvalexprError::expected_number(argument.clone()))
-                }
-            }),
-        )
-        .unwrap();
-
-    let four = 4;
-    context
-        .set_function(
-            "function_four".into(),
-            Function::new(move |_| Ok(Value::Int(four))),
-        )
-        .unwrap();
-
-    assert_eq!(eval_with_context("mult_3 2", &context), Ok(Value::Int(6)));
-    assert_eq!(eval_with_context("mult_3(3)", &context), Ok(Value::Int(9)));
+fn test_shift_examples() {
+    assert_eq!(eval("1 << 4"), Ok(Value::Int(16)));
+    assert_eq!(eval("16 >> 2"), Ok(Value::Int(4)));
+    assert_eq!(eval("-16 >> 2"), Ok(Value::Int(-4)));
+    assert_eq!(eval("1 << 2 + 1"), Ok(Value::Int(8)));
+    assert_eq!(eval("1 << 2 < 5"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("1 << 63"), Ok(Value::Int(IntType::MIN)));
+    assert_eq!(eval("1 <= 2"), Ok(Value::Boolean(true)));
+    assert_eq!(
+        eval("1 << 64"),
+        Err(EvalexprError::ShiftLeftError {
+            value: Value::Int(1),
+            shift: Value::Int(64)
+        })
+    );
     assert_eq!(
-        eval_with_context("mult_3(function_four())", &context),
-        Ok(Value::Int(12))
+        eval("1 >> -1"),
+        Err(EvalexprError::ShiftRightError {
+            value: Value::Int(1),
+            shift: Value::Int(-1)
+        })
+    );
+    assert_eq!(
+        eval("1.0 << 1"),
+        Err(EvalexprError::expected_int(Value::Float(1.0)))
     );
 }
 
 #[test]
-fn test_builtin_functions() {
-    // Log
-    assert_eq!(eval("math::ln(2.718281828459045)"), Ok(Value::Float(1.0)));
-    assert_eq!(eval("math::log(9, 9)"), Ok(Value::Float(1.0)));
-    assert_eq!(eval("math::log2(2)"), Ok(Value::Float(1.0)));
-    assert_eq!(eval("math::log10(10)"), Ok(Value::Float(1.0)));
-    // Powers
+fn test_conditional_examples() {
+    assert_eq!(eval("5 > 3 ? \"pass\" : \"fail\""), Ok(Value::from("pass")));
+    assert_eq!(eval("5 < 3 ? \"pass\" : \"fail\""), Ok(Value::from("fail")));
+    assert_eq!(eval("1 + 1 == 2 ? 10 : 20"), Ok(Value::Int(10)));
+    assert_eq!(eval("false ? 1 : false ? 2 : 3"), Ok(Value::Int(3)));
+    assert_eq!(eval("false ? 1 : true ? 2 : 3"), Ok(Value::Int(2)));
+    assert_eq!(eval("true ? (false ? 1 : 2) : 3"), Ok(Value::Int(2)));
+    assert_eq!(eval("true ? false ? 1 : 2 : 3"), Ok(Value::Int(2)));
+    assert_eq!(eval("false ? true ? 1 : 2 : 3"), Ok(Value::Int(3)));
+    assert_eq!(
+        eval("true ? false ? 1 : true ? 2 : 3 : 4"),
+        Ok(Value::Int(2))
+    );
     assert_eq!(
-        eval("math::exp(2)"),
-        Ok(Value::Float((2.0 as FloatType).exp()))
+        eval("true ? 1, 2 : 3"),
+        Ok(Value::Tuple(vec![Value::Int(1), Value::Int(2)]))
     );
+    assert_eq!(eval("true ? b = 5; b * 2 : 0"), Ok(Value::Int(10)));
+    assert_eq!(eval("{\"a\": false ? 1 : 2}"), eval("{\"a\": 2}"));
+    assert_eq!(eval("(true ? 1 : 2) + 1"), Ok(Value::Int(2)));
+    assert_eq!(eval("a = false ? 1 : 2; a"), Ok(Value::Int(2)));
+    assert_eq!(eval("true ? 1 : 1 / 0"), Ok(Value::Int(1)));
+    assert_eq!(eval("false ? undefined : 4"), Ok(Value::Int(4)));
+    assert_eq!(eval("math::abs(true ? -2 : 3)"), Ok(Value::Int(2)));
     assert_eq!(
-        eval("math::exp2(2)"),
-        Ok(Value::Float((2.0 as FloatType).exp2()))
-    );
-    assert_eq!(
-        eval("math::pow(1.5, 1.3)"),
-        Ok(Value::Float((1.5 as FloatType).powf(1.3)))
+        eval("3 ? 1 : 2"),
+        Err(EvalexprError::expected_boolean(Value::Int(3)))
     );
-    // Cos
-    assert_eq!(eval("math::cos(0)"), Ok(Value::Float(1.0)));
-    assert_eq!(eval("math::acos(1)"), Ok(Value::Float(0.0)));
-    assert_eq!(eval("math::cosh(0)"), Ok(Value::Float(1.0)));
-    assert_eq!(eval("math::acosh(1)"), Ok(Value::Float(0.0)));
-    // Sin
-    assert_eq!(eval("math::sin(0)"), Ok(Value::Float(0.0)));
-    assert_eq!(eval("math::asin(0)"), Ok(Value::Float(0.0)));
-    assert_eq!(eval("math::sinh(0)"), Ok(Value::Float(0.0)));
-    assert_eq!(eval("math::asinh(0)"), Ok(Value::Float(0.0)));
-    // Tan
-    assert_eq!(eval("math::tan(0)"), Ok(Value::Float(0.0)));
-    assert_eq!(eval("math::atan(0)"), Ok(Value::Float(0.0)));
-    assert_eq!(eval("math::tanh(0)"), Ok(Value::Float(0.0)));
-    assert_eq!(eval("math::atanh(0)"), Ok(Value::Float(0.0)));
     assert_eq!(
-        eval("math::atan2(1.2, -5.5)"),
-        Ok(Value::Float((1.2 as FloatType).atan2(-5.5)))
+        eval("true ? 1"),
+        Err(EvalexprError::UnmatchedQuestionMark.at_position(5))
     );
-    // Root
-    assert_eq!(eval("math::sqrt(25)"), Ok(Value::Float(5.0)));
-    assert_eq!(eval("math::cbrt(8)"), Ok(Value::Float(2.0)));
-    // Hypotenuse
     assert_eq!(
-        eval("math::hypot(8.2, 1.1)"),
-        Ok(Value::Float((8.2 as FloatType).hypot(1.1)))
+        eval("1 : 2"),
+        Err(EvalexprError::UnmatchedColon.at_position(2))
     );
-    // Absolute
-    assert_eq!(eval("math::abs(15.4)"), Ok(Value::Float(15.4)));
-    assert_eq!(eval("math::abs(-15.4)"), Ok(Value::Float(15.4)));
-    assert_eq!(eval("math::abs(15)"), Ok(Value::Int(15)));
-    assert_eq!(eval("math::abs(-15)"), Ok(Value::Int(15)));
-    // Rounding
-    assert_eq!(eval("floor(1.1)"), Ok(Value::Float(1.0)));
-    assert_eq!(eval("floor(1.9)"), Ok(Value::Float(1.0)));
-    assert_eq!(eval("round(1.1)"), Ok(Value::Float(1.0)));
-    assert_eq!(eval("round(1.5)"), Ok(Value::Float(2.0)));
-    assert_eq!(eval("round(2.5)"), Ok(Value::Float(3.0)));
-    assert_eq!(eval("round(1.9)"), Ok(Value::Float(2.0)));
-    assert_eq!(eval("ceil(1.1)"), Ok(Value::Float(2.0)));
-    assert_eq!(eval("ceil(1.9)"), Ok(Value::Float(2.0)));
-    assert_eq!(eval("math::is_nan(1.0/0.0)"), Ok(Value::Boolean(false)));
-    assert_eq!(eval("math::is_nan(0.0/0.0)"), Ok(Value::Boolean(true)));
-    assert_eq!(eval("math::is_finite(1.0/0.0)"), Ok(Value::Boolean(false)));
-    assert_eq!(eval("math::is_finite(0.0/0.0)"), Ok(Value::Boolean(false)));
-    assert_eq!(eval("math::is_finite(0.0)"), Ok(Value::Boolean(true)));
     assert_eq!(
-        eval("math::is_infinite(0.0/0.0)"),
-        Ok(Value::Boolean(false))
+        eval("(true ? 1) : 2"),
+        Err(EvalexprError::UnmatchedQuestionMark.at_position(6))
     );
-    assert_eq!(eval("math::is_infinite(1.0/0.0)"), Ok(Value::Boolean(true)));
-    assert_eq!(eval("math::is_normal(1.0/0.0)"), Ok(Value::Boolean(false)));
-    assert_eq!(eval("math::is_normal(0)"), Ok(Value::Boolean(false)));
-    // Other
-    assert_eq!(eval("typeof(4.0, 3)"), Ok(Value::String("tuple".into())));
-    assert_eq!(eval("typeof(4.0)"), Ok(Value::String("float".into())));
-    assert_eq!(eval("typeof(4)"), Ok(Value::String("int".into())));
-    assert_eq!(eval("typeof(\"\")"), Ok(Value::String("string".into())));
-    assert_eq!(eval("typeof(true)"), Ok(Value::String("boolean".into())));
-    assert_eq!(eval("typeof()"), Ok(Value::String("empty".into())));
-    assert_eq!(eval("min(4.0, 3)"), Ok(Value::Int(3)));
-    assert_eq!(eval("max(4.0, 3)"), Ok(Value::Float(4.0)));
-    assert_eq!(eval("len(\"foobar\")"), Ok(Value::Int(6)));
-    assert_eq!(eval("len(\"a\", \"b\")"), Ok(Value::Int(2)));
-    //Contians
     assert_eq!(
-        eval("contains(1, 2, 3)"),
-        Err(EvalexprError::expected_fixed_len_tuple(
-            2,
-            Value::Tuple(vec![Value::Int(1), Value::Int(2), Value::Int(3)])
-        ))
+        eval("true ? : 2"),
+        Err(EvalexprError::WrongOperatorArgumentAmount {
+            expected: 2,
+            actual: 1
+        }
+        .at_position(5))
     );
     assert_eq!(
-        eval("contains((\"foo\", \"bar\"), \"bar\")"),
-        Ok(Value::Boolean(true))
+        build_operator_tree("true?false?1:2:3").unwrap().to_string(),
+        "true ? false ? 1 : 2 : 3"
     );
+
+    let mut context = HashMapContext::new();
     assert_eq!(
-        eval("contains((\"foo\", \"bar\"), \"buzz\")"),
-        Ok(Value::Boolean(false)),
+        eval_with_context_mut("a = 1; true ? (a = 2) : (a = 3); a", &mut context),
+        Ok(Value::Int(2))
     );
+}
+
+#[test]
+fn test_trailing_comma() {
+    assert_eq!(eval("(1, 2, 3,)"), eval("(1, 2, 3)"));
+    assert_eq!(eval("1, 2,"), eval("1, 2"));
+    assert_eq!(eval("(1,)"), Ok(Value::Tuple(vec![Value::Int(1)])));
+    assert_eq!(eval("max(3, 4,)"), Ok(Value::Int(4)));
+    assert_eq!(eval("((1, 2,), 3,)"), eval("((1, 2), 3)"));
+    assert_eq!(eval("a = (1, 2,); a[1]"), Ok(Value::Int(2)));
+    assert_eq!(
+        eval("(4, 5,)[0,]"),
+        Err(EvalexprError::expected_int(Value::Tuple(vec![Value::Int(
+            0
+        )])))
+    );
+    assert_eq!(eval("(\n  1,\n  2,\n)"), eval("(1, 2)"));
+
     assert_eq!(
-        eval("contains(\"foo\", \"bar\")"),
-        Err(EvalexprError::expected_tuple(Value::String("foo".into())))
+        eval("(,)"),
+        Err(EvalexprError::EmptyTupleElement.at_position(1))
     );
     assert_eq!(
-        eval("contains((\"foo\", \"bar\", 123), 123)"),
-        Ok(Value::Boolean(true))
+        eval("(, 1)"),
+        Err(EvalexprError::EmptyTupleElement.at_position(1))
     );
     assert_eq!(
-        eval("contains((\"foo\", \"bar\"), (\"buzz\", \"bazz\"))"),
-        Err(EvalexprError::type_error(
-            Value::Tuple(vec![
-                Value::String("buzz".into()),
-                Value::String("bazz".into())
-            ]),
-            vec![
-                ValueType::String,
-                ValueType::Int,
-                ValueType::Float,
-                ValueType::Boolean
-            ]
-        ))
+        eval("(1,, 2)"),
+        Err(EvalexprError::EmptyTupleElement.at_position(3))
     );
-    //Contains Any
     assert_eq!(
-        eval("contains_any(1, 2, 3)"),
-        Err(EvalexprError::expected_fixed_len_tuple(
-            2,
-            Value::Tuple(vec![Value::Int(1), Value::Int(2), Value::Int(3)])
-        ))
+        eval(","),
+        Err(EvalexprError::EmptyTupleElement.at_position(0))
     );
     assert_eq!(
-        eval("contains_any((\"foo\", \"bar\"), (\"bar\", \"buzz\"))"),
-        Ok(Value::Boolean(true))
+        eval("f(,)"),
+        Err(EvalexprError::EmptyTupleElement.at_position(2))
     );
+}
+
+#[test]
+fn test_index_operator() {
+    let context = context_map! {
+        "t" => Value::Tuple(vec![
+            Value::Tuple(vec![Value::Int(1), Value::Int(2)]),
+            Value::Int(5),
+            Value::Int(7),
+        ]),
+        "f" => Function::new(|argument| Ok(argument.clone())),
+    }
+    .unwrap();
+
+    assert_eq!(eval("(10, 20, 30)[1]"), Ok(Value::Int(20)));
+    assert_eq!(eval("(10, 20, 30) [ 2 ]"), Ok(Value::Int(30)));
+    assert_eq!(eval_with_context("t[0][1]", &context), Ok(Value::Int(2)));
     assert_eq!(
-        eval("contains_any((\"foo\", \"bar\"), (\"buzz\", \"bazz\"))"),
-        Ok(Value::Boolean(false)),
+        eval_with_context("t[1 + 1] * 2", &context),
+        Ok(Value::Int(14))
     );
+    assert_eq!(eval_with_context("-t[2]", &context), Ok(Value::Int(-7)));
+    assert_eq!(eval_with_context("2 ^ t[2]", &context), Ok(Value::Int(128)));
+    assert_eq!(eval_with_context("f(t)[1]", &context), Ok(Value::Int(5)));
+    assert_eq!(eval_with_context("f t[1]", &context), Ok(Value::Int(5)));
     assert_eq!(
-        eval("contains_any((1,2,3), (3,4,5))"),
-        Ok(Value::Boolean(true))
+        eval_with_context("t[1], t[2]", &context),
+        Ok(Value::Tuple(vec![Value::Int(5), Value::Int(7)]))
     );
     assert_eq!(
-        eval("contains_any((1,2,3), (4,5,6))"),
-        Ok(Value::Boolean(false))
+        eval_with_context_mut("a = t[1]; a", &mut context.clone()),
+        Ok(Value::Int(5))
     );
+
     assert_eq!(
-        eval("contains_any((true, false, true, true), (false, false, false))"),
-        Ok(Value::Boolean(true))
+        eval_with_context("t[3]", &context),
+        Err(EvalexprError::IndexOutOfBounds {
+            index: 3,
+            length: 3
+        })
     );
     assert_eq!(
-        eval("contains_any(\"foo\", \"bar\")"),
-        Err(EvalexprError::expected_tuple(Value::String("foo".into())))
+        eval_with_context("t[-1]", &context),
+        Err(EvalexprError::IndexOutOfBounds {
+            index: -1,
+            length: 3
+        })
     );
     assert_eq!(
-        eval("contains_any((\"foo\", \"bar\"), \"buzz\")"),
-        Err(EvalexprError::expected_tuple(Value::String("buzz".into())))
+        eval("(1, 2)[0.5]"),
+        Err(EvalexprError::expected_int(Value::Float(0.5)))
     );
     assert_eq!(
-        eval("contains_any((\"foo\", \"bar\"), (\"buzz\", (1, 2, 3)))"),
+        eval("5[0]"),
         Err(EvalexprError::type_error(
-            Value::Tuple(vec![Value::Int(1), Value::Int(2), Value::Int(3)]),
-            vec![
-                ValueType::String,
-                ValueType::Int,
-                ValueType::Float,
-                ValueType::Boolean
-            ]
+            Value::Int(5),
+            vec![ValueType::Tuple, ValueType::Map]
         ))
     );
-    // String
     assert_eq!(
-        eval("str::to_lowercase(\"FOOBAR\")"),
-        Ok(Value::from("foobar"))
+        eval("(1, 2)[0"),
+        Err(EvalexprError::UnmatchedLBracket.at_position(6))
     );
     assert_eq!(
-        eval("str::to_uppercase(\"foobar\")"),
-        Ok(Value::from("FOOBAR"))
+        eval("(1, 2)]"),
+        Err(EvalexprError::UnmatchedRBracket.at_position(6))
     );
     assert_eq!(
-        eval("str::trim(\"  foo  bar \")"),
-        Ok(Value::from("foo  bar"))
+        eval("((1, 2)[0)"),
+        Err(EvalexprError::UnmatchedRBrace.at_position(9))
     );
     assert_eq!(
-        eval("str::from(\"a\")"),
-        Ok(Value::String(String::from("\"a\"")))
+        eval("(1, 2)[(0])"),
+        Err(EvalexprError::UnmatchedRBracket.at_position(9))
     );
-    assert_eq!(eval("str::from(1.0)"), Ok(Value::String(String::from("1"))));
-    assert_eq!(eval("str::from(1)"), Ok(Value::String(String::from("1"))));
+}
+
+#[test]
+fn test_map() {
+    let map = |entries: &[(&str, Value)]| {
+        Value::Map(
+            entries
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.clone()))
+                .collect(),
+        )
+    };
+
+    assert_eq!(eval("{}"), Ok(Value::Map(MapType::new())));
     assert_eq!(
-        eval("str::from(true)"),
-        Ok(Value::String(String::from("true")))
+        eval("{\"a\": 1, \"b\": (2, 3), \"c\": {\"d\": true}}"),
+        Ok(map(&[
+            ("a", Value::Int(1)),
+            ("b", Value::from(vec![Value::Int(2), Value::Int(3)])),
+            ("c", map(&[("d", Value::Boolean(true))]))
+        ]))
     );
     assert_eq!(
-        eval("str::from(1, 2, 3)"),
-        Ok(Value::String(String::from("(1, 2, 3)")))
+        eval("{\"a\": 1 + 2 * 3, \"b\" + \"c\": true ? 4 : 5,}"),
+        Ok(map(&[("a", Value::Int(7)), ("bc", Value::Int(4))]))
     );
-    assert_eq!(eval("str::from()"), Ok(Value::String(String::from("()"))));
-    // Bitwise
-    assert_eq!(eval("bitand(5, -1)"), Ok(Value::Int(5)));
-    assert_eq!(eval("bitand(6, 5)"), Ok(Value::Int(4)));
-    assert_eq!(eval("bitor(5, -1)"), Ok(Value::Int(-1)));
-    assert_eq!(eval("bitor(6, 5)"), Ok(Value::Int(7)));
-    assert_eq!(eval("bitxor(5, -1)"), Ok(Value::Int(-6)));
-    assert_eq!(eval("bitxor(6, 5)"), Ok(Value::Int(3)));
-    assert_eq!(eval("bitnot(5)"), Ok(Value::Int(-6)));
-    assert_eq!(eval("bitnot(-1)"), Ok(Value::Int(0)));
-    assert_eq!(eval("shl(5, 1)"), Ok(Value::Int(10)));
-    assert_eq!(eval("shl(-6, 5)"), Ok(Value::Int(-192)));
-    assert_eq!(eval("shr(5, 1)"), Ok(Value::Int(2)));
-    assert_eq!(eval("shr(-6, 5)"), Ok(Value::Int(-1)));
-    assert_eq!(eval("if(true, -6, 5)"), Ok(Value::Int(-6)));
-    assert_eq!(eval("if(false, -6, 5)"), Ok(Value::Int(5)));
     assert_eq!(
-        eval("if(2-1==1, \"good\", 0)"),
-        Ok(Value::String(String::from("good")))
+        eval("{\"a\": 1, \"a\": 2}"),
+        Ok(map(&[("a", Value::Int(2))]))
     );
-}
+    assert_eq!(eval("{\"a\": 1} == {\"a\": 1}"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("len({\"a\": 1, \"b\": 2})"), Ok(Value::Int(2)));
+    assert_eq!(eval("typeof({})"), Ok(Value::from("map")));
 
-#[test]
-fn test_errors() {
-    assert_eq!(
-        eval("-true"),
-        Err(EvalexprError::expected_number(Value::Boolean(true)))
-    );
+    assert_eq!(eval("{\"a\": 1, \"b\": 2}[\"b\"]"), Ok(Value::Int(2)));
     assert_eq!(
-        eval("1-true"),
-        Err(EvalexprError::expected_number(Value::Boolean(true)))
+        eval("{\"a\": {\"b\": (1, 2)}}[\"a\"][\"b\"][1]"),
+        Ok(Value::Int(2))
     );
     assert_eq!(
-        eval("true-"),
-        Err(EvalexprError::WrongOperatorArgumentAmount {
-            actual: 1,
-            expected: 2,
-        })
+        eval("{\"a\": 1}[\"b\"]"),
+        Err(EvalexprError::KeyNotFound("b".to_owned()))
     );
-    assert_eq!(eval("!(()true)"), Err(EvalexprError::AppendedToLeafNode));
     assert_eq!(
-        eval("math::is_nan(\"xxx\")"),
-        Err(EvalexprError::ExpectedNumber {
-            actual: Value::String("xxx".to_string())
-        })
+        eval("{\"a\": 1}[0]"),
+        Err(EvalexprError::expected_string(Value::Int(0)))
     );
-}
-
-#[test]
-fn test_no_panic() {
-    assert!(eval(&format!(
-        "{} + {}",
-        IntType::max_value(),
-        IntType::max_value()
-    ))
-    .is_err());
-    assert!(eval(&format!(
-        "-{} - {}",
-        IntType::max_value(),
-        IntType::max_value()
-    ))
-    .is_err());
-    assert!(eval(&format!("-(-{} - 1)", IntType::max_value())).is_err());
-    assert!(eval(&format!(
-        "{} * {}",
-        IntType::max_value(),
-        IntType::max_value()
-    ))
-    .is_err());
-    assert!(eval(&format!("{} / {}", IntType::max_value(), 0)).is_err());
-    assert!(eval(&format!("{} % {}", IntType::max_value(), 0)).is_err());
-    assert!(eval(&format!(
-        "{} ^ {}",
-        IntType::max_value(),
-        IntType::max_value()
-    ))
-    .is_ok());
-    assert!(eval("if").is_err());
-    assert!(eval("if()").is_err());
-    assert!(eval("if(true, 1)").is_err());
-    assert!(eval("if(false, 2)").is_err());
-    assert!(eval("if(1,1,1)").is_err());
-    assert!(eval("if(true,1,1,1)").is_err());
-}
-
-#[test]
-fn test_shortcut_functions() {
-    let mut context = HashMapContext::new();
-    context
-        .set_value("string".into(), Value::from("a string"))
-        .unwrap();
 
-    assert_eq!(eval_string("\"3.3\""), Ok("3.3".to_owned()));
-    assert_eq!(
-        eval_string("3.3"),
-        Err(EvalexprError::ExpectedString {
-            actual: Value::Float(3.3)
-        })
-    );
     assert_eq!(
-        eval_string("3..3"),
-        Err(EvalexprError::VariableIdentifierNotFound("3..3".to_owned()))
+        eval("{1: 2}"),
+        Err(EvalexprError::expected_string(Value::Int(1)))
     );
     assert_eq!(
-        eval_string_with_context("string", &context),
-        Ok("a string".to_owned())
+        eval("{1}"),
+        Err(EvalexprError::IllegalMapEntry.at_position(2))
     );
     assert_eq!(
-        eval_string_with_context("3.3", &context),
-        Err(EvalexprError::ExpectedString {
-            actual: Value::Float(3.3)
-        })
+        eval("{\"a\": 1, 2}"),
+        Err(EvalexprError::IllegalMapEntry.at_position(10))
     );
     assert_eq!(
-        eval_string_with_context("3..3", &context),
-        Err(EvalexprError::VariableIdentifierNotFound("3..3".to_owned()))
+        eval("{true ? \"a\" : \"b\"}"),
+        Err(EvalexprError::IllegalMapEntry.at_position(17))
     );
     assert_eq!(
-        eval_string_with_context_mut("string", &mut context),
-        Ok("a string".to_string())
+        eval("{\"a\": 1"),
+        Err(EvalexprError::UnmatchedLCurlyBrace.at_position(0))
     );
     assert_eq!(
-        eval_string_with_context_mut("3.3", &mut context),
-        Err(EvalexprError::ExpectedString {
-            actual: Value::Float(3.3)
-        })
+        eval("\"a\": 1}"),
+        Err(EvalexprError::UnmatchedRCurlyBrace.at_position(6))
     );
     assert_eq!(
-        eval_string_with_context_mut("3..3", &mut context),
-        Err(EvalexprError::VariableIdentifierNotFound("3..3".to_owned()))
+        eval("({\"a\": 1)}"),
+        Err(EvalexprError::UnmatchedRBrace.at_position(8))
     );
 
-    assert_eq!(eval_float("3.3"), Ok(3.3));
+    let mut context = HashMapContext::new();
     assert_eq!(
-        eval_float("33"),
-        Err(EvalexprError::ExpectedFloat {
-            actual: Value::Int(33)
-        })
+        eval_with_context_mut(
+            "user = {\"name\": \"Ada\", \"address\": {\"city\": \"London\"}}; user.address.city",
+            &mut context
+        ),
+        Ok(Value::from("London"))
     );
     assert_eq!(
-        eval_float("asd()"),
-        Err(EvalexprError::FunctionIdentifierNotFound("asd".to_owned()))
+        eval_with_context("user.name + \" \" + user[\"address\"][\"city\"]", &context),
+        Ok(Value::from("Ada London"))
     );
-    assert_eq!(eval_float_with_context("3.3", &context), Ok(3.3));
     assert_eq!(
-        eval_float_with_context("33", &context),
-        Err(EvalexprError::ExpectedFloat {
-            actual: Value::Int(33)
-        })
+        eval_with_context("user.email", &context),
+        Err(EvalexprError::VariableIdentifierNotFound(
+            "user.email".to_owned()
+        ))
     );
     assert_eq!(
-        eval_float_with_context("asd)", &context),
-        Err(EvalexprError::UnmatchedRBrace)
+        eval_with_context("user.name.first", &context),
+        Err(EvalexprError::VariableIdentifierNotFound(
+            "user.name.first".to_owned()
+        ))
+    );
+
+    for expression in [
+        "{}",
+        "{\"a\": 1, \"b\": (2, 3)}",
+        "{\"a\": {\"b\": 2}}[\"a\"][\"b\"]",
+        "f({\"a\": 1})",
+    ] {
+        assert_eq!(
+            build_operator_tree(expression).unwrap().to_string(),
+            expression
+        );
+    }
+    assert_eq!(
+        eval("{\"b\": (1, \"x\"), \"a\": {}}").unwrap().to_string(),
+        "{\"a\": {}, \"b\": (1, \"x\")}"
+    );
+}
+
+#[test]
+fn test_lazy_if_function() {
+    let context = context_map! { "x" => 3, "y" => -1 }.unwrap();
+    assert_eq!(
+        eval_with_context("if(x > 0, \"pos\", \"neg\")", &context),
+        Ok(Value::from("pos"))
+    );
+    assert_eq!(
+        eval_with_context("if(y > 0, \"pos\", \"neg\")", &context),
+        Ok(Value::from("neg"))
+    );
+    assert_eq!(eval("if(true, 1, 1 / 0)"), Ok(Value::Int(1)));
+    assert_eq!(
+        eval("if(false, undefined, (4, 5))"),
+        Ok(Value::Tuple(vec![Value::Int(4), Value::Int(5)]))
+    );
+    assert_eq!(eval("if(false, 1, if(true, 2, 3))"), Ok(Value::Int(2)));
+    assert_eq!(eval("if(true, 1, 2) + 1"), Ok(Value::Int(2)));
+    assert_eq!(
+        eval("if(3, 1, 2)"),
+        Err(EvalexprError::expected_boolean(Value::Int(3)))
+    );
+    assert_eq!(
+        eval("if(true, 1)"),
+        Err(EvalexprError::expected_fixed_len_tuple(
+            3,
+            Value::Tuple(vec![Value::Boolean(true), Value::Int(1)])
+        ))
+    );
+    // An argument tuple that is not written out is evaluated eagerly
+    assert_eq!(
+        eval_with_context_mut("a = (false, 1, 2); if(a)", &mut HashMapContext::new()),
+        Ok(Value::Int(2))
+    );
+
+    let mut context = HashMapContext::new();
+    assert_eq!(
+        eval_with_context_mut("a = 1; if(true, a = 2, a = 3); a", &mut context),
+        Ok(Value::Int(2))
+    );
+
+    context.set_builtin_functions_disabled(true).unwrap();
+    assert_eq!(
+        eval_with_context("if(true, 1, 2)", &context),
+        Err(EvalexprError::FunctionIdentifierNotFound("if".to_string()))
+    );
+
+    // A function `if` in the context takes precedence over the builtin
+    let context = context_map! { "if" => Function::new(|_| Ok(Value::from(42))) }.unwrap();
+    assert_eq!(
+        eval_with_context("if(true, 1, 2)", &context),
+        Ok(Value::from(42))
+    );
+    assert_eq!(
+        eval_with_context(
+            "if(true, 1, 2)",
+            &CombinedContext::new(&HashMapContext::new(), &context)
+        ),
+        Ok(Value::from(42))
+    );
+    assert!(context.has_function("if"));
+    assert!(!HashMapContext::new().has_function("if"));
+}
+
+#[test]
+fn test_with_context() {
+    let mut context = HashMapContext::new();
+    context
+        .set_value("tr".into(), Value::Boolean(true))
+        .unwrap();
+    context
+        .set_value("fa".into(), Value::Boolean(false))
+        .unwrap();
+    context.set_value("five".into(), Value::Int(5)).unwrap();
+    context.set_value("six".into(), Value::Int(6)).unwrap();
+    context.set_value("half".into(), Value::Float(0.5)).unwrap();
+    context.set_value("zero".into(), Value::Int(0)).unwrap();
+
+    assert_eq!(eval_with_context("tr", &context), Ok(Value::Boolean(true)));
+    assert_eq!(eval_with_context("fa", &context), Ok(Value::Boolean(false)));
+    assert_eq!(
+        eval_with_context("tr && false", &context),
+        Ok(Value::Boolean(false))
+    );
+    assert_eq!(
+        eval_with_context("five + six", &context),
+        Ok(Value::Int(11))
+    );
+    assert_eq!(
+        eval_with_context("five * half", &context),
+        Ok(Value::Float(2.5))
+    );
+    assert_eq!(
+        eval_with_context("five < six && true", &context),
+        Ok(Value::Boolean(true))
+    );
+    assert_eq!(eval_with_context("-five", &context), Ok(Value::Int(-5)));
+    assert_eq!(eval_with_context("-half", &context), Ok(Value::Float(-0.5)));
+    assert_eq!(
+        eval_with_context("six - -five", &context),
+        Ok(Value::Int(11))
+    );
+    assert_eq!(
+        eval_with_context("-tr", &context),
+        Err(EvalexprError::expected_number(Value::Boolean(true)))
+    );
+}
+
+#[test]
+fn test_functions() {
+    let mut context = HashMapContext::new();
+    context
+        .set_function(
+            "sub2".to_string(),
+            Function::new(|argument| {
+                if let Value::Int(int) = argument {
+                    Ok(Value::Int(int - 2))
+                } else if let Value::Float(float) = argument {
+                    Ok(Value::Float(float - 2.0))
+                } else {
+                    Err(EvalexprError::expected_number(argument.clone()))
+                }
+            }),
+        )
+        .unwrap();
+    context
+        .set_value("five".to_string(), Value::Int(5))
+        .unwrap();
+
+    assert_eq!(eval_with_context("sub2 5", &context), Ok(Value::Int(3)));
+    assert_eq!(eval_with_context("sub2(5)", &context), Ok(Value::Int(3)));
+    assert_eq!(eval_with_context("sub2 five", &context), Ok(Value::Int(3)));
+    assert_eq!(eval_with_context("sub2(five)", &context), Ok(Value::Int(3)));
+    assert_eq!(
+        eval_with_context("sub2(3) + five", &context),
+        Ok(Value::Int(6))
+    );
+
+    let sub2 = context.get_function("sub2").unwrap();
+    assert_eq!(sub2.call(&Value::Int(5)), Ok(Value::Int(3)));
+    assert!(context.get_function("five").is_none());
+    assert!(context.get_function("max").is_none());
+    assert!(EmptyContext.get_function("sub2").is_none());
+}
+
+#[test]
+fn test_n_ary_functions() {
+    let mut context = HashMapContext::new();
+    context
+        .set_function(
+            "sub2".into(),
+            Function::new(|argument| {
+                if let Value::Int(int) = argument {
+                    Ok(Value::Int(int - 2))
+                } else if let Value::Float(float) = argument {
+                    Ok(Value::Float(float - 2.0))
+                } else {
+                    Err(EvalexprError::expected_number(argument.clone()))
+                }
+            }),
+        )
+        .unwrap();
+    context
+        .set_function(
+            "avg".into(),
+            Function::new(|argument| {
+                let arguments = argument.as_tuple()?;
+                arguments[0].as_number()?;
+                arguments[1].as_number()?;
+
+                if let (Value::Int(a), Value::Int(b)) = (&arguments[0], &arguments[1]) {
+                    Ok(Value::Int((a + b) / 2))
+                } else {
+                    Ok(Value::Float(
+                        (arguments[0].as_float()? + arguments[1].as_float()?) / 2.0,
+                    ))
+                }
+            }),
+        )
+        .unwrap();
+    context
+        .set_function(
+            "muladd".into(),
+            Function::new(|argument| {
+                let arguments = argument.as_tuple()?;
+                arguments[0].as_number()?;
+                arguments[1].as_number()?;
+                arguments[2].as_number()?;
+
+                if let (Value::Int(a), Value::Int(b), Value::Int(c)) =
+                    (&arguments[0], &arguments[1], &arguments[2])
+                {
+                    Ok(Value::Int(a * b + c))
+                } else {
+                    Ok(Value::Float(
+                        arguments[0].as_float()? * arguments[1].as_float()?
+                            + arguments[2].as_float()?,
+                    ))
+                }
+            }),
+        )
+        .unwrap();
+    context
+        .set_function(
+            "count".into(),
+            Function::new(|arguments| match arguments {
+                Value::Tuple(tuple) => Ok(Value::from(tuple.len() as IntType)),
+                Value::Empty => Ok(Value::from(0)),
+                _ => Ok(Value::from(1)),
+            }),
+        )
+        .unwrap();
+    context
+        .set_value("five".to_string(), Value::Int(5))
+        .unwrap();
+    context
+        .set_function("function_four".into(), Function::new(|_| Ok(Value::Int(4))))
+        .unwrap();
+
+    assert_eq!(eval_with_context("avg(7, 5)", &context), Ok(Value::Int(6)));
+    assert_eq!(
+        eval_with_context("avg(sub2 5, 5)", &context),
+        Ok(Value::Int(4))
+    );
+    assert_eq!(
+        eval_with_context("sub2(avg(3, 6))", &context),
+        Ok(Value::Int(2))
+    );
+    assert_eq!(
+        eval_with_context("sub2 avg(3, 6)", &context),
+        Ok(Value::Int(2))
+    );
+    assert_eq!(
+        eval_with_context("muladd(3, 6, -4)", &context),
+        Ok(Value::Int(14))
+    );
+    assert_eq!(eval_with_context("count()", &context), Ok(Value::Int(0)));
+    assert_eq!(
+        eval_with_context("count((1, 2, 3))", &context),
+        Ok(Value::Int(3))
+    );
+    assert_eq!(
+        eval_with_context("count(3, 5.5, 2)", &context),
+        Ok(Value::Int(3))
+    );
+    assert_eq!(eval_with_context("count 5", &context), Ok(Value::Int(1)));
+    assert_eq!(
+        eval_with_context("function_four()", &context),
+        Ok(Value::Int(4))
+    );
+}
+
+#[test]
+fn test_capturing_functions() {
+    let mut context = HashMapContext::new();
+    // this variable is captured by the function
+    let three = 3;
+    context
+        .set_function(
+            "mult_3".into(),
+            Function::new(move |argument| {
+                if let Value::Int(int) = argument {
+                    Ok(Value::Int(int * three))
+                } else if let Value::Float(float) = argument {
+                    Ok(Value::Float(float * three as FloatType))
+                } else {
+                    Err(EvalexprError::expected_number(argument.clone()))
+                }
+            }),
+        )
+        .unwrap();
+
+    let four = 4;
+    context
+        .set_function(
+            "function_four".into(),
+            Function::new(move |_| Ok(Value::Int(four))),
+        )
+        .unwrap();
+
+    assert_eq!(eval_with_context("mult_3 2", &context), Ok(Value::Int(6)));
+    assert_eq!(eval_with_context("mult_3(3)", &context), Ok(Value::Int(9)));
+    assert_eq!(
+        eval_with_context("mult_3(function_four())", &context),
+        Ok(Value::Int(12))
+    );
+}
+
+#[test]
+fn test_builtin_functions() {
+    // Log
+    assert_eq!(eval("math::ln(2.718281828459045)"), Ok(Value::Float(1.0)));
+    assert_eq!(eval("math::log(9, 9)"), Ok(Value::Float(1.0)));
+    assert_eq!(eval("math::log2(2)"), Ok(Value::Float(1.0)));
+    assert_eq!(eval("math::log10(10)"), Ok(Value::Float(1.0)));
+    assert_eq!(eval("math::log(8, 2)"), Ok(Value::Float(3.0)));
+    assert_eq!(eval("math::log(0.5, 0.5)"), Ok(Value::Float(1.0)));
+    assert_eq!(eval("math::log2(0.25)"), Ok(Value::Float(-2.0)));
+    assert_eq!(eval("math::log10(1000)"), Ok(Value::Float(3.0)));
+    assert_eq!(
+        eval("math::ln(0)"),
+        Err(EvalexprError::LogarithmError {
+            argument: Value::Int(0),
+            base: Value::Float(std::f64::consts::E)
+        })
+    );
+    assert_eq!(
+        eval("math::log2(-1.5)"),
+        Err(EvalexprError::LogarithmError {
+            argument: Value::Float(-1.5),
+            base: Value::Int(2)
+        })
+    );
+    assert_eq!(
+        eval("math::log10(-10)"),
+        Err(EvalexprError::LogarithmError {
+            argument: Value::Int(-10),
+            base: Value::Int(10)
+        })
+    );
+    assert_eq!(
+        eval("math::log(8, 1)"),
+        Err(EvalexprError::LogarithmError {
+            argument: Value::Int(8),
+            base: Value::Int(1)
+        })
+    );
+    assert_eq!(
+        eval("math::log(8, -2)"),
+        Err(EvalexprError::LogarithmError {
+            argument: Value::Int(8),
+            base: Value::Int(-2)
+        })
+    );
+    assert_eq!(
+        eval("math::ln(\"e\")"),
+        Err(EvalexprError::expected_number(Value::from("e")))
+    );
+    // Powers
+    assert_eq!(
+        eval("math::exp(2)"),
+        Ok(Value::Float((2.0 as FloatType).exp()))
+    );
+    assert_eq!(
+        eval("math::exp2(2)"),
+        Ok(Value::Float((2.0 as FloatType).exp2()))
+    );
+    assert_eq!(
+        eval("math::pow(1.5, 1.3)"),
+        Ok(Value::Float((1.5 as FloatType).powf(1.3)))
+    );
+    // Cos
+    assert_eq!(eval("math::cos(0)"), Ok(Value::Float(1.0)));
+    assert_eq!(eval("math::acos(1)"), Ok(Value::Float(0.0)));
+    assert_eq!(eval("math::cosh(0)"), Ok(Value::Float(1.0)));
+    assert_eq!(eval("math::acosh(1)"), Ok(Value::Float(0.0)));
+    // Sin
+    assert_eq!(eval("math::sin(0)"), Ok(Value::Float(0.0)));
+    assert_eq!(eval("math::asin(0)"), Ok(Value::Float(0.0)));
+    assert_eq!(eval("math::sinh(0)"), Ok(Value::Float(0.0)));
+    assert_eq!(eval("math::asinh(0)"), Ok(Value::Float(0.0)));
+    // Tan
+    assert_eq!(eval("math::tan(0)"), Ok(Value::Float(0.0)));
+    assert_eq!(eval("math::atan(0)"), Ok(Value::Float(0.0)));
+    assert_eq!(eval("math::tanh(0)"), Ok(Value::Float(0.0)));
+    assert_eq!(eval("math::atanh(0)"), Ok(Value::Float(0.0)));
+    assert_eq!(
+        eval("math::atan2(1.2, -5.5)"),
+        Ok(Value::Float((1.2 as FloatType).atan2(-5.5)))
+    );
+    // Trigonometric functions operate in radians
+    assert_eq!(
+        eval("math::sin(1.5)"),
+        Ok(Value::Float((1.5 as FloatType).sin()))
+    );
+    assert_eq!(
+        eval("math::cos(2)"),
+        Ok(Value::Float((2.0 as FloatType).cos()))
+    );
+    assert_eq!(
+        eval("math::tan(-0.5)"),
+        Ok(Value::Float((-0.5 as FloatType).tan()))
+    );
+    assert_eq!(
+        eval("math::asin(1)"),
+        Ok(Value::Float(core::f64::consts::FRAC_PI_2))
+    );
+    assert_eq!(
+        eval("math::acos(-1)"),
+        Ok(Value::Float(core::f64::consts::PI))
+    );
+    assert_eq!(
+        eval("math::atan(1)"),
+        Ok(Value::Float(core::f64::consts::FRAC_PI_4))
+    );
+    assert_eq!(
+        eval("math::sin(\"1\")"),
+        Err(EvalexprError::expected_number(Value::from("1")))
+    );
+    assert_eq!(
+        eval("math::atan(())"),
+        Err(EvalexprError::expected_number(Value::Empty))
+    );
+    // Root
+    assert_eq!(eval("math::sqrt(25)"), Ok(Value::Float(5.0)));
+    assert_eq!(eval("math::cbrt(8)"), Ok(Value::Float(2.0)));
+    // Hypotenuse
+    assert_eq!(
+        eval("math::hypot(8.2, 1.1)"),
+        Ok(Value::Float((8.2 as FloatType).hypot(1.1)))
+    );
+    assert_eq!(
+        eval("math::sqrt(\"16\")"),
+        Err(EvalexprError::expected_number(Value::from("16")))
+    );
+    assert_eq!(
+        eval("math::pow(2)"),
+        Err(EvalexprError::expected_tuple(Value::Int(2)))
+    );
+    assert_eq!(
+        eval("math::pow(2, 3, 4)"),
+        Err(EvalexprError::expected_fixed_len_tuple(
+            2,
+            Value::Tuple(vec![Value::Int(2), Value::Int(3), Value::Int(4)])
+        ))
+    );
+    // Absolute
+    assert_eq!(eval("math::abs(15.4)"), Ok(Value::Float(15.4)));
+    assert_eq!(eval("math::abs(-15.4)"), Ok(Value::Float(15.4)));
+    assert_eq!(eval("math::abs(15)"), Ok(Value::Int(15)));
+    assert_eq!(eval("math::abs(-15)"), Ok(Value::Int(15)));
+    assert_eq!(
+        eval(&format!("math::abs({})", IntType::MAX)),
+        Ok(Value::Int(IntType::MAX))
+    );
+    assert_eq!(
+        eval(&format!("math::abs(-{} - 1)", IntType::MAX)),
+        Err(EvalexprError::NegationError {
+            argument: Value::Int(IntType::MIN)
+        })
+    );
+    // Sign
+    assert_eq!(eval("math::signum(-4.0)"), Ok(Value::Float(-1.0)));
+    assert_eq!(eval("math::signum(0.5)"), Ok(Value::Float(1.0)));
+    assert_eq!(eval("math::signum(0.0)"), Ok(Value::Float(0.0)));
+    assert!(eval_float("math::signum(math::sqrt(-1))").unwrap().is_nan());
+    assert_eq!(eval("math::signum(-4)"), Ok(Value::Int(-1)));
+    assert_eq!(eval("math::signum(0)"), Ok(Value::Int(0)));
+    assert_eq!(eval("math::signum(7)"), Ok(Value::Int(1)));
+    assert_eq!(
+        eval("math::signum(\"1\")"),
+        Err(EvalexprError::expected_number(Value::from("1")))
+    );
+    assert_eq!(eval("math::copysign(3.0, -1.0)"), Ok(Value::Float(-3.0)));
+    assert_eq!(eval("math::copysign(-3, 2)"), Ok(Value::Float(3.0)));
+    assert_eq!(eval("math::copysign(3, -0.0)"), Ok(Value::Float(-3.0)));
+    assert_eq!(
+        eval("math::copysign(3, true)"),
+        Err(EvalexprError::expected_number(Value::from(true)))
+    );
+    // Rounding
+    assert_eq!(eval("floor(1.1)"), Ok(Value::Float(1.0)));
+    assert_eq!(eval("floor(1.9)"), Ok(Value::Float(1.0)));
+    assert_eq!(eval("round(1.1)"), Ok(Value::Float(1.0)));
+    assert_eq!(eval("round(1.5)"), Ok(Value::Float(2.0)));
+    assert_eq!(eval("round(2.5)"), Ok(Value::Float(3.0)));
+    assert_eq!(eval("round(1.9)"), Ok(Value::Float(2.0)));
+    assert_eq!(eval("ceil(1.1)"), Ok(Value::Float(2.0)));
+    assert_eq!(eval("ceil(1.9)"), Ok(Value::Float(2.0)));
+    assert_eq!(eval("round(-2.5)"), Ok(Value::Float(-3.0)));
+    assert_eq!(eval("round(0.5)"), Ok(Value::Float(1.0)));
+    assert_eq!(eval("floor(-1.1)"), Ok(Value::Float(-2.0)));
+    assert_eq!(eval("ceil(-1.9)"), Ok(Value::Float(-1.0)));
+    assert_eq!(eval("floor(3)"), Ok(Value::Float(3.0)));
+    assert_eq!(eval("round(-4)"), Ok(Value::Float(-4.0)));
+    assert_eq!(eval("ceil(5)"), Ok(Value::Float(5.0)));
+    assert_eq!(
+        eval("floor(\"3.7\")"),
+        Err(EvalexprError::expected_number(Value::from("3.7")))
+    );
+    assert_eq!(
+        eval("round(true)"),
+        Err(EvalexprError::expected_number(Value::Boolean(true)))
+    );
+    assert_eq!(eval("math::is_nan(1.0/0.0)"), Ok(Value::Boolean(false)));
+    assert_eq!(eval("math::is_nan(0.0/0.0)"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("math::is_finite(1.0/0.0)"), Ok(Value::Boolean(false)));
+    assert_eq!(eval("math::is_finite(0.0/0.0)"), Ok(Value::Boolean(false)));
+    assert_eq!(eval("math::is_finite(0.0)"), Ok(Value::Boolean(true)));
+    assert_eq!(
+        eval("math::is_infinite(0.0/0.0)"),
+        Ok(Value::Boolean(false))
+    );
+    assert_eq!(eval("math::is_infinite(1.0/0.0)"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("math::is_normal(1.0/0.0)"), Ok(Value::Boolean(false)));
+    assert_eq!(eval("math::is_normal(0)"), Ok(Value::Boolean(false)));
+    // Other
+    assert_eq!(eval("typeof(4.0, 3)"), Ok(Value::String("tuple".into())));
+    assert_eq!(eval("typeof(4.0)"), Ok(Value::String("float".into())));
+    assert_eq!(eval("typeof(4)"), Ok(Value::String("int".into())));
+    assert_eq!(eval("typeof(\"\")"), Ok(Value::String("string".into())));
+    assert_eq!(eval("typeof(true)"), Ok(Value::String("boolean".into())));
+    assert_eq!(eval("typeof()"), Ok(Value::String("empty".into())));
+    assert_eq!(eval("min(4.0, 3)"), Ok(Value::Int(3)));
+    assert_eq!(eval("max(4.0, 3)"), Ok(Value::Float(4.0)));
+    assert_eq!(eval("max((3, 7, 2))"), Ok(Value::Int(7)));
+    assert_eq!(eval("min((3.0, 1.5))"), Ok(Value::Float(1.5)));
+    assert_eq!(eval("min(5)"), Ok(Value::Int(5)));
+    assert_eq!(eval("max(-5.5)"), Ok(Value::Float(-5.5)));
+    assert_eq!(
+        eval("max(1, \"a\")"),
+        Err(EvalexprError::expected_number(Value::from("a")))
+    );
+    for expression in ["min(())", "max(())", "min()", "max()"] {
+        assert_eq!(
+            eval(expression),
+            Err(EvalexprError::WrongFunctionArgumentAmount {
+                expected: 1,
+                actual: 0
+            }),
+            "{}",
+            expression
+        );
+    }
+    let context = context_map! { "empty" => Value::Tuple(vec![]) }.unwrap();
+    assert_eq!(
+        eval_with_context("max(empty)", &context),
+        Err(EvalexprError::WrongFunctionArgumentAmount {
+            expected: 1,
+            actual: 0
+        })
+    );
+    assert_eq!(eval("sum(1, 2, 3)"), Ok(Value::Int(6)));
+    assert_eq!(eval("sum((1, 2, 3))"), Ok(Value::Int(6)));
+    assert_eq!(eval("sum(1, 2.5)"), Ok(Value::Float(3.5)));
+    assert_eq!(eval("sum(1.5, 2)"), Ok(Value::Float(3.5)));
+    assert_eq!(eval("sum(4)"), Ok(Value::Int(4)));
+    assert_eq!(eval("sum(())"), Ok(Value::Int(0)));
+    assert_eq!(eval("product(2, 3, 4)"), Ok(Value::Int(24)));
+    assert_eq!(eval("product((2, 0.5))"), Ok(Value::Float(1.0)));
+    assert_eq!(eval("product(())"), Ok(Value::Int(1)));
+    assert_eq!(eval_with_context("sum(empty)", &context), Ok(Value::Int(0)));
+    assert_eq!(
+        eval_with_context("product(empty)", &context),
+        Ok(Value::Int(1))
+    );
+    assert_eq!(
+        eval("sum(1, \"a\", 2)"),
+        Err(EvalexprError::expected_number(Value::from("a")))
+    );
+    assert_eq!(
+        eval("product(2, true)"),
+        Err(EvalexprError::expected_number(Value::Boolean(true)))
+    );
+    assert_eq!(
+        eval(&format!("sum({}, 1)", IntType::MAX)),
+        Err(EvalexprError::AdditionError {
+            augend: Value::Int(IntType::MAX),
+            addend: Value::Int(1)
+        })
+    );
+    assert_eq!(
+        eval(&format!("product({}, 2)", IntType::MAX)),
+        Err(EvalexprError::MultiplicationError {
+            multiplicand: Value::Int(IntType::MAX),
+            multiplier: Value::Int(2)
+        })
+    );
+    assert_eq!(eval("avg(1, 2)"), Ok(Value::Float(1.5)));
+    assert_eq!(eval("avg((2, 4, 6))"), Ok(Value::Float(4.0)));
+    assert_eq!(eval("avg(1, 2.5, 3)"), Ok(Value::Float(2.1666666666666665)));
+    assert_eq!(eval("avg(3)"), Ok(Value::Float(3.0)));
+    assert_eq!(
+        eval("avg(9223372036854775807, 1)"),
+        Ok(Value::Float(IntType::MAX as FloatType / 2.0))
+    );
+    assert_eq!(
+        eval("avg(-9223372036854775807 - 1, -9223372036854775807 - 1)"),
+        Ok(Value::Float(IntType::MIN as FloatType))
+    );
+    assert_eq!(
+        eval("avg(())"),
+        Err(EvalexprError::WrongFunctionArgumentAmount {
+            expected: 1,
+            actual: 0
+        })
+    );
+    assert_eq!(
+        eval_with_context("avg(empty)", &context),
+        Err(EvalexprError::WrongFunctionArgumentAmount {
+            expected: 1,
+            actual: 0
+        })
+    );
+    assert_eq!(
+        eval("avg(1, \"a\")"),
+        Err(EvalexprError::expected_number(Value::from("a")))
+    );
+    assert_eq!(eval("clamp(5, 0, 10)"), Ok(Value::Int(5)));
+    assert_eq!(eval("clamp(-5, 0, 10)"), Ok(Value::Int(0)));
+    assert_eq!(eval("clamp(15, 0, 10)"), Ok(Value::Int(10)));
+    assert_eq!(eval("clamp(3, 3, 3)"), Ok(Value::Int(3)));
+    assert_eq!(eval("clamp(15, 0, 10.5)"), Ok(Value::Float(10.5)));
+    assert_eq!(eval("clamp(0.5, 0, 1)"), Ok(Value::Float(0.5)));
+    assert_eq!(eval("clamp(5, 0.0, 1)"), Ok(Value::Float(1.0)));
+    assert_eq!(
+        eval("clamp(5, 10, 0)"),
+        Err(EvalexprError::IllegalRange {
+            lower: Value::Int(10),
+            upper: Value::Int(0)
+        })
+    );
+    assert_eq!(
+        eval("clamp(5, 1.5, 0.5)"),
+        Err(EvalexprError::IllegalRange {
+            lower: Value::Float(1.5),
+            upper: Value::Float(0.5)
+        })
+    );
+    assert_eq!(
+        eval("clamp(\"a\", 0, 1)"),
+        Err(EvalexprError::expected_number(Value::from("a")))
+    );
+    assert_eq!(
+        eval("clamp(1, 2)"),
+        Err(EvalexprError::ExpectedFixedLenTuple {
+            expected_len: 3,
+            actual: Value::Tuple(vec![Value::Int(1), Value::Int(2)])
+        })
+    );
+    assert_eq!(eval("between(5, 0, 10)"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("between(0, 0, 10)"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("between(10, 0, 10)"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("between(11, 0, 10)"), Ok(Value::Boolean(false)));
+    assert_eq!(eval("between(-1, 0, 10)"), Ok(Value::Boolean(false)));
+    assert_eq!(eval("between(3, 3, 3)"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("between(10.5, 0, 10)"), Ok(Value::Boolean(false)));
+    assert_eq!(eval("between(10, 0, 10.0)"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("between(0.5, 0, 1)"), Ok(Value::Boolean(true)));
+    assert_eq!(
+        eval("between(math::sqrt(-1), 0, 1)"),
+        Ok(Value::Boolean(false))
+    );
+    assert_eq!(
+        eval("between(5, 10, 0)"),
+        Err(EvalexprError::IllegalRange {
+            lower: Value::Int(10),
+            upper: Value::Int(0)
+        })
+    );
+    assert_eq!(
+        eval("between(5, 1.5, 0.5)"),
+        Err(EvalexprError::IllegalRange {
+            lower: Value::Float(1.5),
+            upper: Value::Float(0.5)
+        })
+    );
+    assert_eq!(
+        eval("between(\"b\", \"a\", \"c\")"),
+        Err(EvalexprError::expected_number(Value::from("b")))
+    );
+    assert_eq!(
+        eval("between(1, 2)"),
+        Err(EvalexprError::ExpectedFixedLenTuple {
+            expected_len: 3,
+            actual: Value::Tuple(vec![Value::Int(1), Value::Int(2)])
+        })
+    );
+    assert_eq!(eval("len(\"foobar\")"), Ok(Value::Int(6)));
+    assert_eq!(eval("len(\"a\", \"b\")"), Ok(Value::Int(2)));
+    assert_eq!(eval("len(\"äöü€\")"), Ok(Value::Int(4)));
+    assert_eq!(eval("len(1, (2, 3))"), Ok(Value::Int(2)));
+    assert_eq!(
+        eval("len(3)"),
+        Err(EvalexprError::type_error(
+            Value::Int(3),
+            vec![ValueType::String, ValueType::Tuple, ValueType::Map]
+        ))
+    );
+    assert_eq!(
+        eval("len(true)"),
+        Err(EvalexprError::type_error(
+            Value::Boolean(true),
+            vec![ValueType::String, ValueType::Tuple, ValueType::Map]
+        ))
+    );
+    // Tuple construction and access
+    assert_eq!(
+        eval("tuple(1, \"a\", 2.5)"),
+        Ok(Value::Tuple(vec![
+            Value::Int(1),
+            Value::from("a"),
+            Value::Float(2.5)
+        ]))
+    );
+    assert_eq!(eval("tuple(1)"), Ok(Value::Tuple(vec![Value::Int(1)])));
+    assert_eq!(eval("tuple()"), Ok(Value::Tuple(vec![])));
+    assert_eq!(eval("len(tuple(tuple(1, 2)))"), Ok(Value::Int(2)));
+    assert_eq!(eval("nth((3, 4, 5), 0)"), Ok(Value::Int(3)));
+    assert_eq!(eval("nth(tuple(3, 4, 5), 2)"), Ok(Value::Int(5)));
+    assert_eq!(
+        eval("t = (1, (2, 3)); nth(nth(t, 1), 1)"),
+        Ok(Value::Int(3))
+    );
+    assert_eq!(
+        eval("nth((3, 4, 5), 3)"),
+        Err(EvalexprError::IndexOutOfBounds {
+            index: 3,
+            length: 3
+        })
+    );
+    assert_eq!(
+        eval("nth((3, 4, 5), -1)"),
+        Err(EvalexprError::IndexOutOfBounds {
+            index: -1,
+            length: 3
+        })
+    );
+    assert_eq!(
+        eval("nth(tuple(), 0)"),
+        Err(EvalexprError::IndexOutOfBounds {
+            index: 0,
+            length: 0
+        })
+    );
+    assert_eq!(
+        eval("nth(\"abc\", 0)"),
+        Err(EvalexprError::expected_tuple(Value::from("abc")))
+    );
+    assert_eq!(
+        eval("nth((1, 2), 1.0)"),
+        Err(EvalexprError::expected_int(Value::Float(1.0)))
+    );
+    // Type conversion
+    assert_eq!(eval("int(\"42\") + 1"), Ok(Value::Int(43)));
+    assert_eq!(eval("int(\" -7 \")"), Ok(Value::Int(-7)));
+    assert_eq!(eval("int(3.9)"), Ok(Value::Int(3)));
+    assert_eq!(eval("int(-3.9)"), Ok(Value::Int(-3)));
+    assert_eq!(eval("int(5)"), Ok(Value::Int(5)));
+    assert_eq!(
+        eval("int(-9223372036854775808.0)"),
+        Ok(Value::Int(IntType::MIN))
+    );
+    for (expression, string) in [
+        ("int(1e20)", "100000000000000000000"),
+        ("int(9223372036854775808.0)", "9223372036854776000"),
+        ("int(-1e19)", "-10000000000000000000"),
+        ("int(1.0 / 0.0)", "inf"),
+        ("int(-1.0 / 0.0)", "-inf"),
+        ("int(0.0 / 0.0)", "NaN"),
+    ] {
+        assert_eq!(
+            eval(expression),
+            Err(EvalexprError::IllegalConversion {
+                string: string.to_string(),
+                target: ValueType::Int
+            }),
+            "{}",
+            expression
+        );
+    }
+    assert_eq!(
+        eval("int(\"4.2\")"),
+        Err(EvalexprError::IllegalConversion {
+            string: "4.2".to_string(),
+            target: ValueType::Int
+        })
+    );
+    assert_eq!(
+        eval("int(true)"),
+        Err(EvalexprError::type_error(
+            Value::Boolean(true),
+            vec![ValueType::String, ValueType::Float, ValueType::Int]
+        ))
+    );
+    assert_eq!(eval("float(\"4.5\")"), Ok(Value::Float(4.5)));
+    assert_eq!(eval("float(\"2\")"), Ok(Value::Float(2.0)));
+    assert_eq!(eval("float(2)"), Ok(Value::Float(2.0)));
+    assert_eq!(eval("float(2.5)"), Ok(Value::Float(2.5)));
+    assert_eq!(
+        eval("float(\"abc\")"),
+        Err(EvalexprError::IllegalConversion {
+            string: "abc".to_string(),
+            target: ValueType::Float
+        })
+    );
+    assert_eq!(eval("str(12)"), Ok(Value::from("12")));
+    assert_eq!(eval("str(2.5)"), Ok(Value::from("2.5")));
+    assert_eq!(eval("str(false)"), Ok(Value::from("false")));
+    assert_eq!(eval("str(\"a\")"), Ok(Value::from("a")));
+    assert_eq!(eval("str(1) + str(2)"), Ok(Value::from("12")));
+    assert_eq!(eval("format(3.14159, 2)"), Ok(Value::from("3.14")));
+    assert_eq!(eval("format(2.5, 0)"), Ok(Value::from("2")));
+    assert_eq!(eval("format(-0.125, 4)"), Ok(Value::from("-0.1250")));
+    assert_eq!(eval("format(1.999, 1)"), Ok(Value::from("2.0")));
+    assert_eq!(eval("format(42, 3)"), Ok(Value::from("42.000")));
+    assert_eq!(eval("format(-7, 0)"), Ok(Value::from("-7")));
+    assert_eq!(
+        eval("format(9223372036854775807, 1)"),
+        Ok(Value::from("9223372036854775807.0"))
+    );
+    assert_eq!(eval("format(1.0 / 0.0, 2)"), Ok(Value::from("inf")));
+    assert_eq!(
+        eval("format(3.5, -1)"),
+        Err(EvalexprError::IllegalPrecision(-1))
+    );
+    assert_eq!(
+        eval("format(3.5, 65536)"),
+        Err(EvalexprError::IllegalPrecision(65536))
+    );
+    assert_eq!(
+        eval("format(3.5, 1.0)"),
+        Err(EvalexprError::expected_int(Value::Float(1.0)))
+    );
+    assert_eq!(
+        eval("format(\"3.5\", 1)"),
+        Err(EvalexprError::expected_number(Value::from("3.5")))
+    );
+    assert_eq!(
+        eval("str(())"),
+        Err(EvalexprError::type_error(
+            Value::Empty,
+            vec![
+                ValueType::String,
+                ValueType::Float,
+                ValueType::Int,
+                ValueType::Boolean
+            ]
+        ))
+    );
+    assert_eq!(eval("bool(\"true\")"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("bool(\"false\")"), Ok(Value::Boolean(false)));
+    assert_eq!(eval("bool(true)"), Ok(Value::Boolean(true)));
+    assert_eq!(
+        eval("bool(\"yes\")"),
+        Err(EvalexprError::IllegalConversion {
+            string: "yes".to_string(),
+            target: ValueType::Boolean
+        })
+    );
+    assert_eq!(
+        eval("bool(1)"),
+        Err(EvalexprError::type_error(
+            Value::Int(1),
+            vec![ValueType::String, ValueType::Boolean]
+        ))
+    );
+    assert_eq!(
+        eval("int(\"abc\")").unwrap_err().to_string(),
+        "Cannot convert \"abc\" to Int."
+    );
+    //Contians
+    assert_eq!(
+        eval("contains(1, 2, 3)"),
+        Err(EvalexprError::expected_fixed_len_tuple(
+            2,
+            Value::Tuple(vec![Value::Int(1), Value::Int(2), Value::Int(3)])
+        ))
+    );
+    assert_eq!(
+        eval("contains((\"foo\", \"bar\"), \"bar\")"),
+        Ok(Value::Boolean(true))
+    );
+    assert_eq!(
+        eval("contains((\"foo\", \"bar\"), \"buzz\")"),
+        Ok(Value::Boolean(false)),
+    );
+    assert_eq!(
+        eval("contains(\"foo\", \"bar\")"),
+        Ok(Value::Boolean(false))
+    );
+    assert_eq!(
+        eval("contains(\"an error occurred\", \"error\")"),
+        Ok(Value::Boolean(true))
+    );
+    assert_eq!(eval("contains(\"foo\", \"\")"), Ok(Value::Boolean(true)));
+    assert_eq!(
+        eval("contains(\"foo\", 1)"),
+        Err(EvalexprError::expected_string(Value::Int(1)))
+    );
+    assert_eq!(
+        eval("contains(1, \"foo\")"),
+        Err(EvalexprError::type_error(
+            Value::Int(1),
+            vec![ValueType::String, ValueType::Tuple]
+        ))
+    );
+    assert_eq!(
+        eval("contains((\"foo\", \"bar\", 123), 123)"),
+        Ok(Value::Boolean(true))
+    );
+    assert_eq!(
+        eval("contains((\"foo\", \"bar\"), (\"buzz\", \"bazz\"))"),
+        Err(EvalexprError::type_error(
+            Value::Tuple(vec![
+                Value::String("buzz".into()),
+                Value::String("bazz".into())
+            ]),
+            vec![
+                ValueType::String,
+                ValueType::Int,
+                ValueType::Float,
+                ValueType::Boolean
+            ]
+        ))
+    );
+    //Contains Any
+    assert_eq!(
+        eval("contains_any(1, 2, 3)"),
+        Err(EvalexprError::expected_fixed_len_tuple(
+            2,
+            Value::Tuple(vec![Value::Int(1), Value::Int(2), Value::Int(3)])
+        ))
+    );
+    assert_eq!(
+        eval("contains_any((\"foo\", \"bar\"), (\"bar\", \"buzz\"))"),
+        Ok(Value::Boolean(true))
+    );
+    assert_eq!(
+        eval("contains_any((\"foo\", \"bar\"), (\"buzz\", \"bazz\"))"),
+        Ok(Value::Boolean(false)),
+    );
+    assert_eq!(
+        eval("contains_any((1,2,3), (3,4,5))"),
+        Ok(Value::Boolean(true))
+    );
+    assert_eq!(
+        eval("contains_any((1,2,3), (4,5,6))"),
+        Ok(Value::Boolean(false))
+    );
+    assert_eq!(
+        eval("contains_any((true, false, true, true), (false, false, false))"),
+        Ok(Value::Boolean(true))
+    );
+    assert_eq!(
+        eval("contains_any(\"foo\", \"bar\")"),
+        Err(EvalexprError::expected_tuple(Value::String("foo".into())))
+    );
+    assert_eq!(
+        eval("contains_any((\"foo\", \"bar\"), \"buzz\")"),
+        Err(EvalexprError::expected_tuple(Value::String("buzz".into())))
+    );
+    assert_eq!(
+        eval("contains_any((\"foo\", \"bar\"), (\"buzz\", (1, 2, 3)))"),
+        Err(EvalexprError::type_error(
+            Value::Tuple(vec![Value::Int(1), Value::Int(2), Value::Int(3)]),
+            vec![
+                ValueType::String,
+                ValueType::Int,
+                ValueType::Float,
+                ValueType::Boolean
+            ]
+        ))
+    );
+    // String
+    assert_eq!(
+        eval("str::to_lowercase(\"FOOBAR\")"),
+        Ok(Value::from("foobar"))
+    );
+    assert_eq!(
+        eval("str::to_uppercase(\"foobar\")"),
+        Ok(Value::from("FOOBAR"))
+    );
+    assert_eq!(
+        eval("str::to_lowercase(\"ÄÖÜ SS\")"),
+        Ok(Value::from("äöü ss"))
+    );
+    assert_eq!(
+        eval("str::to_uppercase(\"straße\")"),
+        Ok(Value::from("STRASSE"))
+    );
+    assert_eq!(
+        eval("str::to_lowercase(\"YES\") == \"yes\""),
+        Ok(Value::Boolean(true))
+    );
+    assert_eq!(
+        eval("str::to_lowercase(1)"),
+        Err(EvalexprError::expected_string(Value::Int(1)))
+    );
+    assert_eq!(
+        eval("str::to_uppercase(true)"),
+        Err(EvalexprError::expected_string(Value::Boolean(true)))
+    );
+    assert_eq!(
+        eval("str::trim(\"  foo  bar \")"),
+        Ok(Value::from("foo  bar"))
+    );
+    assert_eq!(
+        eval("str::trim(\"\\t\\n foo\u{3000}\")"),
+        Ok(Value::from("foo"))
+    );
+    assert_eq!(
+        eval("str::trim_start(\"  foo  bar \")"),
+        Ok(Value::from("foo  bar "))
+    );
+    assert_eq!(
+        eval("str::trim_end(\"  foo  bar \")"),
+        Ok(Value::from("  foo  bar"))
+    );
+    assert_eq!(eval("str::trim_start(\"   \")"), Ok(Value::from("")));
+    assert_eq!(
+        eval("str::trim(1)"),
+        Err(EvalexprError::expected_string(Value::Int(1)))
+    );
+    assert_eq!(
+        eval("str::trim_start(true)"),
+        Err(EvalexprError::expected_string(Value::Boolean(true)))
+    );
+    assert_eq!(
+        eval("str::trim_end((\"a\", \"b\"))"),
+        Err(EvalexprError::expected_string(Value::Tuple(vec![
+            Value::from("a"),
+            Value::from("b")
+        ])))
+    );
+    assert_eq!(
+        eval("str::starts_with(\"foobar\", \"foo\")"),
+        Ok(Value::Boolean(true))
+    );
+    assert_eq!(
+        eval("str::starts_with(\"foobar\", \"bar\")"),
+        Ok(Value::Boolean(false))
+    );
+    assert_eq!(
+        eval("str::ends_with(\"foobar\", \"bar\")"),
+        Ok(Value::Boolean(true))
+    );
+    assert_eq!(
+        eval("str::ends_with(\"foobar\", \"foo\")"),
+        Ok(Value::Boolean(false))
+    );
+    assert_eq!(
+        eval("str::starts_with(1, \"foo\")"),
+        Err(EvalexprError::expected_string(Value::Int(1)))
+    );
+    assert_eq!(
+        eval("str::ends_with(\"foobar\", true)"),
+        Err(EvalexprError::expected_string(Value::Boolean(true)))
+    );
+    assert_eq!(
+        eval("str::replace(\"a\\\\b\\\\c\", \"\\\\\", \"/\")"),
+        Ok(Value::from("a/b/c"))
+    );
+    assert_eq!(
+        eval("str::replace(\"aaaa\", \"aa\", \"b\")"),
+        Ok(Value::from("bb"))
+    );
+    assert_eq!(
+        eval("str::replace(\"äöü\", \"ö\", \"\")"),
+        Ok(Value::from("äü"))
+    );
+    assert_eq!(
+        eval("str::replace(\"foo\", \"x\", \"y\")"),
+        Ok(Value::from("foo"))
+    );
+    assert_eq!(
+        eval("str::replace(\"foo\", \"\", \"y\")"),
+        Err(EvalexprError::EmptyPattern)
+    );
+    assert_eq!(
+        eval("str::replace(\"foo\", \"o\", 0)"),
+        Err(EvalexprError::expected_string(Value::Int(0)))
+    );
+    assert_eq!(
+        eval("str::replace(1, \"o\", \"0\")"),
+        Err(EvalexprError::expected_string(Value::Int(1)))
+    );
+    assert_eq!(
+        eval("str::split(\"a,b,c\", \",\")"),
+        Ok(Value::from(vec![
+            Value::from("a"),
+            Value::from("b"),
+            Value::from("c")
+        ]))
+    );
+    assert_eq!(
+        eval("str::split(\"a, b,,c\", \", \")"),
+        Ok(Value::from(vec![Value::from("a"), Value::from("b,,c")]))
+    );
+    assert_eq!(
+        eval("str::split(\",a,\", \",\")"),
+        Ok(Value::from(vec![
+            Value::from(""),
+            Value::from("a"),
+            Value::from("")
+        ]))
+    );
+    assert_eq!(
+        eval("str::split(\"abc\", \",\")"),
+        Ok(Value::from(vec![Value::from("abc")]))
+    );
+    assert_eq!(
+        eval("str::split(\"\", \",\")"),
+        Ok(Value::from(vec![Value::from("")]))
+    );
+    assert_eq!(eval("len(str::split(\"ä→ö→ü\", \"→\"))"), Ok(Value::Int(3)));
+    assert_eq!(
+        eval("str::split(\"abc\", \"\")"),
+        Err(EvalexprError::EmptyPattern)
+    );
+    assert_eq!(
+        eval("str::split(\"abc\", 1)"),
+        Err(EvalexprError::expected_string(Value::Int(1)))
+    );
+    assert_eq!(
+        eval("str::join((\"a\", \"b\", \"c\"), \"-\")"),
+        Ok(Value::from("a-b-c"))
+    );
+    assert_eq!(
+        eval("str::join((\"a\", \"b\"), \"\")"),
+        Ok(Value::from("ab"))
+    );
+    assert_eq!(eval("str::join((\"a\",), \", \")"), Ok(Value::from("a")));
+    assert_eq!(eval("str::join((), \"-\")"), Ok(Value::from("")));
+    assert_eq!(
+        eval("str::join(str::split(\"a,b,c\", \",\"), \";\")"),
+        Ok(Value::from("a;b;c"))
+    );
+    assert_eq!(
+        eval("str::join((\"a\", 2, \"c\"), \"-\")"),
+        Err(EvalexprError::expected_string(Value::Int(2)))
+    );
+    assert_eq!(
+        eval("str::join((\"a\", \"b\"), 0)"),
+        Err(EvalexprError::expected_string(Value::Int(0)))
+    );
+    assert_eq!(
+        eval("str::substring(\"ABC123\", 0, 3)"),
+        Ok(Value::from("ABC"))
+    );
+    assert_eq!(
+        eval("str::substring(\"ABC123\", 3, 10)"),
+        Ok(Value::from("123"))
+    );
+    assert_eq!(
+        eval("str::substring(\"äöü€\", 1, 2)"),
+        Ok(Value::from("öü"))
+    );
+    assert_eq!(eval("str::substring(\"abc\", 3, 1)"), Ok(Value::from("")));
+    assert_eq!(eval("str::substring(\"abc\", 1, 0)"), Ok(Value::from("")));
+    assert_eq!(
+        eval("str::substring(\"abc\", 4, 1)"),
+        Err(EvalexprError::IndexOutOfBounds {
+            index: 4,
+            length: 3
+        })
+    );
+    assert_eq!(
+        eval("str::substring(\"abc\", -1, 1)"),
+        Err(EvalexprError::IndexOutOfBounds {
+            index: -1,
+            length: 3
+        })
+    );
+    assert_eq!(
+        eval("str::substring(\"abc\", 1, -2)"),
+        Err(EvalexprError::IndexOutOfBounds {
+            index: -1,
+            length: 3
+        })
+    );
+    assert_eq!(
+        eval("str::substring(\"abc\", 1.0, 1)"),
+        Err(EvalexprError::expected_int(Value::Float(1.0)))
+    );
+    assert_eq!(
+        eval("str::substring(\"abc\", 4, 1)")
+            .unwrap_err()
+            .to_string(),
+        "Index 4 is out of bounds for length 3."
+    );
+    assert_eq!(
+        eval("str::from(\"a\")"),
+        Ok(Value::String(String::from("\"a\"")))
+    );
+    assert_eq!(eval("str::from(1.0)"), Ok(Value::String(String::from("1"))));
+    assert_eq!(eval("str::from(1)"), Ok(Value::String(String::from("1"))));
+    assert_eq!(
+        eval("str::from(true)"),
+        Ok(Value::String(String::from("true")))
+    );
+    assert_eq!(
+        eval("str::from(1, 2, 3)"),
+        Ok(Value::String(String::from("(1, 2, 3)")))
+    );
+    assert_eq!(eval("str::from()"), Ok(Value::String(String::from("()"))));
+    // Bitwise
+    assert_eq!(eval("bitand(5, -1)"), Ok(Value::Int(5)));
+    assert_eq!(eval("bitand(6, 5)"), Ok(Value::Int(4)));
+    assert_eq!(eval("bitor(5, -1)"), Ok(Value::Int(-1)));
+    assert_eq!(eval("bitor(6, 5)"), Ok(Value::Int(7)));
+    assert_eq!(eval("bitxor(5, -1)"), Ok(Value::Int(-6)));
+    assert_eq!(eval("bitxor(6, 5)"), Ok(Value::Int(3)));
+    assert_eq!(eval("bitnot(5)"), Ok(Value::Int(-6)));
+    assert_eq!(eval("bitnot(-1)"), Ok(Value::Int(0)));
+    assert_eq!(eval("shl(5, 1)"), Ok(Value::Int(10)));
+    assert_eq!(eval("shl(-6, 5)"), Ok(Value::Int(-192)));
+    assert_eq!(eval("shr(5, 1)"), Ok(Value::Int(2)));
+    assert_eq!(eval("shr(-6, 5)"), Ok(Value::Int(-1)));
+    // Integer division
+    assert_eq!(eval("div(7, 2)"), Ok(Value::Int(3)));
+    assert_eq!(eval("div(-7, 2)"), Ok(Value::Int(-3)));
+    assert_eq!(eval("div(7, -2)"), Ok(Value::Int(-3)));
+    assert_eq!(
+        eval("div(7, 0)"),
+        Err(EvalexprError::DivisionError {
+            dividend: Value::Int(7),
+            divisor: Value::Int(0)
+        })
+    );
+    assert_eq!(
+        eval("div(7.0, 2)"),
+        Err(EvalexprError::expected_int(Value::Float(7.0)))
+    );
+    assert_eq!(
+        eval("div(7, 2.0)"),
+        Err(EvalexprError::expected_int(Value::Float(2.0)))
+    );
+    // Floored integer division
+    assert_eq!(eval("div_floor(7, 2)"), Ok(Value::Int(3)));
+    assert_eq!(eval("div_floor(-7, 2)"), Ok(Value::Int(-4)));
+    assert_eq!(eval("div_floor(7, -2)"), Ok(Value::Int(-4)));
+    assert_eq!(eval("div_floor(-7, -2)"), Ok(Value::Int(3)));
+    assert_eq!(eval("div_floor(-6, 2)"), Ok(Value::Int(-3)));
+    assert_eq!(eval("mod_floor(7, 3)"), Ok(Value::Int(1)));
+    assert_eq!(eval("mod_floor(-7, 3)"), Ok(Value::Int(2)));
+    assert_eq!(eval("mod_floor(7, -3)"), Ok(Value::Int(-2)));
+    assert_eq!(eval("mod_floor(-7, -3)"), Ok(Value::Int(-1)));
+    assert_eq!(eval("mod_floor(-6, 3)"), Ok(Value::Int(0)));
+    assert_eq!(eval("-7 % 3"), Ok(Value::Int(-1)));
+    assert_eq!(eval("gcd(12, 18)"), Ok(Value::Int(6)));
+    assert_eq!(eval("gcd(-12, 18)"), Ok(Value::Int(6)));
+    assert_eq!(eval("gcd(7, 13)"), Ok(Value::Int(1)));
+    assert_eq!(eval("gcd(0, 5)"), Ok(Value::Int(5)));
+    assert_eq!(eval("gcd(0, 0)"), Ok(Value::Int(0)));
+    assert_eq!(eval("lcm(4, 6)"), Ok(Value::Int(12)));
+    assert_eq!(eval("lcm(-4, 6)"), Ok(Value::Int(12)));
+    assert_eq!(eval("lcm(0, 6)"), Ok(Value::Int(0)));
+    assert_eq!(eval("lcm(4, 0)"), Ok(Value::Int(0)));
+    assert_eq!(
+        eval("gcd(12.0, 18)"),
+        Err(EvalexprError::expected_int(Value::Float(12.0)))
+    );
+    assert_eq!(
+        eval("lcm(4, \"6\")"),
+        Err(EvalexprError::expected_int(Value::from("6")))
+    );
+    let context = context_map! { "min" => IntType::MIN, "max" => IntType::MAX }.unwrap();
+    assert_eq!(
+        eval_with_context("gcd(min, 6)", &context),
+        Ok(Value::Int(2))
+    );
+    assert_eq!(
+        eval_with_context("gcd(min, 0)", &context),
+        Err(EvalexprError::FunctionOverflow {
+            identifier: "gcd".to_string(),
+            arguments: vec![Value::Int(IntType::MIN), Value::Int(0)]
+        })
+    );
+    assert_eq!(
+        eval_with_context("gcd(min, 0)", &context)
+            .unwrap_err()
+            .to_string(),
+        "Error computing gcd(-9223372036854775808, 0): The result overflows."
+    );
+    assert_eq!(
+        eval_with_context("lcm(max, max - 1)", &context),
+        Err(EvalexprError::FunctionOverflow {
+            identifier: "lcm".to_string(),
+            arguments: vec![Value::Int(IntType::MAX), Value::Int(IntType::MAX - 1)]
+        })
+    );
+    assert_eq!(
+        eval_with_context("lcm(max, max)", &context),
+        Ok(Value::Int(IntType::MAX))
+    );
+    assert_eq!(eval("pow(2, 10)"), Ok(Value::Int(1024)));
+    assert_eq!(eval("pow(-3, 3)"), Ok(Value::Int(-27)));
+    assert_eq!(eval("pow(0, 0)"), Ok(Value::Int(1)));
+    assert_eq!(eval("pow(2, -1)"), Ok(Value::Float(0.5)));
+    assert_eq!(eval("pow(2.0, 3)"), Ok(Value::Float(8.0)));
+    assert_eq!(eval("pow(4, 0.5)"), Ok(Value::Float(2.0)));
+    assert_eq!(
+        eval("pow(2, 63)"),
+        Err(EvalexprError::ExponentiationError {
+            base: Value::Int(2),
+            exponent: Value::Int(63)
+        })
+    );
+    assert_eq!(
+        eval_with_context("pow(2, max)", &context),
+        Err(EvalexprError::ExponentiationError {
+            base: Value::Int(2),
+            exponent: Value::Int(IntType::MAX)
+        })
+    );
+    assert_eq!(
+        eval("pow(\"2\", 2)"),
+        Err(EvalexprError::expected_number(Value::from("2")))
+    );
+    assert_eq!(
+        eval("a = -7; b = 3; div_floor(a, b) * b + mod_floor(a, b) == a"),
+        Ok(Value::Boolean(true))
+    );
+    assert_eq!(
+        eval_with_context(
+            "mod_floor(a, -1)",
+            &context_map! { "a" => IntType::MIN }.unwrap()
+        ),
+        Ok(Value::Int(0))
+    );
+    assert_eq!(
+        eval_with_context(
+            "div_floor(a, -1)",
+            &context_map! { "a" => IntType::MIN }.unwrap()
+        ),
+        Err(EvalexprError::DivisionError {
+            dividend: Value::Int(IntType::MIN),
+            divisor: Value::Int(-1)
+        })
+    );
+    assert_eq!(
+        eval("div_floor(7, 0)"),
+        Err(EvalexprError::DivisionError {
+            dividend: Value::Int(7),
+            divisor: Value::Int(0)
+        })
+    );
+    assert_eq!(
+        eval("mod_floor(7, 0)"),
+        Err(EvalexprError::ModulationError {
+            dividend: Value::Int(7),
+            divisor: Value::Int(0)
+        })
+    );
+    assert_eq!(
+        eval("mod_floor(7.0, 2)"),
+        Err(EvalexprError::expected_int(Value::Float(7.0)))
+    );
+    assert_eq!(eval("if(true, -6, 5)"), Ok(Value::Int(-6)));
+    assert_eq!(eval("if(false, -6, 5)"), Ok(Value::Int(5)));
+    assert_eq!(
+        eval("if(2-1==1, \"good\", 0)"),
+        Ok(Value::String(String::from("good")))
+    );
+}
+
+#[test]
+fn test_errors() {
+    assert_eq!(
+        eval("-true"),
+        Err(EvalexprError::expected_number(Value::Boolean(true)))
+    );
+    assert_eq!(
+        eval("1-true"),
+        Err(EvalexprError::expected_number(Value::Boolean(true)))
+    );
+    assert_eq!(
+        eval("true-"),
+        Err(EvalexprError::WrongOperatorArgumentAmount {
+            actual: 1,
+            expected: 2,
+        }
+        .at_position(4))
+    );
+    assert_eq!(
+        eval("-\"abc\""),
+        Err(EvalexprError::expected_number(Value::from("abc")))
+    );
+    assert_eq!(
+        eval("-(1, 2)"),
+        Err(EvalexprError::expected_number(Value::from(vec![
+            Value::Int(1),
+            Value::Int(2)
+        ])))
+    );
+    assert_eq!(
+        eval("!(()true)"),
+        Err(EvalexprError::AppendedToLeafNode.at_position(4))
+    );
+    assert_eq!(
+        eval("math::is_nan(\"xxx\")"),
+        Err(EvalexprError::ExpectedNumber {
+            actual: Value::String("xxx".to_string())
+        })
+    );
+}
+
+#[test]
+fn test_no_panic() {
+    assert!(eval(&format!("{} + {}", IntType::MAX, IntType::MAX)).is_err());
+    assert!(eval(&format!("-{} - {}", IntType::MAX, IntType::MAX)).is_err());
+    assert!(eval(&format!("-(-{} - 1)", IntType::MAX)).is_err());
+    assert!(eval(&format!("{} * {}", IntType::MAX, IntType::MAX)).is_err());
+    assert!(eval(&format!("{} / {}", IntType::MAX, 0)).is_err());
+    assert!(eval(&format!("{} % {}", IntType::MAX, 0)).is_err());
+    assert!(eval(&format!("{} ^ {}", IntType::MAX, IntType::MAX)).is_err());
+    assert!(eval("if").is_err());
+    assert!(eval("if()").is_err());
+    assert!(eval("if(true, 1)").is_err());
+    assert!(eval("if(false, 2)").is_err());
+    assert!(eval("if(1,1,1)").is_err());
+    assert!(eval("if(true,1,1,1)").is_err());
+}
+
+#[test]
+fn test_integer_overflow() {
+    let min = format!("(-{} - 1)", IntType::MAX);
+    assert_eq!(
+        eval(&format!("{} + 1", IntType::MAX)),
+        Err(EvalexprError::AdditionError {
+            augend: Value::Int(IntType::MAX),
+            addend: Value::Int(1)
+        })
+    );
+    assert_eq!(
+        eval(&format!("{} - 1", min)),
+        Err(EvalexprError::SubtractionError {
+            minuend: Value::Int(IntType::MIN),
+            subtrahend: Value::Int(1)
+        })
+    );
+    assert_eq!(
+        eval(&format!("{} * -2", IntType::MAX)),
+        Err(EvalexprError::MultiplicationError {
+            multiplicand: Value::Int(IntType::MAX),
+            multiplier: Value::Int(-2)
+        })
+    );
+    assert_eq!(
+        eval(&format!("{} / -1", min)),
+        Err(EvalexprError::DivisionError {
+            dividend: Value::Int(IntType::MIN),
+            divisor: Value::Int(-1)
+        })
+    );
+    assert_eq!(
+        eval(&format!("{} % -1", min)),
+        Err(EvalexprError::ModulationError {
+            dividend: Value::Int(IntType::MIN),
+            divisor: Value::Int(-1)
+        })
+    );
+    assert_eq!(
+        eval(&format!("-{}", min)),
+        Err(EvalexprError::NegationError {
+            argument: Value::Int(IntType::MIN)
+        })
+    );
+    assert_eq!(
+        eval(&format!("a = {}; a += 1", IntType::MAX)),
+        Err(EvalexprError::AdditionError {
+            augend: Value::Int(IntType::MAX),
+            addend: Value::Int(1)
+        })
+    );
+    assert_eq!(
+        eval(&format!("{} - 1 + 1", IntType::MAX)),
+        Ok(Value::Int(IntType::MAX))
+    );
+
+    // Floating point arithmetic is unaffected.
+    assert_eq!(
+        eval(&format!("{}.0 + 1", IntType::MAX)),
+        Ok(Value::Float(IntType::MAX as FloatType + 1.0))
+    );
+    assert_eq!(eval("1e308 * 10"), Ok(Value::Float(FloatType::INFINITY)));
+}
+
+#[test]
+fn test_shortcut_functions() {
+    let mut context = HashMapContext::new();
+    context
+        .set_value("string".into(), Value::from("a string"))
+        .unwrap();
+
+    assert_eq!(eval_string("\"3.3\""), Ok("3.3".to_owned()));
+    assert_eq!(
+        eval_string("3.3"),
+        Err(EvalexprError::ExpectedString {
+            actual: Value::Float(3.3)
+        })
+    );
+    assert_eq!(
+        eval_string("3..3"),
+        Err(EvalexprError::VariableIdentifierNotFound("3..3".to_owned()))
+    );
+    assert_eq!(
+        eval_string_with_context("string", &context),
+        Ok("a string".to_owned())
+    );
+    assert_eq!(
+        eval_string_with_context("3.3", &context),
+        Err(EvalexprError::ExpectedString {
+            actual: Value::Float(3.3)
+        })
+    );
+    assert_eq!(
+        eval_string_with_context("3..3", &context),
+        Err(EvalexprError::VariableIdentifierNotFound("3..3".to_owned()))
+    );
+    assert_eq!(
+        eval_string_with_context_mut("string", &mut context),
+        Ok("a string".to_string())
+    );
+    assert_eq!(
+        eval_string_with_context_mut("3.3", &mut context),
+        Err(EvalexprError::ExpectedString {
+            actual: Value::Float(3.3)
+        })
+    );
+    assert_eq!(
+        eval_string_with_context_mut("3..3", &mut context),
+        Err(EvalexprError::VariableIdentifierNotFound("3..3".to_owned()))
+    );
+
+    assert_eq!(eval_float("3.3"), Ok(3.3));
+    assert_eq!(
+        eval_float("33"),
+        Err(EvalexprError::ExpectedFloat {
+            actual: Value::Int(33)
+        })
+    );
+    assert_eq!(
+        eval_float("asd()"),
+        Err(EvalexprError::FunctionIdentifierNotFound("asd".to_owned()))
+    );
+    assert_eq!(eval_float_with_context("3.3", &context), Ok(3.3));
+    assert_eq!(
+        eval_float_with_context("33", &context),
+        Err(EvalexprError::ExpectedFloat {
+            actual: Value::Int(33)
+        })
+    );
+    assert_eq!(
+        eval_float_with_context("asd)", &context),
+        Err(EvalexprError::UnmatchedRBrace.at_position(3))
+    );
+    assert_eq!(eval_float_with_context_mut("3.3", &mut context), Ok(3.3));
+    assert_eq!(
+        eval_float_with_context_mut("33", &mut context),
+        Err(EvalexprError::ExpectedFloat {
+            actual: Value::Int(33)
+        })
+    );
+    assert_eq!(
+        eval_float_with_context_mut("asd(", &mut context),
+        Err(EvalexprError::UnmatchedLBrace.at_position(3))
+    );
+
+    assert_eq!(eval_int("3"), Ok(3));
+    assert_eq!(
+        eval_int("3.3"),
+        Err(EvalexprError::ExpectedInt {
+            actual: Value::Float(3.3)
+        })
+    );
+    assert_eq!(
+        eval_int("(1, 2);."),
+        Err(EvalexprError::VariableIdentifierNotFound(".".to_owned()))
+    );
+    assert_eq!(eval_int_with_context("3", &context), Ok(3));
+    assert_eq!(
+        eval_int_with_context("3.3", &context),
+        Err(EvalexprError::ExpectedInt {
+            actual: Value::Float(3.3)
+        })
+    );
+    assert_eq!(
+        eval_int_with_context("(1, 2);.", &context),
+        Err(EvalexprError::VariableIdentifierNotFound(".".to_owned()))
+    );
+    assert_eq!(eval_int_with_context_mut("3", &mut context), Ok(3));
+    assert_eq!(
+        eval_int_with_context_mut("3.3", &mut context),
+        Err(EvalexprError::ExpectedInt {
+            actual: Value::Float(3.3)
+        })
+    );
+    assert_eq!(
+        eval_int_with_context_mut("(1, 2);.", &mut context),
+        Err(EvalexprError::VariableIdentifierNotFound(".".to_owned()))
+    );
+
+    assert_eq!(eval_number("3"), Ok(3.0));
+    assert_eq!(
+        eval_number("true"),
+        Err(EvalexprError::ExpectedNumber {
+            actual: Value::Boolean(true)
+        })
+    );
+    assert_eq!(
+        eval_number("abc"),
+        Err(EvalexprError::VariableIdentifierNotFound("abc".to_owned()))
+    );
+    assert_eq!(eval_number_with_context("3.5", &context), Ok(3.5));
+    assert_eq!(eval_number_with_context("3", &context), Ok(3.0));
+    assert_eq!(
+        eval_number_with_context("true", &context),
+        Err(EvalexprError::ExpectedNumber {
+            actual: Value::Boolean(true)
+        })
+    );
+    assert_eq!(
+        eval_number_with_context("abc", &context),
+        Err(EvalexprError::VariableIdentifierNotFound("abc".to_owned()))
+    );
+    assert_eq!(eval_number_with_context_mut("3.5", &mut context), Ok(3.5));
+    assert_eq!(eval_number_with_context_mut("3", &mut context), Ok(3.0));
+    assert_eq!(
+        eval_number_with_context_mut("true", &mut context),
+        Err(EvalexprError::ExpectedNumber {
+            actual: Value::Boolean(true)
+        })
+    );
+    assert_eq!(
+        eval_number_with_context_mut("abc", &mut context),
+        Err(EvalexprError::VariableIdentifierNotFound("abc".to_owned()))
+    );
+
+    assert_eq!(eval_boolean("true"), Ok(true));
+    assert_eq!(
+        eval_boolean("4"),
+        Err(EvalexprError::ExpectedBoolean {
+            actual: Value::Int(4)
+        })
+    );
+    assert_eq!(
+        eval_boolean("trueee"),
+        Err(EvalexprError::VariableIdentifierNotFound(
+            "trueee".to_owned()
+        ))
+    );
+    assert_eq!(eval_boolean_with_context("true", &context), Ok(true));
+    assert_eq!(
+        eval_boolean_with_context("4", &context),
+        Err(EvalexprError::ExpectedBoolean {
+            actual: Value::Int(4)
+        })
+    );
+    assert_eq!(
+        eval_boolean_with_context("trueee", &context),
+        Err(EvalexprError::VariableIdentifierNotFound(
+            "trueee".to_owned()
+        ))
+    );
+    assert_eq!(
+        eval_boolean_with_context_mut("true", &mut context),
+        Ok(true)
+    );
+    assert_eq!(
+        eval_boolean_with_context_mut("4", &mut context),
+        Err(EvalexprError::ExpectedBoolean {
+            actual: Value::Int(4)
+        })
+    );
+    assert_eq!(
+        eval_boolean_with_context_mut("trueee", &mut context),
+        Err(EvalexprError::VariableIdentifierNotFound(
+            "trueee".to_owned()
+        ))
+    );
+
+    assert_eq!(eval_tuple("3,3"), Ok(vec![Value::Int(3), Value::Int(3)]));
+    assert_eq!(
+        eval_tuple("33"),
+        Err(EvalexprError::ExpectedTuple {
+            actual: Value::Int(33)
+        })
+    );
+    assert_eq!(
+        eval_tuple("3a3"),
+        Err(EvalexprError::VariableIdentifierNotFound("3a3".to_owned()))
+    );
+    assert_eq!(
+        eval_tuple_with_context("3,3", &context),
+        Ok(vec![Value::Int(3), Value::Int(3)])
+    );
+    assert_eq!(
+        eval_tuple_with_context("33", &context),
+        Err(EvalexprError::ExpectedTuple {
+            actual: Value::Int(33)
+        })
+    );
+    assert_eq!(
+        eval_tuple_with_context("3a3", &context),
+        Err(EvalexprError::VariableIdentifierNotFound("3a3".to_owned()))
+    );
+    assert_eq!(
+        eval_tuple_with_context_mut("3,3", &mut context),
+        Ok(vec![Value::Int(3), Value::Int(3)])
+    );
+    assert_eq!(
+        eval_tuple_with_context_mut("33", &mut context),
+        Err(EvalexprError::ExpectedTuple {
+            actual: Value::Int(33)
+        })
+    );
+    assert_eq!(
+        eval_tuple_with_context_mut("3a3", &mut context),
+        Err(EvalexprError::VariableIdentifierNotFound("3a3".to_owned()))
+    );
+
+    assert_eq!(
+        eval_string("()"),
+        Err(EvalexprError::expected_string(Value::Empty))
+    );
+    assert_eq!(
+        eval_float("()"),
+        Err(EvalexprError::expected_float(Value::Empty))
+    );
+    assert_eq!(eval_int(""), Err(EvalexprError::expected_int(Value::Empty)));
+    assert_eq!(
+        eval_number("()"),
+        Err(EvalexprError::expected_number(Value::Empty))
+    );
+    assert_eq!(
+        eval_boolean("()"),
+        Err(EvalexprError::expected_boolean(Value::Empty))
+    );
+    assert_eq!(
+        eval_tuple("()"),
+        Err(EvalexprError::expected_tuple(Value::Empty))
+    );
+    assert_eq!(
+        eval_int_with_context_mut("a = 5", &mut context),
+        Err(EvalexprError::expected_int(Value::Empty))
+    );
+
+    assert_eq!(eval_empty(""), Ok(EMPTY_VALUE));
+    assert_eq!(eval_empty("()"), Ok(EMPTY_VALUE));
+    assert_eq!(
+        eval_empty("((), ())"),
+        Err(EvalexprError::ExpectedEmpty {
+            actual: Value::Tuple(vec![Value::Empty, Value::Empty])
+        })
+    );
+    assert_eq!(
+        eval_empty("xaq"),
+        Err(EvalexprError::VariableIdentifierNotFound("xaq".to_owned()))
+    );
+    assert_eq!(eval_empty_with_context("", &context), Ok(EMPTY_VALUE));
+    assert_eq!(eval_empty_with_context("()", &context), Ok(EMPTY_VALUE));
+    assert_eq!(
+        eval_empty_with_context("((), ())", &context),
+        Err(EvalexprError::ExpectedEmpty {
+            actual: Value::Tuple(vec![Value::Empty, Value::Empty])
+        })
+    );
+    assert_eq!(
+        eval_empty_with_context("xaq", &context),
+        Err(EvalexprError::VariableIdentifierNotFound("xaq".to_owned()))
+    );
+    assert_eq!(
+        eval_empty_with_context_mut("", &mut context),
+        Ok(EMPTY_VALUE)
+    );
+    assert_eq!(
+        eval_empty_with_context_mut("()", &mut context),
+        Ok(EMPTY_VALUE)
+    );
+    assert_eq!(
+        eval_empty_with_context_mut("((), ())", &mut context),
+        Err(EvalexprError::ExpectedEmpty {
+            actual: Value::Tuple(vec![Value::Empty, Value::Empty])
+        })
+    );
+    assert_eq!(
+        eval_empty_with_context_mut("xaq", &mut context),
+        Err(EvalexprError::VariableIdentifierNotFound("xaq".to_owned()))
+    );
+
+    // With detour via build_operator_tree
+
+    assert_eq!(
+        build_operator_tree("\"3.3\"").unwrap().eval_string(),
+        Ok("3.3".to_owned())
     );
-    assert_eq!(eval_float_with_context_mut("3.3", &mut context), Ok(3.3));
     assert_eq!(
-        eval_float_with_context_mut("33", &mut context),
+        build_operator_tree("3.3").unwrap().eval_string(),
+        Err(EvalexprError::ExpectedString {
+            actual: Value::Float(3.3)
+        })
+    );
+    assert_eq!(
+        build_operator_tree("3..3").unwrap().eval_string(),
+        Err(EvalexprError::VariableIdentifierNotFound("3..3".to_owned()))
+    );
+    assert_eq!(
+        build_operator_tree("string")
+            .unwrap()
+            .eval_string_with_context(&context),
+        Ok("a string".to_owned())
+    );
+    assert_eq!(
+        build_operator_tree("3.3")
+            .unwrap()
+            .eval_string_with_context(&context),
+        Err(EvalexprError::ExpectedString {
+            actual: Value::Float(3.3)
+        })
+    );
+    assert_eq!(
+        build_operator_tree("3..3")
+            .unwrap()
+            .eval_string_with_context(&context),
+        Err(EvalexprError::VariableIdentifierNotFound("3..3".to_owned()))
+    );
+    assert_eq!(
+        build_operator_tree("string")
+            .unwrap()
+            .eval_string_with_context_mut(&mut context),
+        Ok("a string".to_string())
+    );
+    assert_eq!(
+        build_operator_tree("3.3")
+            .unwrap()
+            .eval_string_with_context_mut(&mut context),
+        Err(EvalexprError::ExpectedString {
+            actual: Value::Float(3.3)
+        })
+    );
+    assert_eq!(
+        build_operator_tree("3..3")
+            .unwrap()
+            .eval_string_with_context_mut(&mut context),
+        Err(EvalexprError::VariableIdentifierNotFound("3..3".to_owned()))
+    );
+
+    assert_eq!(build_operator_tree("3.3").unwrap().eval_float(), Ok(3.3));
+    assert_eq!(
+        build_operator_tree("33").unwrap().eval_float(),
+        Err(EvalexprError::ExpectedFloat {
+            actual: Value::Int(33)
+        })
+    );
+    assert_eq!(
+        build_operator_tree("asd()").unwrap().eval_float(),
+        Err(EvalexprError::FunctionIdentifierNotFound("asd".to_owned()))
+    );
+    assert_eq!(
+        build_operator_tree("3.3")
+            .unwrap()
+            .eval_float_with_context(&context),
+        Ok(3.3)
+    );
+    assert_eq!(
+        build_operator_tree("33")
+            .unwrap()
+            .eval_float_with_context(&context),
+        Err(EvalexprError::ExpectedFloat {
+            actual: Value::Int(33)
+        })
+    );
+    assert_eq!(
+        build_operator_tree("asd")
+            .unwrap()
+            .eval_float_with_context(&context),
+        Err(EvalexprError::VariableIdentifierNotFound("asd".to_owned()))
+    );
+    assert_eq!(
+        build_operator_tree("3.3")
+            .unwrap()
+            .eval_float_with_context_mut(&mut context),
+        Ok(3.3)
+    );
+    assert_eq!(
+        build_operator_tree("33")
+            .unwrap()
+            .eval_float_with_context_mut(&mut context),
         Err(EvalexprError::ExpectedFloat {
             actual: Value::Int(33)
         })
     );
     assert_eq!(
-        eval_float_with_context_mut("asd(", &mut context),
-        Err(EvalexprError::UnmatchedLBrace)
+        build_operator_tree("asd")
+            .unwrap()
+            .eval_float_with_context_mut(&mut context),
+        Err(EvalexprError::VariableIdentifierNotFound("asd".to_owned()))
+    );
+
+    assert_eq!(build_operator_tree("3").unwrap().eval_int(), Ok(3));
+    assert_eq!(
+        build_operator_tree("3.3").unwrap().eval_int(),
+        Err(EvalexprError::ExpectedInt {
+            actual: Value::Float(3.3)
+        })
+    );
+    assert_eq!(
+        build_operator_tree("(1, 2);.").unwrap().eval_int(),
+        Err(EvalexprError::VariableIdentifierNotFound(".".to_owned()))
+    );
+    assert_eq!(
+        build_operator_tree("3")
+            .unwrap()
+            .eval_int_with_context(&context),
+        Ok(3)
+    );
+    assert_eq!(
+        build_operator_tree("3.3")
+            .unwrap()
+            .eval_int_with_context(&context),
+        Err(EvalexprError::ExpectedInt {
+            actual: Value::Float(3.3)
+        })
+    );
+    assert_eq!(
+        build_operator_tree("(1, 2);.")
+            .unwrap()
+            .eval_int_with_context(&context),
+        Err(EvalexprError::VariableIdentifierNotFound(".".to_owned()))
+    );
+    assert_eq!(
+        build_operator_tree("3")
+            .unwrap()
+            .eval_int_with_context_mut(&mut context),
+        Ok(3)
+    );
+    assert_eq!(
+        build_operator_tree("3.3")
+            .unwrap()
+            .eval_int_with_context_mut(&mut context),
+        Err(EvalexprError::ExpectedInt {
+            actual: Value::Float(3.3)
+        })
+    );
+    assert_eq!(
+        build_operator_tree("(1, 2);.")
+            .unwrap()
+            .eval_int_with_context_mut(&mut context),
+        Err(EvalexprError::VariableIdentifierNotFound(".".to_owned()))
+    );
+
+    assert_eq!(build_operator_tree("3").unwrap().eval_number(), Ok(3.0));
+    assert_eq!(
+        build_operator_tree("true").unwrap().eval_number(),
+        Err(EvalexprError::ExpectedNumber {
+            actual: Value::Boolean(true)
+        })
+    );
+    assert_eq!(
+        build_operator_tree("abc").unwrap().eval_number(),
+        Err(EvalexprError::VariableIdentifierNotFound("abc".to_owned()))
+    );
+    assert_eq!(
+        build_operator_tree("3")
+            .unwrap()
+            .eval_number_with_context(&context),
+        Ok(3.0)
+    );
+    assert_eq!(
+        build_operator_tree("true")
+            .unwrap()
+            .eval_number_with_context(&context),
+        Err(EvalexprError::ExpectedNumber {
+            actual: Value::Boolean(true)
+        })
+    );
+    assert_eq!(
+        build_operator_tree("abc")
+            .unwrap()
+            .eval_number_with_context(&context),
+        Err(EvalexprError::VariableIdentifierNotFound("abc".to_owned()))
+    );
+    assert_eq!(
+        build_operator_tree("3")
+            .unwrap()
+            .eval_number_with_context_mut(&mut context),
+        Ok(3.0)
+    );
+    assert_eq!(
+        build_operator_tree("true")
+            .unwrap()
+            .eval_number_with_context_mut(&mut context),
+        Err(EvalexprError::ExpectedNumber {
+            actual: Value::Boolean(true)
+        })
+    );
+    assert_eq!(
+        build_operator_tree("abc")
+            .unwrap()
+            .eval_number_with_context_mut(&mut context),
+        Err(EvalexprError::VariableIdentifierNotFound("abc".to_owned()))
+    );
+
+    assert_eq!(
+        build_operator_tree("true").unwrap().eval_boolean(),
+        Ok(true)
+    );
+    assert_eq!(
+        build_operator_tree("4").unwrap().eval_boolean(),
+        Err(EvalexprError::ExpectedBoolean {
+            actual: Value::Int(4)
+        })
+    );
+    assert_eq!(
+        build_operator_tree("trueee").unwrap().eval_boolean(),
+        Err(EvalexprError::VariableIdentifierNotFound(
+            "trueee".to_owned()
+        ))
+    );
+    assert_eq!(
+        build_operator_tree("true")
+            .unwrap()
+            .eval_boolean_with_context(&context),
+        Ok(true)
+    );
+    assert_eq!(
+        build_operator_tree("4")
+            .unwrap()
+            .eval_boolean_with_context(&context),
+        Err(EvalexprError::ExpectedBoolean {
+            actual: Value::Int(4)
+        })
+    );
+    assert_eq!(
+        build_operator_tree("trueee")
+            .unwrap()
+            .eval_boolean_with_context(&context),
+        Err(EvalexprError::VariableIdentifierNotFound(
+            "trueee".to_owned()
+        ))
+    );
+    assert_eq!(
+        build_operator_tree("true")
+            .unwrap()
+            .eval_boolean_with_context_mut(&mut context),
+        Ok(true)
+    );
+    assert_eq!(
+        build_operator_tree("4")
+            .unwrap()
+            .eval_boolean_with_context_mut(&mut context),
+        Err(EvalexprError::ExpectedBoolean {
+            actual: Value::Int(4)
+        })
+    );
+    assert_eq!(
+        build_operator_tree("trueee")
+            .unwrap()
+            .eval_boolean_with_context_mut(&mut context),
+        Err(EvalexprError::VariableIdentifierNotFound(
+            "trueee".to_owned()
+        ))
     );
 
-    assert_eq!(eval_int("3"), Ok(3));
     assert_eq!(
-        eval_int("3.3"),
-        Err(EvalexprError::ExpectedInt {
-            actual: Value::Float(3.3)
+        build_operator_tree("3,3").unwrap().eval_tuple(),
+        Ok(vec![Value::Int(3), Value::Int(3)])
+    );
+    assert_eq!(
+        build_operator_tree("33").unwrap().eval_tuple(),
+        Err(EvalexprError::ExpectedTuple {
+            actual: Value::Int(33)
         })
     );
     assert_eq!(
-        eval_int("(,);."),
-        Err(EvalexprError::VariableIdentifierNotFound(".".to_owned()))
+        build_operator_tree("3a3").unwrap().eval_tuple(),
+        Err(EvalexprError::VariableIdentifierNotFound("3a3".to_owned()))
     );
-    assert_eq!(eval_int_with_context("3", &context), Ok(3));
     assert_eq!(
-        eval_int_with_context("3.3", &context),
-        Err(EvalexprError::ExpectedInt {
-            actual: Value::Float(3.3)
+        build_operator_tree("3,3")
+            .unwrap()
+            .eval_tuple_with_context(&context),
+        Ok(vec![Value::Int(3), Value::Int(3)])
+    );
+    assert_eq!(
+        build_operator_tree("33")
+            .unwrap()
+            .eval_tuple_with_context(&context),
+        Err(EvalexprError::ExpectedTuple {
+            actual: Value::Int(33)
         })
     );
     assert_eq!(
-        eval_int_with_context("(,);.", &context),
-        Err(EvalexprError::VariableIdentifierNotFound(".".to_owned()))
+        build_operator_tree("3a3")
+            .unwrap()
+            .eval_tuple_with_context(&context),
+        Err(EvalexprError::VariableIdentifierNotFound("3a3".to_owned()))
     );
-    assert_eq!(eval_int_with_context_mut("3", &mut context), Ok(3));
     assert_eq!(
-        eval_int_with_context_mut("3.3", &mut context),
-        Err(EvalexprError::ExpectedInt {
-            actual: Value::Float(3.3)
+        build_operator_tree("3,3")
+            .unwrap()
+            .eval_tuple_with_context_mut(&mut context),
+        Ok(vec![Value::Int(3), Value::Int(3)])
+    );
+    assert_eq!(
+        build_operator_tree("33")
+            .unwrap()
+            .eval_tuple_with_context_mut(&mut context),
+        Err(EvalexprError::ExpectedTuple {
+            actual: Value::Int(33)
         })
     );
     assert_eq!(
-        eval_int_with_context_mut("(,);.", &mut context),
-        Err(EvalexprError::VariableIdentifierNotFound(".".to_owned()))
+        build_operator_tree("3a3")
+            .unwrap()
+            .eval_tuple_with_context_mut(&mut context),
+        Err(EvalexprError::VariableIdentifierNotFound("3a3".to_owned()))
     );
 
-    assert_eq!(eval_number("3"), Ok(3.0));
     assert_eq!(
-        eval_number("true"),
-        Err(EvalexprError::ExpectedNumber {
-            actual: Value::Boolean(true)
+        build_operator_tree("").unwrap().eval_empty(),
+        Ok(EMPTY_VALUE)
+    );
+    assert_eq!(
+        build_operator_tree("()").unwrap().eval_empty(),
+        Ok(EMPTY_VALUE)
+    );
+    assert_eq!(
+        build_operator_tree("((), ())").unwrap().eval_empty(),
+        Err(EvalexprError::ExpectedEmpty {
+            actual: Value::Tuple(vec![Value::Empty, Value::Empty])
         })
     );
     assert_eq!(
-        eval_number("abc"),
-        Err(EvalexprError::VariableIdentifierNotFound("abc".to_owned()))
+        build_operator_tree("xaq").unwrap().eval_empty(),
+        Err(EvalexprError::VariableIdentifierNotFound("xaq".to_owned()))
     );
-    assert_eq!(eval_number_with_context("3.5", &context), Ok(3.5));
-    assert_eq!(eval_number_with_context("3", &context), Ok(3.0));
     assert_eq!(
-        eval_number_with_context("true", &context),
-        Err(EvalexprError::ExpectedNumber {
-            actual: Value::Boolean(true)
+        build_operator_tree("")
+            .unwrap()
+            .eval_empty_with_context(&context),
+        Ok(EMPTY_VALUE)
+    );
+    assert_eq!(
+        build_operator_tree("()")
+            .unwrap()
+            .eval_empty_with_context(&context),
+        Ok(EMPTY_VALUE)
+    );
+    assert_eq!(
+        build_operator_tree("((), ())")
+            .unwrap()
+            .eval_empty_with_context(&context),
+        Err(EvalexprError::ExpectedEmpty {
+            actual: Value::Tuple(vec![Value::Empty, Value::Empty])
         })
     );
     assert_eq!(
-        eval_number_with_context("abc", &context),
-        Err(EvalexprError::VariableIdentifierNotFound("abc".to_owned()))
+        build_operator_tree("xaq")
+            .unwrap()
+            .eval_empty_with_context(&context),
+        Err(EvalexprError::VariableIdentifierNotFound("xaq".to_owned()))
     );
-    assert_eq!(eval_number_with_context_mut("3.5", &mut context), Ok(3.5));
-    assert_eq!(eval_number_with_context_mut("3", &mut context), Ok(3.0));
     assert_eq!(
-        eval_number_with_context_mut("true", &mut context),
-        Err(EvalexprError::ExpectedNumber {
-            actual: Value::Boolean(true)
+        build_operator_tree("")
+            .unwrap()
+            .eval_empty_with_context_mut(&mut context),
+        Ok(EMPTY_VALUE)
+    );
+    assert_eq!(
+        build_operator_tree("()")
+            .unwrap()
+            .eval_empty_with_context_mut(&mut context),
+        Ok(EMPTY_VALUE)
+    );
+    assert_eq!(
+        build_operator_tree("((), ())")
+            .unwrap()
+            .eval_empty_with_context_mut(&mut context),
+        Err(EvalexprError::ExpectedEmpty {
+            actual: Value::Tuple(vec![Value::Empty, Value::Empty])
         })
     );
     assert_eq!(
-        eval_number_with_context_mut("abc", &mut context),
-        Err(EvalexprError::VariableIdentifierNotFound("abc".to_owned()))
+        build_operator_tree("xaq")
+            .unwrap()
+            .eval_empty_with_context_mut(&mut context),
+        Err(EvalexprError::VariableIdentifierNotFound("xaq".to_owned()))
     );
+}
 
-    assert_eq!(eval_boolean("true"), Ok(true));
+#[test]
+fn test_whitespace() {
+    assert!(eval_boolean("2 < = 3").is_err());
+}
+
+#[test]
+fn test_assignment() {
+    let mut context = HashMapContext::new();
     assert_eq!(
-        eval_boolean("4"),
-        Err(EvalexprError::ExpectedBoolean {
-            actual: Value::Int(4)
-        })
+        eval_empty_with_context_mut("int = 3", &mut context),
+        Ok(EMPTY_VALUE)
     );
     assert_eq!(
-        eval_boolean("trueee"),
-        Err(EvalexprError::VariableIdentifierNotFound(
-            "trueee".to_owned()
-        ))
+        eval_empty_with_context_mut("float = 2.0", &mut context),
+        Ok(EMPTY_VALUE)
     );
-    assert_eq!(eval_boolean_with_context("true", &context), Ok(true));
     assert_eq!(
-        eval_boolean_with_context("4", &context),
-        Err(EvalexprError::ExpectedBoolean {
-            actual: Value::Int(4)
-        })
+        eval_empty_with_context_mut("tuple = (1,1)", &mut context),
+        Ok(EMPTY_VALUE)
     );
     assert_eq!(
-        eval_boolean_with_context("trueee", &context),
-        Err(EvalexprError::VariableIdentifierNotFound(
-            "trueee".to_owned()
-        ))
+        eval_empty_with_context_mut("empty = ()", &mut context),
+        Ok(EMPTY_VALUE)
     );
     assert_eq!(
-        eval_boolean_with_context_mut("true", &mut context),
+        eval_empty_with_context_mut("boolean = false", &mut context),
+        Ok(EMPTY_VALUE)
+    );
+
+    assert_eq!(eval_int_with_context("int", &context), Ok(3));
+    assert_eq!(eval_float_with_context("float", &context), Ok(2.0));
+    assert_eq!(
+        eval_tuple_with_context("tuple", &context),
+        Ok(vec![1.into(), 1.into()])
+    );
+    assert_eq!(eval_empty_with_context("empty", &context), Ok(EMPTY_VALUE));
+    assert_eq!(eval_boolean_with_context("boolean", &context), Ok(false));
+
+    assert_eq!(
+        eval_empty_with_context_mut("b = a = 5", &mut context),
+        Ok(EMPTY_VALUE)
+    );
+    assert_eq!(eval_empty_with_context("b", &context), Ok(EMPTY_VALUE));
+}
+
+#[test]
+fn test_expression_chaining() {
+    let mut context = HashMapContext::new();
+    assert_eq!(
+        eval_int_with_context_mut("a = 5; a = a + 2; a", &mut context),
+        Ok(7)
+    );
+    assert_eq!(
+        eval_with_context_mut("x = 5; x + 1", &mut context),
+        Ok(Value::Int(6))
+    );
+    assert_eq!(
+        eval_with_context_mut("x = 6; y = x * 2;", &mut context),
+        Ok(Value::Empty)
+    );
+    assert_eq!(context.get_value("y"), Some(&Value::Int(12)));
+    assert_eq!(
+        eval_with_context("z = 1; z", &context),
+        Err(EvalexprError::ContextNotMutable)
+    );
+    assert_eq!(context.get_value("z"), None);
+}
+
+#[test]
+fn test_strings() {
+    assert_eq!(eval("\"foo\" + \"bar\""), Ok(Value::from("foobar")));
+    assert_eq!(eval("\"x\" + str::from(1)"), Ok(Value::from("x1")));
+    let mut context = HashMapContext::new();
+    assert_eq!(eval("\"string\""), Ok(Value::from("string")));
+    assert_eq!(
+        eval_with_context_mut("a = \"a string\"", &mut context),
+        Ok(Value::Empty)
+    );
+    assert_eq!(
+        eval_boolean_with_context("a == \"a string\"", &context),
         Ok(true)
     );
+    assert_eq!(eval("\"a\" + \"b\""), Ok(Value::from("ab")));
+    assert_eq!(eval("\"a\" > \"b\""), Ok(Value::from(false)));
+    assert_eq!(eval("\"a\" < \"b\""), Ok(Value::from(true)));
+    assert_eq!(eval("\"a\" >= \"b\""), Ok(Value::from(false)));
+    assert_eq!(eval("\"a\" <= \"b\""), Ok(Value::from(true)));
+    assert_eq!(eval("\"a\" >= \"a\""), Ok(Value::from(true)));
+    assert_eq!(eval("\"a\" <= \"a\""), Ok(Value::from(true)));
+    assert_eq!(eval("\"xa\" > \"xb\""), Ok(Value::from(false)));
+    assert_eq!(eval("\"xa\" < \"xb\""), Ok(Value::from(true)));
+    assert_eq!(eval("\"{}\" != \"{}\""), Ok(Value::from(false)));
+    assert_eq!(eval("\"{}\" == \"{}\""), Ok(Value::from(true)));
+}
+
+#[test]
+fn test_string_escaping() {
+    assert_eq!(
+        eval("\"\\\"str\\\\ing\\\"\""),
+        Ok(Value::from("\"str\\ing\""))
+    );
+    assert_eq!(eval("\"a\\nb\\tc\\rd\""), Ok(Value::from("a\nb\tc\rd")));
     assert_eq!(
-        eval_boolean_with_context_mut("4", &mut context),
-        Err(EvalexprError::ExpectedBoolean {
-            actual: Value::Int(4)
-        })
+        eval("\"\\q\""),
+        Err(EvalexprError::IllegalEscapeSequence("\\q".to_string()))
     );
     assert_eq!(
-        eval_boolean_with_context_mut("trueee", &mut context),
-        Err(EvalexprError::VariableIdentifierNotFound(
-            "trueee".to_owned()
-        ))
+        eval("\"abc\\"),
+        Err(EvalexprError::IllegalEscapeSequence("\\".to_string()))
+    );
+}
+
+#[test]
+fn test_single_quoted_strings() {
+    assert_eq!(eval("'abc'"), Ok(Value::from("abc")));
+    assert_eq!(eval("''"), Ok(Value::from("")));
+    assert_eq!(eval("'a' + \"b\" + 'c'"), Ok(Value::from("abc")));
+    assert_eq!(eval("'abc' == \"abc\""), Ok(Value::Boolean(true)));
+    assert_eq!(eval(r#"'say "hi"'"#), Ok(Value::from("say \"hi\"")));
+    assert_eq!(eval(r#""it's""#), Ok(Value::from("it's")));
+    assert_eq!(eval(r"'it\'s'"), Ok(Value::from("it's")));
+    assert_eq!(eval(r#""\'\"""#), Ok(Value::from("'\"")));
+    assert_eq!(eval(r"'a\nb\\'"), Ok(Value::from("a\nb\\")));
+    assert_eq!(
+        eval(r"'\q'"),
+        Err(EvalexprError::IllegalEscapeSequence("\\q".to_string()))
+    );
+    assert_eq!(
+        tokenize("f('a', \"b\")"),
+        Ok(vec![
+            (Token::Identifier("f".into()), 0),
+            (Token::LBrace, 1),
+            (Token::String("a".into()), 2),
+            (Token::Comma, 5),
+            (Token::String("b".into()), 7),
+            (Token::RBrace, 10),
+        ])
+    );
+}
+
+#[test]
+fn test_comments() {
+    assert_eq!(eval("1 + 2 // add them"), Ok(Value::from(3)));
+    assert_eq!(eval("1 + 2 //"), Ok(Value::from(3)));
+    assert_eq!(eval("// only a comment"), Ok(Value::Empty));
+    assert_eq!(
+        eval("a = 2; // first line\n// second line\na * 3 // third line"),
+        Ok(Value::from(6))
     );
+    assert_eq!(eval("4 //comment\n/ 2"), Ok(Value::from(2)));
+    assert_eq!(eval("\"a // b\""), Ok(Value::from("a // b")));
+    assert_eq!(eval("4 / 2"), Ok(Value::from(2)));
+}
 
-    assert_eq!(eval_tuple("3,3"), Ok(vec![Value::Int(3), Value::Int(3)]));
-    assert_eq!(
-        eval_tuple("33"),
-        Err(EvalexprError::ExpectedTuple {
-            actual: Value::Int(33)
+#[test]
+fn test_error_trait() {
+    fn evaluate(expression: &str) -> Result<IntType, Box<dyn std::error::Error>> {
+        Ok(eval_int(expression)?)
+    }
+
+    assert_eq!(evaluate("1 + 2").unwrap(), 3);
+    let error = evaluate("1 / 0").unwrap_err();
+    assert_eq!(error.to_string(), "Error dividing 1 / 0.");
+    assert_eq!(
+        error.downcast_ref::<EvalexprError>(),
+        Some(&EvalexprError::DivisionError {
+            dividend: Value::Int(1),
+            divisor: Value::Int(0)
         })
     );
+
     assert_eq!(
-        eval_tuple("3a3"),
-        Err(EvalexprError::VariableIdentifierNotFound("3a3".to_owned()))
+        eval("a").unwrap_err().to_string(),
+        "Variable 'a' is not bound in the context."
     );
     assert_eq!(
-        eval_tuple_with_context("3,3", &context),
-        Ok(vec![Value::Int(3), Value::Int(3)])
+        eval("(1 + 2").unwrap_err().to_string(),
+        "At position 0: Found an unmatched opening parenthesis '('."
     );
     assert_eq!(
-        eval_tuple_with_context("33", &context),
-        Err(EvalexprError::ExpectedTuple {
-            actual: Value::Int(33)
-        })
+        eval("\"a\" < 1").unwrap_err().to_string(),
+        "The operator Lt was called with a wrong combination of types: [String, Int]."
     );
     assert_eq!(
-        eval_tuple_with_context("3a3", &context),
-        Err(EvalexprError::VariableIdentifierNotFound("3a3".to_owned()))
+        eval("(1, 2)[5]").unwrap_err().to_string(),
+        "Index 5 is out of bounds for length 2."
     );
     assert_eq!(
-        eval_tuple_with_context_mut("3,3", &mut context),
-        Ok(vec![Value::Int(3), Value::Int(3)])
+        eval("1 2").unwrap_err().to_string(),
+        "At position 2: Tried to append a node to a leaf node."
     );
+}
+
+#[test]
+fn test_error_positions() {
+    let error = eval("1 + * 2").unwrap_err();
+    assert_eq!(error.position(), Some(4));
     assert_eq!(
-        eval_tuple_with_context_mut("33", &mut context),
-        Err(EvalexprError::ExpectedTuple {
-            actual: Value::Int(33)
-        })
+        error,
+        EvalexprError::WrongOperatorArgumentAmount {
+            expected: 2,
+            actual: 1
+        }
+        .at_position(4)
     );
     assert_eq!(
-        eval_tuple_with_context_mut("3a3", &mut context),
-        Err(EvalexprError::VariableIdentifierNotFound("3a3".to_owned()))
+        error.to_string(),
+        "At position 4: An operator expected 2 arguments, but got 1."
     );
-
-    assert_eq!(eval_empty(""), Ok(EMPTY_VALUE));
-    assert_eq!(eval_empty("()"), Ok(EMPTY_VALUE));
     assert_eq!(
-        eval_empty("(,)"),
-        Err(EvalexprError::ExpectedEmpty {
-            actual: Value::Tuple(vec![Value::Empty, Value::Empty])
-        })
+        eval("a = 1;\nb = 1 2"),
+        Err(EvalexprError::AppendedToLeafNode.at_position(13))
     );
     assert_eq!(
-        eval_empty("xaq"),
-        Err(EvalexprError::VariableIdentifierNotFound("xaq".to_owned()))
+        eval("foo + 1").unwrap_err().to_string(),
+        "Variable 'foo' is not bound in the context."
     );
-    assert_eq!(eval_empty_with_context("", &context), Ok(EMPTY_VALUE));
-    assert_eq!(eval_empty_with_context("()", &context), Ok(EMPTY_VALUE));
     assert_eq!(
-        eval_empty_with_context("(,)", &context),
-        Err(EvalexprError::ExpectedEmpty {
-            actual: Value::Tuple(vec![Value::Empty, Value::Empty])
-        })
+        eval("bar(1)").unwrap_err().to_string(),
+        "Function 'bar' is not bound in the context."
     );
+    assert_eq!(eval("(1").unwrap_err().position(), Some(0));
     assert_eq!(
-        eval_empty_with_context("xaq", &context),
-        Err(EvalexprError::VariableIdentifierNotFound("xaq".to_owned()))
+        eval("((1 + 2) * (3"),
+        Err(EvalexprError::UnmatchedLBrace.at_position(11))
     );
-    assert_eq!(
-        eval_empty_with_context_mut("", &mut context),
-        Ok(EMPTY_VALUE)
+    assert_eq!(eval("1 / 0").unwrap_err().position(), None);
+
+    // The positions of nodes do not affect the equality of trees.
+    let tree = build_operator_tree("1+2*x").unwrap();
+    assert_eq!(tree.children()[0].position(), Some(1));
+    let spaced_tree = build_operator_tree("1 + 2 * x").unwrap();
+    assert_eq!(spaced_tree.children()[0].position(), Some(2));
+    assert_eq!(tree, spaced_tree);
+    assert_ne!(tree, build_operator_tree("1 + 2 * y").unwrap());
+    assert_eq!(
+        EvalexprError::UnmatchedRBrace
+            .at_position(1)
+            .at_position(2)
+            .position(),
+        Some(1)
     );
+}
+
+#[test]
+fn test_tuple_definitions() {
+    assert_eq!(eval_empty("()"), Ok(()));
+    assert_eq!(eval_int("(3)"), Ok(3));
     assert_eq!(
-        eval_empty_with_context_mut("()", &mut context),
-        Ok(EMPTY_VALUE)
+        eval_tuple("(3, 4)"),
+        Ok(vec![Value::from(3), Value::from(4)])
     );
     assert_eq!(
-        eval_empty_with_context_mut("(,)", &mut context),
-        Err(EvalexprError::ExpectedEmpty {
-            actual: Value::Tuple(vec![Value::Empty, Value::Empty])
-        })
+        eval_tuple("2, (5, 6)"),
+        Ok(vec![
+            Value::from(2),
+            Value::from(vec![Value::from(5), Value::from(6)])
+        ])
     );
+    assert_eq!(eval_tuple("1, 2"), Ok(vec![Value::from(1), Value::from(2)]));
     assert_eq!(
-        eval_empty_with_context_mut("xaq", &mut context),
-        Err(EvalexprError::VariableIdentifierNotFound("xaq".to_owned()))
+        eval_tuple("1, 2, 3, 4"),
+        Ok(vec![
+            Value::from(1),
+            Value::from(2),
+            Value::from(3),
+            Value::from(4)
+        ])
     );
-
-    // With detour via build_operator_tree
-
     assert_eq!(
-        build_operator_tree("\"3.3\"").unwrap().eval_string(),
-        Ok("3.3".to_owned())
+        eval_tuple("(1, 2, 3), 5, 6, (true, false, 0)"),
+        Ok(vec![
+            Value::from(vec![Value::from(1), Value::from(2), Value::from(3)]),
+            Value::from(5),
+            Value::from(6),
+            Value::from(vec![Value::from(true), Value::from(false), Value::from(0)])
+        ])
     );
     assert_eq!(
-        build_operator_tree("3.3").unwrap().eval_string(),
-        Err(EvalexprError::ExpectedString {
-            actual: Value::Float(3.3)
-        })
+        eval_tuple("1, (2)"),
+        Ok(vec![Value::from(1), Value::from(2)])
     );
     assert_eq!(
-        build_operator_tree("3..3").unwrap().eval_string(),
-        Err(EvalexprError::VariableIdentifierNotFound("3..3".to_owned()))
+        eval_tuple("1, ()"),
+        Ok(vec![Value::from(1), Value::from(())])
     );
     assert_eq!(
-        build_operator_tree("string")
-            .unwrap()
-            .eval_string_with_context(&context),
-        Ok("a string".to_owned())
+        eval_tuple("1, ((2))"),
+        Ok(vec![Value::from(1), Value::from(2)])
     );
+}
+
+#[test]
+fn test_dotted_identifiers() {
     assert_eq!(
-        build_operator_tree("3.3")
-            .unwrap()
-            .eval_string_with_context(&context),
-        Err(EvalexprError::ExpectedString {
-            actual: Value::Float(3.3)
-        })
+        tokenize("user.age>=18").unwrap(),
+        vec![
+            (Token::Identifier("user.age".to_owned()), 0),
+            (Token::Geq, 8),
+            (Token::Int(18), 10)
+        ]
     );
+
+    let mut context = context_map! {
+        "user.name" => "Ada",
+        "user.address.city" => "London",
+    }
+    .unwrap();
     assert_eq!(
-        build_operator_tree("3..3")
-            .unwrap()
-            .eval_string_with_context(&context),
-        Err(EvalexprError::VariableIdentifierNotFound("3..3".to_owned()))
+        eval_with_context("user.name", &context),
+        Ok(Value::from("Ada"))
     );
     assert_eq!(
-        build_operator_tree("string")
-            .unwrap()
-            .eval_string_with_context_mut(&mut context),
-        Ok("a string".to_string())
+        eval_with_context("user.address.city", &context),
+        Ok(Value::from("London"))
     );
     assert_eq!(
-        build_operator_tree("3.3")
-            .unwrap()
-            .eval_string_with_context_mut(&mut context),
-        Err(EvalexprError::ExpectedString {
-            actual: Value::Float(3.3)
-        })
+        eval_with_context_mut("user.age = 36; user.age += 1; user.age", &mut context),
+        Ok(Value::Int(37))
     );
+    assert_eq!(context.get_value("user.age"), Some(&Value::Int(37)));
     assert_eq!(
-        build_operator_tree("3..3")
-            .unwrap()
-            .eval_string_with_context_mut(&mut context),
-        Err(EvalexprError::VariableIdentifierNotFound("3..3".to_owned()))
+        eval_with_context("user", &context),
+        Err(EvalexprError::VariableIdentifierNotFound("user".to_owned()))
     );
-
-    assert_eq!(build_operator_tree("3.3").unwrap().eval_float(), Ok(3.3));
     assert_eq!(
-        build_operator_tree("33").unwrap().eval_float(),
-        Err(EvalexprError::ExpectedFloat {
-            actual: Value::Int(33)
-        })
+        eval_with_context("user.email", &context),
+        Err(EvalexprError::VariableIdentifierNotFound(
+            "user.email".to_owned()
+        ))
     );
     assert_eq!(
-        build_operator_tree("asd()").unwrap().eval_float(),
-        Err(EvalexprError::FunctionIdentifierNotFound("asd".to_owned()))
+        eval_with_context("1.5 + .5", &context),
+        Ok(Value::Float(2.0))
     );
+}
+
+#[test]
+fn test_implicit_context() {
     assert_eq!(
-        build_operator_tree("3.3")
-            .unwrap()
-            .eval_float_with_context(&context),
-        Ok(3.3)
+        eval("a = 2 + 4 * 2; b = -5 + 3 * 5; a == b"),
+        Ok(Value::from(true))
     );
     assert_eq!(
-        build_operator_tree("33")
-            .unwrap()
-            .eval_float_with_context(&context),
-        Err(EvalexprError::ExpectedFloat {
-            actual: Value::Int(33)
-        })
+        eval_boolean("a = 2 + 4 * 2; b = -5 + 3 * 5; a == b"),
+        Ok(true)
     );
+    assert_eq!(eval_int("a = 2 + 4 * 2; b = -5 + 3 * 5; a - b"), Ok(0));
     assert_eq!(
-        build_operator_tree("asd")
-            .unwrap()
-            .eval_float_with_context(&context),
-        Err(EvalexprError::VariableIdentifierNotFound("asd".to_owned()))
+        eval_float("a = 2 + 4 * 2; b = -5 + 3 * 5; a - b + 0.5"),
+        Ok(0.5)
     );
+    assert_eq!(eval_number("a = 2 + 4 * 2; b = -5 + 3 * 5; a - b"), Ok(0.0));
+    assert_eq!(eval_empty("a = 2 + 4 * 2; b = -5 + 3 * 5;"), Ok(()));
     assert_eq!(
-        build_operator_tree("3.3")
-            .unwrap()
-            .eval_float_with_context_mut(&mut context),
-        Ok(3.3)
+        eval_tuple("a = 2 + 4 * 2; b = -5 + 3 * 5; a, b + 0.5"),
+        Ok(vec![Value::from(10), Value::from(10.5)])
     );
     assert_eq!(
-        build_operator_tree("33")
-            .unwrap()
-            .eval_float_with_context_mut(&mut context),
-        Err(EvalexprError::ExpectedFloat {
-            actual: Value::Int(33)
-        })
+        eval_string("a = \"xyz\"; b = \"abc\"; c = a + b; c"),
+        Ok("xyzabc".to_string())
     );
+}
+
+#[test]
+fn test_operator_assignments() {
+    let mut context = HashMapContext::new();
+    assert_eq!(eval_empty_with_context_mut("a = 5", &mut context), Ok(()));
+    assert_eq!(eval_empty_with_context_mut("a += 5", &mut context), Ok(()));
+    assert_eq!(eval_empty_with_context_mut("a -= 5", &mut context), Ok(()));
+    assert_eq!(eval_empty_with_context_mut("a *= 5", &mut context), Ok(()));
+    assert_eq!(eval_empty_with_context_mut("b = 5.0", &mut context), Ok(()));
+    assert_eq!(eval_empty_with_context_mut("b /= 5", &mut context), Ok(()));
+    assert_eq!(eval_empty_with_context_mut("b %= 5", &mut context), Ok(()));
+    assert_eq!(eval_empty_with_context_mut("b ^= 5", &mut context), Ok(()));
     assert_eq!(
-        build_operator_tree("asd")
-            .unwrap()
-            .eval_float_with_context_mut(&mut context),
-        Err(EvalexprError::VariableIdentifierNotFound("asd".to_owned()))
+        eval_empty_with_context_mut("c = true", &mut context),
+        Ok(())
     );
-
-    assert_eq!(build_operator_tree("3").unwrap().eval_int(), Ok(3));
     assert_eq!(
-        build_operator_tree("3.3").unwrap().eval_int(),
-        Err(EvalexprError::ExpectedInt {
-            actual: Value::Float(3.3)
-        })
+        eval_empty_with_context_mut("c &&= false", &mut context),
+        Ok(())
     );
     assert_eq!(
-        build_operator_tree("(,);.").unwrap().eval_int(),
-        Err(EvalexprError::VariableIdentifierNotFound(".".to_owned()))
+        eval_empty_with_context_mut("c ||= true", &mut context),
+        Ok(())
     );
+
+    let mut context = HashMapContext::new();
+    assert_eq!(eval_int_with_context_mut("a = 5; a", &mut context), Ok(5));
+    assert_eq!(eval_int_with_context_mut("a += 3; a", &mut context), Ok(8));
+    assert_eq!(eval_int_with_context_mut("a -= 5; a", &mut context), Ok(3));
+    assert_eq!(eval_int_with_context_mut("a *= 5; a", &mut context), Ok(15));
     assert_eq!(
-        build_operator_tree("3")
-            .unwrap()
-            .eval_int_with_context(&context),
-        Ok(3)
+        eval_float_with_context_mut("b = 5.0; b", &mut context),
+        Ok(5.0)
     );
     assert_eq!(
-        build_operator_tree("3.3")
-            .unwrap()
-            .eval_int_with_context(&context),
-        Err(EvalexprError::ExpectedInt {
-            actual: Value::Float(3.3)
-        })
+        eval_float_with_context_mut("b /= 2; b", &mut context),
+        Ok(2.5)
     );
     assert_eq!(
-        build_operator_tree("(,);.")
-            .unwrap()
-            .eval_int_with_context(&context),
-        Err(EvalexprError::VariableIdentifierNotFound(".".to_owned()))
+        eval_float_with_context_mut("b %= 2; b", &mut context),
+        Ok(0.5)
     );
     assert_eq!(
-        build_operator_tree("3")
-            .unwrap()
-            .eval_int_with_context_mut(&mut context),
-        Ok(3)
+        eval_float_with_context_mut("b ^= 2; b", &mut context),
+        Ok(0.25)
     );
     assert_eq!(
-        build_operator_tree("3.3")
-            .unwrap()
-            .eval_int_with_context_mut(&mut context),
-        Err(EvalexprError::ExpectedInt {
-            actual: Value::Float(3.3)
-        })
+        eval_boolean_with_context_mut("c = true; c", &mut context),
+        Ok(true)
     );
     assert_eq!(
-        build_operator_tree("(,);.")
-            .unwrap()
-            .eval_int_with_context_mut(&mut context),
-        Err(EvalexprError::VariableIdentifierNotFound(".".to_owned()))
+        eval_boolean_with_context_mut("c &&= false; c", &mut context),
+        Ok(false)
+    );
+    assert_eq!(
+        eval_boolean_with_context_mut("c ||= true; c", &mut context),
+        Ok(true)
     );
+}
 
-    assert_eq!(build_operator_tree("3").unwrap().eval_number(), Ok(3.0));
+#[test]
+fn test_type_errors_in_binary_operators() {
+    // Only addition and ordering comparisons support incompatible types, all others work only on numbers or only on booleans.
+    // So only those require the more fancy error message.
     assert_eq!(
-        build_operator_tree("true").unwrap().eval_number(),
-        Err(EvalexprError::ExpectedNumber {
-            actual: Value::Boolean(true)
-        })
+        eval("4 + \"abc\""),
+        Err(EvalexprError::wrong_type_combination(
+            Operator::Add,
+            vec![ValueType::Int, ValueType::String]
+        ))
     );
     assert_eq!(
-        build_operator_tree("abc").unwrap().eval_number(),
-        Err(EvalexprError::VariableIdentifierNotFound("abc".to_owned()))
+        eval("\"abc\" + 4"),
+        Err(EvalexprError::wrong_type_combination(
+            Operator::Add,
+            vec![ValueType::String, ValueType::Int]
+        ))
     );
     assert_eq!(
-        build_operator_tree("3")
-            .unwrap()
-            .eval_number_with_context(&context),
-        Ok(3.0)
+        eval("\"abc\" < 4"),
+        Err(EvalexprError::wrong_type_combination(
+            Operator::Lt,
+            vec![ValueType::String, ValueType::Int]
+        ))
     );
     assert_eq!(
-        build_operator_tree("true")
-            .unwrap()
-            .eval_number_with_context(&context),
-        Err(EvalexprError::ExpectedNumber {
-            actual: Value::Boolean(true)
-        })
+        eval("4.0 >= \"abc\""),
+        Err(EvalexprError::wrong_type_combination(
+            Operator::Geq,
+            vec![ValueType::Float, ValueType::String]
+        ))
     );
     assert_eq!(
-        build_operator_tree("abc")
-            .unwrap()
-            .eval_number_with_context(&context),
-        Err(EvalexprError::VariableIdentifierNotFound("abc".to_owned()))
+        eval("true > false"),
+        Err(EvalexprError::expected_number_or_string(Value::Boolean(
+            true
+        )))
+    );
+}
+
+#[test]
+fn test_comparison_examples() {
+    assert_eq!(eval("1 < 1.5"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("2 >= 1.5"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("2 <= 2"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("\"abc\" < \"abd\""), Ok(Value::Boolean(true)));
+    assert_eq!(eval("\"b\" > \"abc\""), Ok(Value::Boolean(true)));
+    assert_eq!(eval("true == true"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("true != false"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("(1, \"a\") == (1, \"a\")"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("(1, \"a\") != (1, \"b\")"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("1 + 2 == 3"), Ok(Value::Boolean(true)));
+}
+
+#[test]
+fn test_chained_comparisons() {
+    assert_eq!(eval("0 <= 5 <= 10"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("0 <= 15 <= 10"), Ok(Value::Boolean(false)));
+    assert_eq!(eval("1 == 1.0"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("1 <= 1.0 >= 1"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("1.0 in (2, 1)"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("1 < 2 < 3 < 4"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("1 < 2 < 2 < 4"), Ok(Value::Boolean(false)));
+    assert_eq!(eval("1 < 3 > 2"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("3 >= 3 > 1 <= 1.5"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("1 < 2 < 3.5 >= 3.5"), Ok(Value::Boolean(true)));
+    assert_eq!(
+        eval_with_context(
+            "0 < x < 1",
+            &context_map! { "x" => FloatType::NAN }.unwrap()
+        ),
+        Ok(Value::Boolean(false))
     );
+    assert_eq!(eval("\"a\" < \"b\" < \"c\""), Ok(Value::Boolean(true)));
+    assert_eq!(eval("1 + 1 < 3 < 2 * 2"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("1 < 2 < 3 && 3 > 2 > 1"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("1 < 2 < 3 == true"), Ok(Value::Boolean(true)));
+
+    // The middle operand is evaluated only once.
+    let mut context = HashMapContext::new();
     assert_eq!(
-        build_operator_tree("3")
-            .unwrap()
-            .eval_number_with_context_mut(&mut context),
-        Ok(3.0)
+        eval_with_context_mut("0 < (x = 5; x) < 10; x", &mut context),
+        Ok(Value::from(5))
     );
     assert_eq!(
-        build_operator_tree("true")
-            .unwrap()
-            .eval_number_with_context_mut(&mut context),
-        Err(EvalexprError::ExpectedNumber {
-            actual: Value::Boolean(true)
-        })
+        eval_with_context_mut("0 < (x += 1; x) < 10", &mut context),
+        Ok(Value::Boolean(true))
     );
+    assert_eq!(context.get_value("x"), Some(&Value::from(6)));
+
+    // Operands after a false comparison are not evaluated.
     assert_eq!(
-        build_operator_tree("abc")
-            .unwrap()
-            .eval_number_with_context_mut(&mut context),
-        Err(EvalexprError::VariableIdentifierNotFound("abc".to_owned()))
+        eval_with_context_mut("2 < 1 < (x = 100; x)", &mut context),
+        Ok(Value::Boolean(false))
     );
+    assert_eq!(context.get_value("x"), Some(&Value::from(6)));
 
+    // Parenthesized comparisons are not chained.
     assert_eq!(
-        build_operator_tree("true").unwrap().eval_boolean(),
-        Ok(true)
+        eval("(1 < 2) < 3"),
+        Err(EvalexprError::expected_number_or_string(Value::Boolean(
+            true
+        )))
     );
     assert_eq!(
-        build_operator_tree("4").unwrap().eval_boolean(),
-        Err(EvalexprError::ExpectedBoolean {
-            actual: Value::Int(4)
+        eval("1 < 2 < \"a\""),
+        Err(EvalexprError::WrongTypeCombination {
+            operator: Operator::Lt,
+            actual: vec![ValueType::Int, ValueType::String]
         })
     );
+
+    let tree = build_operator_tree("0 <= x < 10").unwrap();
+    assert_eq!(tree.to_string(), "0 <= x < 10");
+    assert_eq!(tree.variable_identifiers(), vec!["x"]);
     assert_eq!(
-        build_operator_tree("trueee").unwrap().eval_boolean(),
-        Err(EvalexprError::VariableIdentifierNotFound(
-            "trueee".to_owned()
-        ))
+        build_operator_tree("(1 < 2) < 3").unwrap().to_string(),
+        "(1 < 2) < 3"
     );
-    assert_eq!(
-        build_operator_tree("true")
-            .unwrap()
-            .eval_boolean_with_context(&context),
-        Ok(true)
+}
+
+#[test]
+fn test_in_operator() {
+    assert_eq!(eval("3 in (1, 2, 3)"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("4 in (1, 2, 3)"), Ok(Value::Boolean(false)));
+    assert_eq!(eval("3.0 in (1, 2, 3)"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("3.5 in (1, 2, 3)"), Ok(Value::Boolean(false)));
+    assert_eq!(eval("(1, 2) in ((1, 2), 3)"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("() in ((), 1)"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("\"ell\" in \"hello\""), Ok(Value::Boolean(true)));
+    assert_eq!(eval("\"\" in \"hello\""), Ok(Value::Boolean(true)));
+    assert_eq!(eval("\"hello\" in \"ell\""), Ok(Value::Boolean(false)));
+    assert_eq!(eval("\"a\" in {\"a\": 1}"), Ok(Value::Boolean(true)));
+    assert_eq!(eval("\"b\" in {\"a\": 1}"), Ok(Value::Boolean(false)));
+    assert_eq!(
+        eval("1 + 1 in (1, 2) && !(\"x\" in \"abc\")"),
+        Ok(Value::Boolean(true))
     );
+
+    let mut context = HashMapContext::new();
+    context.set_value("index".into(), Value::from(2)).unwrap();
     assert_eq!(
-        build_operator_tree("4")
-            .unwrap()
-            .eval_boolean_with_context(&context),
-        Err(EvalexprError::ExpectedBoolean {
-            actual: Value::Int(4)
-        })
+        eval_with_context("index in (1, 2)", &context),
+        Ok(Value::Boolean(true))
     );
+
     assert_eq!(
-        build_operator_tree("trueee")
-            .unwrap()
-            .eval_boolean_with_context(&context),
-        Err(EvalexprError::VariableIdentifierNotFound(
-            "trueee".to_owned()
-        ))
+        eval("1 in \"123\""),
+        Err(EvalexprError::WrongTypeCombination {
+            operator: Operator::In,
+            actual: vec![ValueType::Int, ValueType::String]
+        })
     );
     assert_eq!(
-        build_operator_tree("true")
-            .unwrap()
-            .eval_boolean_with_context_mut(&mut context),
-        Ok(true)
+        eval("1 in 1"),
+        Err(EvalexprError::WrongTypeCombination {
+            operator: Operator::In,
+            actual: vec![ValueType::Int, ValueType::Int]
+        })
     );
     assert_eq!(
-        build_operator_tree("4")
-            .unwrap()
-            .eval_boolean_with_context_mut(&mut context),
-        Err(EvalexprError::ExpectedBoolean {
-            actual: Value::Int(4)
+        eval("1 in {\"1\": 1}"),
+        Err(EvalexprError::WrongTypeCombination {
+            operator: Operator::In,
+            actual: vec![ValueType::Int, ValueType::Map]
         })
     );
     assert_eq!(
-        build_operator_tree("trueee")
-            .unwrap()
-            .eval_boolean_with_context_mut(&mut context),
-        Err(EvalexprError::VariableIdentifierNotFound(
-            "trueee".to_owned()
-        ))
+        build_operator_tree("a in (1, 2)").unwrap().to_string(),
+        "a in (1, 2)"
     );
+}
 
+#[test]
+fn test_non_finite_floats_forbidden() {
+    let mut context = HashMapContext::new();
+    assert!(!context.are_non_finite_floats_forbidden());
     assert_eq!(
-        build_operator_tree("3,3").unwrap().eval_tuple(),
-        Ok(vec![Value::Int(3), Value::Int(3)])
+        eval_with_context("1.0 / 0.0", &context),
+        Ok(Value::Float(f64::INFINITY))
     );
+
+    assert_eq!(context.set_non_finite_floats_forbidden(true), Ok(()));
+    assert!(context.are_non_finite_floats_forbidden());
     assert_eq!(
-        build_operator_tree("33").unwrap().eval_tuple(),
-        Err(EvalexprError::ExpectedTuple {
-            actual: Value::Int(33)
-        })
+        eval_with_context("1.0 / 0.0", &context),
+        Err(EvalexprError::NonFiniteResult(f64::INFINITY))
     );
     assert_eq!(
-        build_operator_tree("3a3").unwrap().eval_tuple(),
-        Err(EvalexprError::VariableIdentifierNotFound("3a3".to_owned()))
+        eval_with_context("-1e400", &context),
+        Err(EvalexprError::NonFiniteResult(f64::INFINITY))
     );
+    assert!(matches!(
+        eval_with_context("math::sqrt(-1) + 1", &context),
+        Err(EvalexprError::NonFiniteResult(float)) if float.is_nan()
+    ));
     assert_eq!(
-        build_operator_tree("3,3")
-            .unwrap()
-            .eval_tuple_with_context(&context),
-        Ok(vec![Value::Int(3), Value::Int(3)])
+        eval_with_context("1.0 / 4.0", &context),
+        Ok(Value::Float(0.25))
     );
     assert_eq!(
-        build_operator_tree("33")
-            .unwrap()
-            .eval_tuple_with_context(&context),
-        Err(EvalexprError::ExpectedTuple {
-            actual: Value::Int(33)
-        })
+        eval_with_context("true || 1.0 / 0.0 > 0", &context),
+        Ok(Value::Boolean(true))
     );
     assert_eq!(
-        build_operator_tree("3a3")
-            .unwrap()
-            .eval_tuple_with_context(&context),
-        Err(EvalexprError::VariableIdentifierNotFound("3a3".to_owned()))
+        eval_with_context("false ? 1.0 / 0.0 : 2.0", &context),
+        Ok(Value::Float(2.0))
     );
+
     assert_eq!(
-        build_operator_tree("3,3")
-            .unwrap()
-            .eval_tuple_with_context_mut(&mut context),
-        Ok(vec![Value::Int(3), Value::Int(3)])
+        eval_with_context_mut("a = 1.0; a = a / 0.0", &mut context),
+        Err(EvalexprError::NonFiniteResult(f64::INFINITY))
     );
+    assert_eq!(context.get_value("a"), Some(&Value::Float(1.0)));
+
+    let mut btree_context = BTreeMapContext::new();
+    assert_eq!(btree_context.set_non_finite_floats_forbidden(true), Ok(()));
     assert_eq!(
-        build_operator_tree("33")
-            .unwrap()
-            .eval_tuple_with_context_mut(&mut context),
-        Err(EvalexprError::ExpectedTuple {
-            actual: Value::Int(33)
-        })
+        eval_with_context("1.0 / 0.0", &btree_context),
+        Err(EvalexprError::NonFiniteResult(f64::INFINITY))
     );
+
+    let fallback = HashMapContext::new();
+    let mut combined = CombinedContext::new(&context, &fallback);
+    assert!(combined.are_non_finite_floats_forbidden());
+    assert_eq!(combined.set_non_finite_floats_forbidden(true), Ok(()));
     assert_eq!(
-        build_operator_tree("3a3")
-            .unwrap()
-            .eval_tuple_with_context_mut(&mut context),
-        Err(EvalexprError::VariableIdentifierNotFound("3a3".to_owned()))
+        combined.set_non_finite_floats_forbidden(false),
+        Err(EvalexprError::NonFiniteFloatsCannotBeAllowed)
     );
-
+    let mut combined = CombinedContext::new(&fallback, &context);
     assert_eq!(
-        build_operator_tree("").unwrap().eval_empty(),
-        Ok(EMPTY_VALUE)
+        eval_with_context("1.0 / 0.0", &combined),
+        Ok(Value::Float(f64::INFINITY))
     );
     assert_eq!(
-        build_operator_tree("()").unwrap().eval_empty(),
-        Ok(EMPTY_VALUE)
+        combined.set_non_finite_floats_forbidden(true),
+        Err(EvalexprError::NonFiniteFloatsCannotBeForbidden)
     );
+
+    let mut context = EmptyContext;
+    assert_eq!(context.set_non_finite_floats_forbidden(false), Ok(()));
     assert_eq!(
-        build_operator_tree("(,)").unwrap().eval_empty(),
-        Err(EvalexprError::ExpectedEmpty {
-            actual: Value::Tuple(vec![Value::Empty, Value::Empty])
-        })
+        context.set_non_finite_floats_forbidden(true),
+        Err(EvalexprError::NonFiniteFloatsCannotBeForbidden)
     );
+}
+
+#[test]
+fn test_empty_context() {
+    let mut context = EmptyContext;
+    assert_eq!(context.get_value("abc"), None);
     assert_eq!(
-        build_operator_tree("xaq").unwrap().eval_empty(),
-        Err(EvalexprError::VariableIdentifierNotFound("xaq".to_owned()))
+        context.call_function("abc", &Value::Empty),
+        Err(EvalexprError::FunctionIdentifierNotFound("abc".to_owned()))
     );
     assert_eq!(
-        build_operator_tree("")
-            .unwrap()
-            .eval_empty_with_context(&context),
-        Ok(EMPTY_VALUE)
+        eval_with_context("max(1,3)", &context),
+        Err(EvalexprError::FunctionIdentifierNotFound(String::from(
+            "max"
+        )))
     );
+    assert_eq!(context.set_builtin_functions_disabled(true), Ok(()));
     assert_eq!(
-        build_operator_tree("()")
-            .unwrap()
-            .eval_empty_with_context(&context),
-        Ok(EMPTY_VALUE)
+        context.set_builtin_functions_disabled(false),
+        Err(EvalexprError::BuiltinFunctionsCannotBeEnabled)
+    )
+}
+
+#[test]
+fn test_empty_context_with_builtin_functions() {
+    let mut context = EmptyContextWithBuiltinFunctions;
+    assert_eq!(context.get_value("abc"), None);
+    assert_eq!(
+        context.call_function("abc", &Value::Empty),
+        Err(EvalexprError::FunctionIdentifierNotFound("abc".to_owned()))
     );
+    assert_eq!(eval_with_context("max(1,3)", &context), Ok(Value::Int(3)));
+    assert_eq!(context.set_builtin_functions_disabled(false), Ok(()));
     assert_eq!(
-        build_operator_tree("(,)")
-            .unwrap()
-            .eval_empty_with_context(&context),
-        Err(EvalexprError::ExpectedEmpty {
-            actual: Value::Tuple(vec![Value::Empty, Value::Empty])
-        })
+        context.set_builtin_functions_disabled(true),
+        Err(EvalexprError::BuiltinFunctionsCannotBeDisabled)
     );
+}
+
+#[test]
+fn test_combined_context() {
+    let globals = context_map! {
+        "a" => 1,
+        "b" => 2,
+        "f" => Function::new(|_| Ok(Value::from("globals"))),
+        "g" => Function::new(|_| Ok(Value::from("globals"))),
+    }
+    .unwrap();
+    let defaults = context_map! {
+        "b" => 3,
+        "c" => 4,
+        "f" => Function::new(|_| Ok(Value::from("defaults"))),
+    }
+    .unwrap();
+    let request = context_map! { "c" => 5 }.unwrap();
+
+    let defaults_and_globals = CombinedContext::new(&defaults, &globals);
+    let mut context = CombinedContext::new(&request, &defaults_and_globals);
+    assert_eq!(context.get_value("a"), Some(&Value::Int(1)));
+    assert_eq!(context.get_value("b"), Some(&Value::Int(3)));
+    assert_eq!(context.get_value("c"), Some(&Value::Int(5)));
+    assert_eq!(context.get_value("d"), None);
     assert_eq!(
-        build_operator_tree("xaq")
-            .unwrap()
-            .eval_empty_with_context(&context),
-        Err(EvalexprError::VariableIdentifierNotFound("xaq".to_owned()))
+        eval_with_context("f()", &context),
+        Ok(Value::from("defaults"))
     );
     assert_eq!(
-        build_operator_tree("")
-            .unwrap()
-            .eval_empty_with_context_mut(&mut context),
-        Ok(EMPTY_VALUE)
+        eval_with_context("g()", &context),
+        Ok(Value::from("globals"))
     );
     assert_eq!(
-        build_operator_tree("()")
-            .unwrap()
-            .eval_empty_with_context_mut(&mut context),
-        Ok(EMPTY_VALUE)
+        eval_with_context("h()", &context),
+        Err(EvalexprError::FunctionIdentifierNotFound("h".to_owned()))
     );
+    assert_eq!(eval_with_context("a + b * c", &context), Ok(Value::Int(16)));
+    assert_eq!(eval_with_context("max(a, c)", &context), Ok(Value::Int(5)));
+    assert!(context.get_function("g").is_some());
+    assert!(context.get_function("h").is_none());
     assert_eq!(
-        build_operator_tree("(,)")
-            .unwrap()
-            .eval_empty_with_context_mut(&mut context),
-        Err(EvalexprError::ExpectedEmpty {
-            actual: Value::Tuple(vec![Value::Empty, Value::Empty])
-        })
+        eval_with_context("a = 2", &context),
+        Err(EvalexprError::ContextNotMutable)
+    );
+
+    assert_eq!(context.set_builtin_functions_disabled(false), Ok(()));
+    assert_eq!(
+        context.set_builtin_functions_disabled(true),
+        Err(EvalexprError::BuiltinFunctionsCannotBeDisabled)
     );
+    let context = CombinedContext::new(&EmptyContext, &globals);
+    assert!(context.are_builtin_functions_disabled());
     assert_eq!(
-        build_operator_tree("xaq")
-            .unwrap()
-            .eval_empty_with_context_mut(&mut context),
-        Err(EvalexprError::VariableIdentifierNotFound("xaq".to_owned()))
+        eval_with_context("max(a, b)", &context),
+        Err(EvalexprError::FunctionIdentifierNotFound("max".to_owned()))
     );
 }
 
 #[test]
-fn test_whitespace() {
-    assert!(eval_boolean("2 < = 3").is_err());
-}
-
-#[test]
-fn test_assignment() {
-    let mut context = HashMapContext::new();
+fn test_map_builtin() {
+    let context = context_map! {
+        "double" => Function::new(|argument| Ok(Value::from(argument.as_int()? * 2))),
+        "count" => Function::new_with_arguments(|arguments| Ok(Value::from(arguments.len() as IntType))),
+        "list" => vec![Value::Int(1), Value::Int(2), Value::Int(3)],
+    }
+    .unwrap();
     assert_eq!(
-        eval_empty_with_context_mut("int = 3", &mut context),
-        Ok(EMPTY_VALUE)
+        eval_with_context("map(\"double\", (1, 2, 3))", &context),
+        Ok(Value::from(vec![
+            Value::Int(2),
+            Value::Int(4),
+            Value::Int(6)
+        ]))
     );
     assert_eq!(
-        eval_empty_with_context_mut("float = 2.0", &mut context),
-        Ok(EMPTY_VALUE)
+        eval_with_context("map(\"double\", map(\"double\", list))", &context),
+        Ok(Value::from(vec![
+            Value::Int(4),
+            Value::Int(8),
+            Value::Int(12)
+        ]))
     );
     assert_eq!(
-        eval_empty_with_context_mut("tuple = (1,1)", &mut context),
-        Ok(EMPTY_VALUE)
+        eval_with_context("map(\"double\", tuple())", &context),
+        Ok(Value::Tuple(vec![]))
     );
+    // Builtin functions can be mapped, and each element is passed as a single argument.
     assert_eq!(
-        eval_empty_with_context_mut("empty = ()", &mut context),
-        Ok(EMPTY_VALUE)
+        eval_with_context("map(\"math::abs\", (-1, 2.5))", &context),
+        Ok(Value::from(vec![Value::Int(1), Value::Float(2.5)]))
     );
     assert_eq!(
-        eval_empty_with_context_mut("boolean = false", &mut context),
-        Ok(EMPTY_VALUE)
+        eval_with_context("map(\"count\", ((1, 2), 3))", &context),
+        Ok(Value::from(vec![Value::Int(1), Value::Int(1)]))
     );
-
-    assert_eq!(eval_int_with_context("int", &context), Ok(3));
-    assert_eq!(eval_float_with_context("float", &context), Ok(2.0));
     assert_eq!(
-        eval_tuple_with_context("tuple", &context),
-        Ok(vec![1.into(), 1.into()])
+        eval_with_context("map(\"len\", ((1, 2), \"abc\"))", &context),
+        Ok(Value::from(vec![Value::Int(2), Value::Int(3)]))
     );
-    assert_eq!(eval_empty_with_context("empty", &context), Ok(EMPTY_VALUE));
-    assert_eq!(eval_boolean_with_context("boolean", &context), Ok(false));
 
     assert_eq!(
-        eval_empty_with_context_mut("b = a = 5", &mut context),
-        Ok(EMPTY_VALUE)
+        eval_with_context("map(\"double\", (1, \"a\", 2.5))", &context),
+        Err(EvalexprError::ElementError {
+            index: 1,
+            error: Box::new(EvalexprError::expected_int(Value::from("a")))
+        })
     );
-    assert_eq!(eval_empty_with_context("b", &context), Ok(EMPTY_VALUE));
-}
-
-#[test]
-fn test_expression_chaining() {
-    let mut context = HashMapContext::new();
     assert_eq!(
-        eval_int_with_context_mut("a = 5; a = a + 2; a", &mut context),
-        Ok(7)
+        eval_with_context("map(\"triple\", (1, 2))", &context),
+        Err(EvalexprError::ElementError {
+            index: 0,
+            error: Box::new(EvalexprError::FunctionIdentifierNotFound(
+                "triple".to_string()
+            ))
+        })
     );
-}
-
-#[test]
-fn test_strings() {
-    let mut context = HashMapContext::new();
-    assert_eq!(eval("\"string\""), Ok(Value::from("string")));
     assert_eq!(
-        eval_with_context_mut("a = \"a string\"", &mut context),
-        Ok(Value::Empty)
+        eval_with_context("map(double, (1, 2))", &context),
+        Err(EvalexprError::VariableIdentifierNotFound(
+            "double".to_string()
+        ))
     );
     assert_eq!(
-        eval_boolean_with_context("a == \"a string\"", &context),
-        Ok(true)
+        eval_with_context("map(\"double\", 1)", &context),
+        Err(EvalexprError::expected_tuple(Value::Int(1)))
+    );
+    assert_eq!(
+        eval_with_context("map(1, (1, 2))", &context),
+        Err(EvalexprError::expected_string(Value::Int(1)))
+    );
+    assert_eq!(
+        EvalexprError::ElementError {
+            index: 2,
+            error: Box::new(EvalexprError::expected_int(Value::from("a")))
+        }
+        .to_string(),
+        "At element 2: Expected a Value::Int, but got String(\"a\")."
     );
-    assert_eq!(eval("\"a\" + \"b\""), Ok(Value::from("ab")));
-    assert_eq!(eval("\"a\" > \"b\""), Ok(Value::from(false)));
-    assert_eq!(eval("\"a\" < \"b\""), Ok(Value::from(true)));
-    assert_eq!(eval("\"a\" >= \"b\""), Ok(Value::from(false)));
-    assert_eq!(eval("\"a\" <= \"b\""), Ok(Value::from(true)));
-    assert_eq!(eval("\"a\" >= \"a\""), Ok(Value::from(true)));
-    assert_eq!(eval("\"a\" <= \"a\""), Ok(Value::from(true)));
-    assert_eq!(eval("\"xa\" > \"xb\""), Ok(Value::from(false)));
-    assert_eq!(eval("\"xa\" < \"xb\""), Ok(Value::from(true)));
-    assert_eq!(eval("\"{}\" != \"{}\""), Ok(Value::from(false)));
-    assert_eq!(eval("\"{}\" == \"{}\""), Ok(Value::from(true)));
-}
 
-#[test]
-fn test_string_escaping() {
+    let mut context = context.clone();
+    context.set_builtin_functions_disabled(true).unwrap();
     assert_eq!(
-        eval("\"\\\"str\\\\ing\\\"\""),
-        Ok(Value::from("\"str\\ing\""))
+        eval_with_context("map(\"double\", (1, 2))", &context),
+        Err(EvalexprError::FunctionIdentifierNotFound("map".to_string()))
     );
 }
 
 #[test]
-fn test_tuple_definitions() {
-    assert_eq!(eval_empty("()"), Ok(()));
-    assert_eq!(eval_int("(3)"), Ok(3));
+fn test_filter_builtin() {
+    let context = context_map! {
+        "is_even" => Function::new(|argument| Ok(Value::from(argument.as_int()? % 2 == 0))),
+        "double" => Function::new(|argument| Ok(Value::from(argument.as_int()? * 2))),
+    }
+    .unwrap();
     assert_eq!(
-        eval_tuple("(3, 4)"),
-        Ok(vec![Value::from(3), Value::from(4)])
+        eval_with_context("filter(\"is_even\", (1, 2, 3, 4))", &context),
+        Ok(Value::from(vec![Value::Int(2), Value::Int(4)]))
     );
     assert_eq!(
-        eval_tuple("2, (5, 6)"),
-        Ok(vec![
-            Value::from(2),
-            Value::from(vec![Value::from(5), Value::from(6)])
-        ])
+        eval_with_context("filter(\"is_even\", (1, 3))", &context),
+        Ok(Value::Tuple(vec![]))
     );
-    assert_eq!(eval_tuple("1, 2"), Ok(vec![Value::from(1), Value::from(2)]));
     assert_eq!(
-        eval_tuple("1, 2, 3, 4"),
-        Ok(vec![
-            Value::from(1),
-            Value::from(2),
-            Value::from(3),
-            Value::from(4)
-        ])
+        eval_with_context(
+            "map(\"double\", filter(\"is_even\", (1, 2, 3, 4)))",
+            &context
+        ),
+        Ok(Value::from(vec![Value::Int(4), Value::Int(8)]))
     );
     assert_eq!(
-        eval_tuple("(1, 2, 3), 5, 6, (true, false, 0)"),
-        Ok(vec![
-            Value::from(vec![Value::from(1), Value::from(2), Value::from(3)]),
-            Value::from(5),
-            Value::from(6),
-            Value::from(vec![Value::from(true), Value::from(false), Value::from(0)])
-        ])
+        eval_with_context("filter(\"math::is_nan\", (1.0, 0.0 / 0.0, 2))", &context)
+            .map(|tuple| tuple.as_tuple().unwrap().len()),
+        Ok(1)
     );
+
     assert_eq!(
-        eval_tuple("1, (2)"),
-        Ok(vec![Value::from(1), Value::from(2)])
+        eval_with_context("filter(\"double\", (1, 2))", &context),
+        Err(EvalexprError::ElementError {
+            index: 0,
+            error: Box::new(EvalexprError::expected_boolean(Value::Int(2)))
+        })
     );
     assert_eq!(
-        eval_tuple("1, ()"),
-        Ok(vec![Value::from(1), Value::from(())])
+        eval_with_context("filter(\"is_even\", (2, true))", &context),
+        Err(EvalexprError::ElementError {
+            index: 1,
+            error: Box::new(EvalexprError::expected_int(Value::Boolean(true)))
+        })
     );
     assert_eq!(
-        eval_tuple("1, ((2))"),
-        Ok(vec![Value::from(1), Value::from(2)])
+        eval_with_context("filter(\"is_even\", 2)", &context),
+        Err(EvalexprError::expected_tuple(Value::Int(2)))
     );
 }
 
 #[test]
-fn test_implicit_context() {
+fn test_reduce_builtin() {
+    let context = context_map! {
+        "add" => Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+            Ok(Value::from(arguments[0].as_int()? + arguments[1].as_int()?))
+        }),
+        "append" => Function::new_with_arguments(|arguments| {
+            let mut accumulator = arguments[0].as_tuple()?;
+            accumulator.push(arguments[1].clone());
+            Ok(Value::from(accumulator))
+        }),
+    }
+    .unwrap();
     assert_eq!(
-        eval("a = 2 + 4 * 2; b = -5 + 3 * 5; a == b"),
-        Ok(Value::from(true))
+        eval_with_context("reduce(\"add\", 0, (1, 2, 3))", &context),
+        Ok(Value::Int(6))
     );
     assert_eq!(
-        eval_boolean("a = 2 + 4 * 2; b = -5 + 3 * 5; a == b"),
-        Ok(true)
+        eval_with_context("reduce(\"add\", 10, tuple())", &context),
+        Ok(Value::Int(10))
     );
-    assert_eq!(eval_int("a = 2 + 4 * 2; b = -5 + 3 * 5; a - b"), Ok(0));
+    // The accumulator may be a tuple, and the elements are passed unchanged.
     assert_eq!(
-        eval_float("a = 2 + 4 * 2; b = -5 + 3 * 5; a - b + 0.5"),
-        Ok(0.5)
+        eval_with_context("reduce(\"append\", tuple(), ((1, 2), 3))", &context),
+        Ok(Value::from(vec![
+            Value::from(vec![Value::Int(1), Value::Int(2)]),
+            Value::Int(3)
+        ]))
     );
-    assert_eq!(eval_number("a = 2 + 4 * 2; b = -5 + 3 * 5; a - b"), Ok(0.0));
-    assert_eq!(eval_empty("a = 2 + 4 * 2; b = -5 + 3 * 5;"), Ok(()));
     assert_eq!(
-        eval_tuple("a = 2 + 4 * 2; b = -5 + 3 * 5; a, b + 0.5"),
-        Ok(vec![Value::from(10), Value::from(10.5)])
+        eval_with_context("reduce(\"max\", 0, (3, 7, 5))", &context),
+        Ok(Value::Int(7))
+    );
+
+    assert_eq!(
+        eval_with_context("reduce(\"add\", 0, (1, \"a\", 2))", &context),
+        Err(EvalexprError::ElementError {
+            index: 1,
+            error: Box::new(EvalexprError::expected_int(Value::from("a")))
+        })
     );
     assert_eq!(
-        eval_string("a = \"xyz\"; b = \"abc\"; c = a + b; c"),
-        Ok("xyzabc".to_string())
+        eval_with_context("reduce(\"add\", 0, 1)", &context),
+        Err(EvalexprError::expected_tuple(Value::Int(1)))
+    );
+    assert_eq!(
+        eval_with_context("reduce(\"add\", (1, 2))", &context),
+        Err(EvalexprError::ExpectedFixedLenTuple {
+            expected_len: 3,
+            actual: Value::from(vec![
+                Value::from("add"),
+                Value::from(vec![Value::Int(1), Value::Int(2)])
+            ])
+        })
     );
 }
 
 #[test]
-fn test_operator_assignments() {
-    let mut context = HashMapContext::new();
-    assert_eq!(eval_empty_with_context_mut("a = 5", &mut context), Ok(()));
-    assert_eq!(eval_empty_with_context_mut("a += 5", &mut context), Ok(()));
-    assert_eq!(eval_empty_with_context_mut("a -= 5", &mut context), Ok(()));
-    assert_eq!(eval_empty_with_context_mut("a *= 5", &mut context), Ok(()));
-    assert_eq!(eval_empty_with_context_mut("b = 5.0", &mut context), Ok(()));
-    assert_eq!(eval_empty_with_context_mut("b /= 5", &mut context), Ok(()));
-    assert_eq!(eval_empty_with_context_mut("b %= 5", &mut context), Ok(()));
-    assert_eq!(eval_empty_with_context_mut("b ^= 5", &mut context), Ok(()));
+fn test_call_function_mut() {
+    /// A context whose `accumulate` function adds its arguments to a running total.
+    struct AccumulatorContext {
+        total: IntType,
+        variables: HashMapContext,
+    }
+
+    impl Context for AccumulatorContext {
+        fn get_value(&self, identifier: &str) -> Option<&Value> {
+            self.variables.get_value(identifier)
+        }
+
+        fn call_function(&self, identifier: &str, argument: &Value) -> EvalexprResult<Value> {
+            self.variables.call_function(identifier, argument)
+        }
+
+        fn are_builtin_functions_disabled(&self) -> bool {
+            self.variables.are_builtin_functions_disabled()
+        }
+
+        fn set_builtin_functions_disabled(&mut self, disabled: bool) -> EvalexprResult<()> {
+            self.variables.set_builtin_functions_disabled(disabled)
+        }
+    }
+
+    impl ContextWithMutableVariables for AccumulatorContext {
+        fn set_value(&mut self, identifier: String, value: Value) -> EvalexprResult<()> {
+            self.variables.set_value(identifier, value)
+        }
+
+        fn call_function_mut(
+            &mut self,
+            identifier: &str,
+            arguments: &[Value],
+        ) -> EvalexprResult<Value> {
+            if identifier == "accumulate" {
+                for argument in arguments {
+                    self.total += argument.as_int()?;
+                }
+                Ok(Value::from(self.total))
+            } else {
+                self.call_function_with_arguments(identifier, arguments)
+            }
+        }
+    }
+
+    let mut context = AccumulatorContext {
+        total: 0,
+        variables: context_map! {
+            "double" => Function::new(|argument| Ok(Value::from(argument.as_int()? * 2))),
+        }
+        .unwrap(),
+    };
     assert_eq!(
-        eval_empty_with_context_mut("c = true", &mut context),
-        Ok(())
+        eval_with_context_mut("accumulate(1, 2); accumulate(double(3))", &mut context),
+        Ok(Value::from(9))
     );
     assert_eq!(
-        eval_empty_with_context_mut("c &&= false", &mut context),
-        Ok(())
+        eval_with_context_mut(
+            "a = 10; accumulate(a) + accumulate(min(a, 1))",
+            &mut context
+        ),
+        Ok(Value::from(39))
     );
     assert_eq!(
-        eval_empty_with_context_mut("c ||= true", &mut context),
-        Ok(())
+        eval_with_context_mut("false ? accumulate(100) : 0", &mut context),
+        Ok(Value::from(0))
     );
-
-    let mut context = HashMapContext::new();
-    assert_eq!(eval_int_with_context_mut("a = 5; a", &mut context), Ok(5));
-    assert_eq!(eval_int_with_context_mut("a += 3; a", &mut context), Ok(8));
-    assert_eq!(eval_int_with_context_mut("a -= 5; a", &mut context), Ok(3));
-    assert_eq!(eval_int_with_context_mut("a *= 5; a", &mut context), Ok(15));
+    assert_eq!(context.total, 20);
     assert_eq!(
-        eval_float_with_context_mut("b = 5.0; b", &mut context),
-        Ok(5.0)
+        eval_with_context_mut("accumulate(\"a\")", &mut context),
+        Err(EvalexprError::expected_int(Value::from("a")))
     );
+
+    // Only evaluation with a mutable context calls `call_function_mut`.
     assert_eq!(
-        eval_float_with_context_mut("b /= 2; b", &mut context),
-        Ok(2.5)
+        eval_with_context("accumulate(1)", &context),
+        Err(EvalexprError::FunctionIdentifierNotFound(
+            "accumulate".to_string()
+        ))
     );
+
+    context.set_builtin_functions_disabled(true).unwrap();
     assert_eq!(
-        eval_float_with_context_mut("b %= 2; b", &mut context),
-        Ok(0.5)
+        eval_with_context_mut("min(1, 2)", &mut context),
+        Err(EvalexprError::FunctionIdentifierNotFound("min".to_string()))
     );
+}
+
+#[test]
+fn test_fn_context() {
+    use std::cell::Cell;
+
+    let lookups = Cell::new(0);
+    let mut context = FnContext::new(|identifier| {
+        lookups.set(lookups.get() + 1);
+        match identifier {
+            "a" => Some(Value::Int(2)),
+            "b" => Some(Value::from("b")),
+            _ => None,
+        }
+    });
+    assert_eq!(context.get_value("a"), None);
+    assert_eq!(context.get_owned_value("a"), Some(Value::Int(2)));
+    assert_eq!(context.get_owned_value("c"), None);
+    assert_eq!(eval_with_context("a * a + 1", &context), Ok(Value::Int(5)));
+    assert_eq!(lookups.get(), 4);
     assert_eq!(
-        eval_float_with_context_mut("b ^= 2; b", &mut context),
-        Ok(0.25)
+        eval_with_context("str::to_uppercase(b)", &context),
+        Ok(Value::from("B"))
     );
     assert_eq!(
-        eval_boolean_with_context_mut("c = true; c", &mut context),
-        Ok(true)
+        eval_with_context("c", &context),
+        Err(EvalexprError::VariableIdentifierNotFound("c".to_owned()))
     );
     assert_eq!(
-        eval_boolean_with_context_mut("c &&= false; c", &mut context),
-        Ok(false)
+        eval_with_context("f()", &context),
+        Err(EvalexprError::FunctionIdentifierNotFound("f".to_owned()))
     );
+    assert!(context.get_function("f").is_none());
+
+    let functions = context_map! {
+        "c" => 3,
+        "f" => Function::new(|argument| Ok(Value::Int(argument.as_int()? * 10))),
+    }
+    .unwrap();
+    let combined = CombinedContext::new(&context, &functions);
+    assert_eq!(eval_with_context("f(a + c)", &combined), Ok(Value::Int(50)));
+    let combined = CombinedContext::new(&functions, &context);
+    assert_eq!(eval_with_context("f(a + c)", &combined), Ok(Value::Int(50)));
+
+    assert_eq!(context.set_builtin_functions_disabled(true), Ok(()));
+    assert!(context.are_builtin_functions_disabled());
     assert_eq!(
-        eval_boolean_with_context_mut("c ||= true; c", &mut context),
-        Ok(true)
+        eval_with_context("max(a, 1)", &context),
+        Err(EvalexprError::FunctionIdentifierNotFound("max".to_owned()))
     );
 }
 
 #[test]
-fn test_type_errors_in_binary_operators() {
-    // Only addition supports incompatible types, all others work only on numbers or only on booleans.
-    // So only addition requires the more fancy error message.
+fn test_functions_disabled_context() {
+    let inner = context_map! {
+        "a" => 2,
+        "f" => Function::new(|argument| Ok(Value::Int(argument.as_int()? * 10))),
+    }
+    .unwrap();
+    let mut context = FunctionsDisabledContext::new(inner);
+    assert_eq!(eval_with_context("a * a + 1", &context), Ok(Value::Int(5)));
     assert_eq!(
-        eval("4 + \"abc\""),
-        Err(EvalexprError::wrong_type_combination(
-            Operator::Add,
-            vec![ValueType::Int, ValueType::String]
-        ))
+        eval_with_context_mut("b = a + 1; b", &mut context),
+        Ok(Value::Int(3))
     );
+    assert_eq!(context.inner().get_value("b"), Some(&Value::Int(3)));
     assert_eq!(
-        eval("\"abc\" + 4"),
-        Err(EvalexprError::wrong_type_combination(
-            Operator::Add,
-            vec![ValueType::String, ValueType::Int]
-        ))
+        eval_with_context("f(a)", &context),
+        Err(EvalexprError::FunctionsDisabled("f".to_owned()))
     );
-}
-
-#[test]
-fn test_empty_context() {
-    let mut context = EmptyContext;
-    assert_eq!(context.get_value("abc"), None);
     assert_eq!(
-        context.call_function("abc", &Value::Empty),
-        Err(EvalexprError::FunctionIdentifierNotFound("abc".to_owned()))
+        eval_with_context_mut("f(a)", &mut context),
+        Err(EvalexprError::FunctionsDisabled("f".to_owned()))
     );
     assert_eq!(
-        eval_with_context("max(1,3)", &context),
-        Err(EvalexprError::FunctionIdentifierNotFound(String::from(
-            "max"
-        )))
+        eval_with_context("min(a, 1)", &context),
+        Err(EvalexprError::FunctionsDisabled("min".to_owned()))
+    );
+    assert_eq!(
+        eval_with_context("map(\"f\", (1, 2))", &context),
+        Err(EvalexprError::FunctionsDisabled("map".to_owned()))
+    );
+    assert_eq!(
+        eval_with_context("g()", &context),
+        Err(EvalexprError::FunctionsDisabled("g".to_owned()))
+    );
+    assert!(context.get_function("f").is_none());
+    assert_eq!(
+        EvalexprError::FunctionsDisabled("f".to_owned()).to_string(),
+        "Function 'f' cannot be called, as functions are disabled in the context."
     );
+
+    assert!(context.are_builtin_functions_disabled());
     assert_eq!(context.set_builtin_functions_disabled(true), Ok(()));
     assert_eq!(
         context.set_builtin_functions_disabled(false),
         Err(EvalexprError::BuiltinFunctionsCannotBeEnabled)
-    )
-}
-
-#[test]
-fn test_empty_context_with_builtin_functions() {
-    let mut context = EmptyContextWithBuiltinFunctions;
-    assert_eq!(context.get_value("abc"), None);
-    assert_eq!(
-        context.call_function("abc", &Value::Empty),
-        Err(EvalexprError::FunctionIdentifierNotFound("abc".to_owned()))
     );
-    assert_eq!(eval_with_context("max(1,3)", &context), Ok(Value::Int(3)));
-    assert_eq!(context.set_builtin_functions_disabled(false), Ok(()));
-    assert_eq!(
-        context.set_builtin_functions_disabled(true),
-        Err(EvalexprError::BuiltinFunctionsCannotBeDisabled)
+
+    let mut names = context.iter_variable_names().collect::<Vec<_>>();
+    names.sort();
+    assert_eq!(names, vec!["a", "b"]);
+    assert_eq!(
+        eval_with_context("f(a)", &context.into_inner()),
+        Ok(Value::Int(20))
     );
 }
 
@@ -1641,6 +4129,149 @@ fn test_hashmap_context_type_safety() {
     );
 }
 
+#[test]
+fn test_case_insensitive_hashmap_context() {
+    let mut context = HashMapContext::new_case_insensitive();
+    context
+        .set_value("temperature".into(), Value::Int(20))
+        .unwrap();
+    context
+        .set_function(
+            "Double".into(),
+            Function::new(|argument| Ok(Value::Int(argument.as_int()? * 2))),
+        )
+        .unwrap();
+
+    assert_eq!(
+        eval_with_context("TEMPERATURE + 1", &context),
+        Ok(Value::Int(21))
+    );
+    assert_eq!(
+        eval_with_context("double(Temperature)", &context),
+        Ok(Value::Int(40))
+    );
+    assert_eq!(context.get_value("TeMpErAtUrE"), Some(&Value::Int(20)));
+    assert!(context.get_function("DOUBLE").is_some());
+
+    // Identifiers that only differ in case refer to the same variable
+    assert_eq!(
+        eval_with_context_mut("Temperature = 25; temperature", &mut context),
+        Ok(Value::Int(25))
+    );
+    assert_eq!(
+        eval_with_context_mut("TEMPERATURE = 2.5", &mut context),
+        Err(EvalexprError::expected_int(Value::Float(2.5)))
+    );
+    assert_eq!(
+        context.iter_variables().collect::<Vec<_>>(),
+        vec![("temperature".to_string(), Value::Int(25))]
+    );
+
+    let context = context_map! { "temperature" => 20 }.unwrap();
+    assert_eq!(
+        eval_with_context("TEMPERATURE", &context),
+        Err(EvalexprError::VariableIdentifierNotFound(
+            "TEMPERATURE".to_string()
+        ))
+    );
+}
+
+#[test]
+fn test_function_with_arguments() {
+    let context = context_map! {
+        "sum" => Function::new_with_arguments(|arguments| {
+            let mut sum = 0;
+            for argument in arguments {
+                sum += argument.as_int()?;
+            }
+            Ok(Value::Int(sum))
+        }),
+        "count" => Function::new_with_arguments(|arguments| Ok(Value::Int(arguments.len() as IntType)))
+    }
+    .unwrap();
+
+    assert_eq!(eval_with_context("sum()", &context), Ok(Value::Int(0)));
+    assert_eq!(eval_with_context("sum(4)", &context), Ok(Value::Int(4)));
+    assert_eq!(eval_with_context("sum 4", &context), Ok(Value::Int(4)));
+    assert_eq!(
+        eval_with_context("sum(1, 2, 3)", &context),
+        Ok(Value::Int(6))
+    );
+    assert_eq!(
+        eval_with_context("sum(1, 2.0)", &context),
+        Err(EvalexprError::expected_int(Value::Float(2.0)))
+    );
+    assert_eq!(
+        eval_with_context("count(\"a\")", &context),
+        Ok(Value::Int(1))
+    );
+    // A tuple in its own parentheses is a single argument
+    assert_eq!(
+        eval_with_context("count((1, 2))", &context),
+        Ok(Value::Int(1))
+    );
+    assert_eq!(
+        eval_with_context("count((1, 2), 3)", &context),
+        Ok(Value::Int(2))
+    );
+    assert_eq!(eval_with_context("count(1,)", &context), Ok(Value::Int(1)));
+    assert_eq!(eval_with_context("count(())", &context), Ok(Value::Int(1)));
+    assert_eq!(
+        eval_with_context_mut("t = (1, 2, 3); count(t)", &mut context.clone()),
+        Ok(Value::Int(1))
+    );
+    assert_eq!(
+        eval_with_context_mut("t = (1, 2, 3); count(t, t)", &mut context.clone()),
+        Ok(Value::Int(2))
+    );
+    let combined = CombinedContext::new(&EmptyContext, &context);
+    assert_eq!(
+        eval_with_context("count((1, 2), 3)", &combined),
+        Ok(Value::Int(2))
+    );
+    assert_eq!(
+        eval_with_context("count((1, 2))", &combined),
+        Ok(Value::Int(1))
+    );
+
+    // Called directly with a single value, a tuple is spread
+    let count = context.get_function("count").unwrap();
+    assert_eq!(
+        count.call(&Value::from(vec![Value::Int(1), Value::Int(2)])),
+        Ok(Value::Int(2))
+    );
+    assert_eq!(count.call(&Value::Empty), Ok(Value::Int(0)));
+    assert_eq!(
+        count.call_with_arguments(&[Value::from(vec![Value::Int(1), Value::Int(2)])]),
+        Ok(Value::Int(1))
+    );
+
+    // Functions taking a single value and builtin functions receive the argument list packed into a tuple
+    let context = context_map! {
+        "id" => Function::new(|argument| Ok(argument.clone())),
+    }
+    .unwrap();
+    assert_eq!(
+        eval_with_context("id(1, 2)", &context),
+        Ok(Value::from(vec![Value::Int(1), Value::Int(2)]))
+    );
+    assert_eq!(
+        eval_with_context("id((1, 2))", &context),
+        Ok(Value::from(vec![Value::Int(1), Value::Int(2)]))
+    );
+    assert_eq!(eval_with_context("id()", &context), Ok(Value::Empty));
+    assert_eq!(
+        context
+            .get_function("id")
+            .unwrap()
+            .call_with_arguments(&[Value::Int(1), Value::Int(2)]),
+        Ok(Value::from(vec![Value::Int(1), Value::Int(2)]))
+    );
+    assert_eq!(eval("max(1, 3, 2)"), Ok(Value::Int(3)));
+    assert_eq!(eval("max((1, 3, 2))"), Ok(Value::Int(3)));
+    assert_eq!(eval("if((true, 1, 2))"), Ok(Value::Int(1)));
+}
+
 #[test]
 fn test_hashmap_context_clone_debug() {
     let mut context = HashMapContext::new();
@@ -1727,10 +4358,11 @@ fn test_error_constructors() {
     );
     assert_eq!(
         eval("&"),
-        Err(EvalexprError::UnmatchedPartialToken {
-            first: PartialToken::Ampersand,
-            second: None
-        })
+        Err(EvalexprError::WrongOperatorArgumentAmount {
+            expected: 2,
+            actual: 0
+        }
+        .at_position(0))
     );
 
     assert_eq!(expect_function_argument_amount(2, 2), Ok(()));
@@ -1768,6 +4400,268 @@ fn test_iterators() {
     let mut iter = tree.iter_function_identifiers();
     assert_eq!(iter.next(), Some("fun"));
     assert_eq!(iter.next(), None);
+
+    assert_eq!(tree.variable_identifiers(), vec!["writevar", "var"]);
+    let tree = build_operator_tree("a + b * a").unwrap();
+    assert_eq!(tree.variable_identifiers(), vec!["a", "b"]);
+    let tree = build_operator_tree("b = a; a = f(b, c); a").unwrap();
+    assert_eq!(tree.variable_identifiers(), vec!["b", "a", "c"]);
+    let tree = build_operator_tree("f(1) + 2").unwrap();
+    assert!(tree.variable_identifiers().is_empty());
+
+    let tree = build_operator_tree("f(a) + g(f(b), f) + if(x, h(1), 0)").unwrap();
+    assert_eq!(tree.function_identifiers(), vec!["f", "g", "if", "h"]);
+    let tree = build_operator_tree("a + b * a").unwrap();
+    assert!(tree.function_identifiers().is_empty());
+}
+
+#[test]
+fn test_tokenize() {
+    assert_eq!(
+        tokenize("f(x, \"a\") >= 2.5"),
+        Ok(vec![
+            (Token::Identifier("f".into()), 0),
+            (Token::LBrace, 1),
+            (Token::Identifier("x".into()), 2),
+            (Token::Comma, 3),
+            (Token::String("a".into()), 5),
+            (Token::RBrace, 8),
+            (Token::Geq, 10),
+            (Token::Float(2.5), 13),
+        ])
+    );
+    assert_eq!(tokenize(""), Ok(vec![]));
+    assert_eq!(
+        tokenize("\"\\q\""),
+        Err(EvalexprError::IllegalEscapeSequence("\\q".into()))
+    );
+
+    // Rename a variable before building the tree.
+    let tokens = tokenize("x * 2 + x").unwrap();
+    let tokens = tokens
+        .into_iter()
+        .map(|(token, position)| match token {
+            Token::Identifier(identifier) if identifier == "x" => {
+                (Token::Identifier("y".into()), position)
+            },
+            token => (token, position),
+        })
+        .collect();
+    let tree = build_operator_tree_from_tokens(tokens).unwrap();
+    assert_eq!(tree.variable_identifiers(), vec!["y"]);
+    let context = context_map! { "y" => 3 }.unwrap();
+    assert_eq!(tree.eval_with_context(&context), Ok(Value::Int(9)));
+
+    assert_eq!(
+        build_operator_tree_from_tokens(vec![]).and_then(|tree| tree.eval()),
+        Ok(Value::Empty)
+    );
+    assert_eq!(
+        build_operator_tree_from_tokens(vec![(Token::Int(1), 0), (Token::RBrace, 7)]),
+        Err(EvalexprError::AtPosition {
+            position: 7,
+            error: Box::new(EvalexprError::UnmatchedRBrace)
+        })
+    );
+}
+
+#[test]
+fn test_tokenize_collect_errors() {
+    assert_eq!(
+        tokenize_collect_errors("a + 2"),
+        (
+            vec![
+                (Token::Identifier("a".into()), 0),
+                (Token::Plus, 2),
+                (Token::Int(2), 4)
+            ],
+            vec![]
+        )
+    );
+    assert_eq!(
+        tokenize_collect_errors("1.5e + \"\\q\" + 0b12 + 1__0 + 3"),
+        (
+            vec![
+                (Token::Plus, 5),
+                (Token::Plus, 12),
+                (Token::Plus, 19),
+                (Token::Plus, 26),
+                (Token::Int(3), 28)
+            ],
+            vec![
+                EvalexprError::IllegalNumberLiteral("1.5e".into()).at_position(0),
+                EvalexprError::IllegalEscapeSequence("\\q".into()).at_position(7),
+                EvalexprError::IllegalNumberLiteral("0b12".into()).at_position(14),
+                EvalexprError::IllegalNumberLiteral("1__0".into()).at_position(21),
+            ]
+        )
+    );
+}
+
+#[test]
+fn test_node_count_and_depth() {
+    let tree = build_operator_tree("").unwrap();
+    assert_eq!((tree.node_count(), tree.depth()), (1, 1));
+    let tree = build_operator_tree("a").unwrap();
+    assert_eq!((tree.node_count(), tree.depth()), (2, 2));
+    // RootNode, Add, Const 1, Mul, Const 2, Const 3
+    let tree = build_operator_tree("1 + 2 * 3").unwrap();
+    assert_eq!((tree.node_count(), tree.depth()), (6, 4));
+    // RootNode, FunctionIdentifier, RootNode, Tuple, 2 * (RootNode, Const)
+    let tree = build_operator_tree("f(1, 2)").unwrap();
+    assert_eq!((tree.node_count(), tree.depth()), (8, 6));
+    assert_eq!(tree.node_count(), tree.iter().count() + 1);
+
+    let deep = format!("{}1{}", "(".repeat(1000), ")".repeat(1000));
+    let tree = build_operator_tree(&deep).unwrap();
+    assert_eq!((tree.node_count(), tree.depth()), (1002, 1002));
+}
+
+#[test]
+fn test_max_evaluation_depth() {
+    let nested = |depth: usize| format!("{}1{}", "(".repeat(depth), ")".repeat(depth));
+
+    // Each pair of parentheses adds a root node and a constant is nested into the outermost root node.
+    let tree = build_operator_tree(&nested(DEFAULT_MAX_EVALUATION_DEPTH - 2)).unwrap();
+    assert_eq!(tree.depth(), DEFAULT_MAX_EVALUATION_DEPTH);
+    assert_eq!(tree.eval(), Ok(Value::Int(1)));
+    let tree = build_operator_tree(&nested(DEFAULT_MAX_EVALUATION_DEPTH - 1)).unwrap();
+    assert_eq!(tree.eval(), Err(EvalexprError::RecursionLimitExceeded));
+    assert_eq!(
+        eval(&nested(DEFAULT_MAX_EVALUATION_DEPTH + 100)),
+        Err(EvalexprError::RecursionLimitExceeded)
+    );
+    assert_eq!(
+        eval_int(&format!("1{}", " + 1".repeat(2000))),
+        Err(EvalexprError::RecursionLimitExceeded)
+    );
+
+    let tree = build_operator_tree("a = (1 + 2) * 3; a").unwrap();
+    assert_eq!(tree.depth(), 8);
+    let mut context = HashMapContext::new();
+    assert_eq!(
+        tree.eval_with_context_mut_and_max_depth(&mut context, 8),
+        Ok(Value::Int(9))
+    );
+    assert_eq!(
+        tree.eval_with_context_mut_and_max_depth(&mut context, 7),
+        Err(EvalexprError::RecursionLimitExceeded)
+    );
+    assert_eq!(
+        tree.eval_with_context_mut_and_max_depth(&mut context, 0),
+        Err(EvalexprError::RecursionLimitExceeded)
+    );
+
+    let tree = build_operator_tree("true ? 1 : ((2))").unwrap();
+    assert_eq!(
+        tree.eval_with_context_and_max_depth(&context, 4),
+        Ok(Value::Int(1))
+    );
+    assert_eq!(
+        tree.eval_with_context_and_max_depth(&context, 3),
+        Err(EvalexprError::RecursionLimitExceeded)
+    );
+}
+
+#[test]
+fn test_deeply_nested_trees() {
+    // Building, evaluating and dropping these trees must not overflow the stack of the test thread.
+    let depth = 100_000;
+    let nested = format!("{}1{}", "(".repeat(depth), ")".repeat(depth));
+    assert_eq!(eval(&nested), Err(EvalexprError::RecursionLimitExceeded));
+    let tree = build_operator_tree(&nested).unwrap();
+    assert_eq!(tree.depth(), depth + 2);
+    drop(tree);
+
+    let negations = format!("{}1{}", "-(".repeat(depth), ")".repeat(depth));
+    assert_eq!(eval(&negations), Err(EvalexprError::RecursionLimitExceeded));
+    assert_eq!(eval(&format!("{}1", "-".repeat(1000))), Ok(Value::Int(1)));
+    let sum = format!("1{}", " + 1".repeat(depth));
+    assert_eq!(eval(&sum), Err(EvalexprError::RecursionLimitExceeded));
+    let chain = format!("1{}", "; 1".repeat(depth));
+    assert_eq!(eval(&chain), Ok(Value::Int(1)));
+}
+
+#[test]
+fn test_debug_tree() {
+    let tree = build_operator_tree("x = f(2, -y) * (3)").unwrap();
+    let expected = [
+        "RootNode",
+        "  Assign at 2",
+        "    VariableIdentifierWrite { identifier: \"x\" } at 0",
+        "    Mul at 13",
+        "      FunctionIdentifier { identifier: \"f\" } at 4",
+        "        RootNode",
+        "          Tuple at 7",
+        "            RootNode",
+        "              Const { value: Int(2) } at 6",
+        "            RootNode",
+        "              Neg at 9",
+        "                VariableIdentifierRead { identifier: \"y\" } at 10",
+        "      RootNode",
+        "        Const { value: Int(3) } at 16",
+        "",
+    ];
+    assert_eq!(tree.debug_tree(), expected.join("\n"));
+    assert_eq!(build_operator_tree("").unwrap().debug_tree(), "RootNode\n");
+}
+
+#[test]
+fn test_node_display() {
+    let expressions = [
+        ("1+2*3", "1 + 2 * 3"),
+        ("(1+2)*3", "(1 + 2) * 3"),
+        ("2^3^2", "2 ^ 3 ^ 2"),
+        ("a = 5; b = a * 2;", "a = 5; b = a * 2;"),
+        ("f(1, 2) + g 4 + h()", "f(1, 2) + g 4 + h()"),
+        ("-(1 + 2) - -3", "-(1 + 2) - -3"),
+        ("!true || false && x", "!true || false && x"),
+        ("\"a\\\"b\\\\c\\n\"", "\"a\\\"b\\\\c\\n\""),
+        ("1.0 + 2.5e3", "1.0 + 2500.0"),
+        ("1, (2, 3), ()", "1, (2, 3), ()"),
+        ("true ? 1 : 2", "true ? 1 : 2"),
+        ("a += 1; a", "a += 1; a"),
+        ("(1, (2, 3))[1][0]", "(1, (2, 3))[1][0]"),
+        ("-(5,6)[1 + -1]", "-(5, 6)[1 + -1]"),
+        ("(1, 2,)", "(1, 2)"),
+        ("(1,)", "(1,)"),
+        ("((1,),)", "((1,),)"),
+        ("0<=1<x", "0 <= 1 < x"),
+        ("{\"a\": 1, \"b\": (2)}", "{\"a\": 1, \"b\": (2)}"),
+        ("", ""),
+    ];
+
+    for (expression, expected) in &expressions {
+        let tree = build_operator_tree(expression).unwrap();
+        let displayed = tree.to_string();
+        assert_eq!(&displayed, expected);
+
+        let reparsed = build_operator_tree(&displayed).unwrap();
+        assert_eq!(reparsed, tree);
+        assert_eq!(reparsed.to_string(), displayed);
+        assert_eq!(
+            reparsed.eval_with_context_mut(&mut context_map! {"x" => true}.unwrap()),
+            tree.eval_with_context_mut(&mut context_map! {"x" => true}.unwrap())
+        );
+    }
+
+    // Trees with missing operands fail to evaluate, but are still displayed as equivalent expressions.
+    let malformed = [
+        ("2 * *3", "2 * * 3"),
+        ("* 3", "3 *"),
+        ("1 + * 2", "1 + 2 *"),
+        ("1 + *", "1 + *"),
+        ("2 * (*3)", "2 * (3 *)"),
+        ("*3 + 1", "3 * + 1"),
+    ];
+
+    for (expression, expected) in &malformed {
+        let tree = build_operator_tree(expression).unwrap();
+        assert!(tree.eval().is_err());
+        let displayed = tree.to_string();
+        assert_eq!(&displayed, expected);
+        assert_eq!(build_operator_tree(&displayed).unwrap(), tree);
+    }
 }
 
 #[test]
@@ -1995,23 +4889,23 @@ fn test_parenthese_combinations() {
     // These are from issue #94
     assert_eq!(
         eval("123(1*2)"),
-        Err(EvalexprError::MissingOperatorOutsideOfBrace)
+        Err(EvalexprError::MissingOperatorOutsideOfBrace.at_position(7))
     );
     assert_eq!(
         eval("1()"),
-        Err(EvalexprError::MissingOperatorOutsideOfBrace)
+        Err(EvalexprError::MissingOperatorOutsideOfBrace.at_position(2))
     );
     assert_eq!(
         eval("1()()()()"),
-        Err(EvalexprError::MissingOperatorOutsideOfBrace)
+        Err(EvalexprError::MissingOperatorOutsideOfBrace.at_position(2))
     );
     assert_eq!(
         eval("1()()()(9)()()"),
-        Err(EvalexprError::MissingOperatorOutsideOfBrace)
+        Err(EvalexprError::MissingOperatorOutsideOfBrace.at_position(2))
     );
     assert_eq!(
         eval_with_context("a+100(a*2)", &context_map! {"a" => 4}.unwrap()),
-        Err(EvalexprError::MissingOperatorOutsideOfBrace)
+        Err(EvalexprError::MissingOperatorOutsideOfBrace.at_position(9))
     );
     assert_eq!(eval_int("(((1+2)*(3+4)+(5-(6)))/((7-8)))"), Ok(-20));
     assert_eq!(eval_int("(((((5)))))"), Ok(5));
@@ -2223,6 +5117,32 @@ fn test_try_from() {
     assert_eq!(EmptyType::try_from(value.clone()), Ok(()));
 }
 
+#[test]
+fn test_try_from_with_question_mark() {
+    fn eval_as<T: TryFrom<Value, Error = EvalexprError>>(string: &str) -> EvalexprResult<T> {
+        let value = eval(string)?;
+        let result = T::try_from(value)?;
+        Ok(result)
+    }
+
+    assert_eq!(eval_as::<IntType>("1 + 2"), Ok(3));
+    assert_eq!(eval_as::<FloatType>("1.5 * 2"), Ok(3.0));
+    assert_eq!(eval_as::<bool>("1 < 2"), Ok(true));
+    assert_eq!(eval_as::<String>("\"a\" + \"b\""), Ok("ab".to_string()));
+    assert_eq!(
+        eval_as::<TupleType>("1, 2"),
+        Ok(vec![Value::Int(1), Value::Int(2)])
+    );
+    assert_eq!(
+        eval_as::<IntType>("1.5"),
+        Err(EvalexprError::expected_int(Value::Float(1.5)))
+    );
+    assert_eq!(
+        eval_as::<bool>("3"),
+        Err(EvalexprError::expected_boolean(Value::Int(3)))
+    );
+}
+
 #[test]
 fn assignment_lhs_is_identifier() {
     let tree = build_operator_tree("a = 1").unwrap();
@@ -2265,13 +5185,169 @@ fn test_variable_assignment_and_iteration() {
     assert_eq!(variables, vec!["a".to_string(), "b".to_string()],);
 }
 
+#[test]
+fn test_hashmap_context_remove_value() {
+    let mut context = HashMapContext::new();
+    eval_with_context_mut("a = 5; b = 5.0", &mut context).unwrap();
+
+    assert_eq!(context.remove_value("a"), Some(Value::Int(5)));
+    assert_eq!(context.remove_value("a"), None);
+    assert_eq!(context.remove_value("c"), None);
+    assert_eq!(
+        eval_with_context("a", &context),
+        Err(EvalexprError::VariableIdentifierNotFound("a".to_string()))
+    );
+    assert_eq!(eval_with_context("b", &context), Ok(Value::Float(5.0)));
+    // A removed variable can be assigned a value of another type
+    assert_eq!(
+        eval_with_context_mut("a = \"five\"; a", &mut context),
+        Ok(Value::from("five"))
+    );
+
+    let mut context = HashMapContext::new_case_insensitive();
+    eval_with_context_mut("Temperature = 20", &mut context).unwrap();
+    assert_eq!(context.remove_value("TEMPERATURE"), Some(Value::Int(20)));
+    assert_eq!(context.get_value("temperature"), None);
+}
+
+#[test]
+fn test_hashmap_context_builder() {
+    let context = HashMapContext::builder()
+        .with_variable("a", 5)
+        .with_variable(String::from("b"), 2.5)
+        .with_variable("s", "abc")
+        .with_variable("a", 6)
+        .with_function(
+            "inc",
+            Function::new(|argument| Ok(Value::from(argument.as_int()? + 1))),
+        )
+        .build()
+        .unwrap();
+    assert_eq!(context.get_value("a"), Some(&Value::Int(6)));
+    assert_eq!(context.get_value("b"), Some(&Value::Float(2.5)));
+    assert_eq!(
+        eval_with_context("inc(a) + len(s)", &context),
+        Ok(Value::Int(10))
+    );
+    assert!(!context.are_builtin_functions_disabled());
+
+    let context = HashMapContextBuilder::new()
+        .with_builtin_functions_disabled(true)
+        .build()
+        .unwrap();
+    assert!(context.are_builtin_functions_disabled());
+    assert_eq!(
+        eval_with_context("max(1, 2)", &context),
+        Err(EvalexprError::FunctionIdentifierNotFound("max".to_string()))
+    );
+
+    // The first error is reported, and later steps are ignored.
+    assert_eq!(
+        HashMapContext::builder()
+            .with_variable("a", 5)
+            .with_variable("a", true)
+            .with_variable("a", "five")
+            .build()
+            .unwrap_err(),
+        EvalexprError::expected_int(Value::Boolean(true))
+    );
+}
+
+#[test]
+fn test_btreemap_context() {
+    let mut context = BTreeMapContext::new();
+    eval_with_context_mut("zeta = 1; alpha = 2.5; mu = \"m\"", &mut context).unwrap();
+    context_map! {
+        (&mut context)
+        "g" => Function::new(|argument| Ok(Value::Int(argument.as_int()? * 2))),
+        "f" => Function::new(|_| Ok(Value::Int(3))),
+    }
+    .unwrap();
+
+    assert_eq!(
+        context.iter_variable_names().collect::<Vec<_>>(),
+        vec!["alpha", "mu", "zeta"]
+    );
+    assert_eq!(
+        context.iter_variables().collect::<Vec<_>>(),
+        vec![
+            ("alpha".to_string(), Value::Float(2.5)),
+            ("mu".to_string(), Value::from("m")),
+            ("zeta".to_string(), Value::Int(1)),
+        ]
+    );
+    assert_eq!(
+        context
+            .iter_functions()
+            .map(|(identifier, _)| identifier)
+            .collect::<Vec<_>>(),
+        vec!["f", "g"]
+    );
+    assert_eq!(
+        eval_with_context("g(zeta + f())", &context),
+        Ok(Value::Int(8))
+    );
+    assert!(context.get_function("f").is_some());
+
+    assert_eq!(
+        eval_with_context_mut("zeta = 1.0", &mut context),
+        Err(EvalexprError::ExpectedInt {
+            actual: Value::Float(1.0)
+        })
+    );
+    assert_eq!(context.remove_value("zeta"), Some(Value::Int(1)));
+    assert_eq!(context.remove_value("zeta"), None);
+    assert_eq!(
+        eval_with_context("zeta", &context),
+        Err(EvalexprError::VariableIdentifierNotFound(
+            "zeta".to_string()
+        ))
+    );
+
+    context.set_builtin_functions_disabled(true).unwrap();
+    assert_eq!(
+        eval_with_context("max(1, 2)", &context),
+        Err(EvalexprError::FunctionIdentifierNotFound("max".to_string()))
+    );
+
+    let mut context = BTreeMapContext::new_case_insensitive();
+    eval_with_context_mut("Temperature = 20; temperature += 1", &mut context).unwrap();
+    assert_eq!(
+        context.iter_variables().collect::<Vec<_>>(),
+        vec![("temperature".to_string(), Value::Int(21))]
+    );
+    assert_eq!(context.remove_value("TEMPERATURE"), Some(Value::Int(21)));
+}
+
+#[test]
+fn test_function_iteration() {
+    let mut context = context_map! {
+        "x" => 1,
+        "f" => Function::new(|argument| Ok(argument.clone())),
+        "g" => Function::new(|_| Ok(Value::Int(2))),
+    }
+    .unwrap();
+
+    let mut functions: Vec<_> = context.iter_functions().collect();
+    functions.sort_unstable_by_key(|(identifier, _)| *identifier);
+    assert_eq!(functions.len(), 2);
+    assert_eq!(functions[0].0, "f");
+    assert_eq!(functions[0].1.call(&Value::Int(3)), Ok(Value::Int(3)));
+    assert_eq!(functions[1].0, "g");
+    assert_eq!(functions[1].1.call(&Value::Empty), Ok(Value::Int(2)));
+
+    context.set_builtin_functions_disabled(true).unwrap();
+    assert_eq!(context.iter_functions().count(), 2);
+    assert_eq!(HashMapContext::new().iter_functions().count(), 0);
+}
+
 #[test]
 fn test_negative_power() {
     println!("{:?}", build_operator_tree("3^-2").unwrap());
     assert_eq!(eval("3^-2"), Ok(Value::Float(1.0 / 9.0)));
     assert_eq!(eval("3^(-2)"), Ok(Value::Float(1.0 / 9.0)));
-    assert_eq!(eval("-3^2"), Ok(Value::Float(-9.0)));
-    assert_eq!(eval("-(3)^2"), Ok(Value::Float(-9.0)));
+    assert_eq!(eval("-3^2"), Ok(Value::Int(-9)));
+    assert_eq!(eval("-(3)^2"), Ok(Value::Int(-9)));
     assert_eq!(eval("(-3)^-2"), Ok(Value::Float(1.0 / 9.0)));
     assert_eq!(eval("-(3^-2)"), Ok(Value::Float(-1.0 / 9.0)));
 }
@@ -2291,3 +5367,106 @@ fn test_builtin_functions_context() {
         )))
     );
 }
+
+#[test]
+fn test_value_as_hash_map_key() {
+    use std::collections::HashMap;
+
+    let mut cache = HashMap::new();
+    cache.insert(Value::from(1), "int");
+    cache.insert(Value::from(1.5), "float");
+    cache.insert(Value::from("1"), "string");
+    cache.insert(
+        Value::from(vec![Value::from(1), Value::from(true)]),
+        "tuple",
+    );
+    cache.insert(Value::Empty, "empty");
+    cache.insert(Value::from(0.0), "zero");
+
+    assert_eq!(cache.len(), 6);
+    assert_eq!(cache.get(&Value::from(1)), Some(&"int"));
+    assert_eq!(cache.get(&Value::from(1.0)), Some(&"int"));
+    assert_eq!(cache.get(&Value::from(1.5)), Some(&"float"));
+    assert_eq!(cache.get(&Value::from("1")), Some(&"string"));
+    assert_eq!(cache.get(&eval("(1, true)").unwrap()), Some(&"tuple"));
+    assert_eq!(
+        cache.get(&Value::from(vec![Value::from(true), Value::from(1)])),
+        None
+    );
+    assert_eq!(cache.get(&eval("()").unwrap()), Some(&"empty"));
+    assert_eq!(cache.get(&Value::from(-0.0)), Some(&"zero"));
+    assert_eq!(cache.get(&Value::from(0)), Some(&"zero"));
+    assert_eq!(cache.get(&eval("(1.0, true)").unwrap()), Some(&"tuple"));
+
+    cache.insert(Value::from(f64::NAN), "nan");
+    assert_eq!(cache.get(&Value::from(f64::NAN)), None);
+    assert_ne!(Value::from(f64::NAN), Value::from(f64::NAN));
+    assert_eq!(
+        eval("x = math::sqrt(-1.0); (x == x, x != x, x in (1, x))"),
+        Ok(Value::from(vec![
+            Value::Boolean(false),
+            Value::Boolean(true),
+            Value::Boolean(false)
+        ]))
+    );
+}
+
+#[test]
+fn test_expression_cache() {
+    let mut cache = ExpressionCache::new();
+    assert!(cache.is_empty());
+    assert_eq!(cache.capacity(), None);
+    assert_eq!(cache.eval("1 + 2"), Ok(Value::Int(3)));
+    assert_eq!(cache.eval("1 + 2"), Ok(Value::Int(3)));
+    assert_eq!(cache.len(), 1);
+    assert_eq!(
+        cache.get_or_build("1 + 2").cloned(),
+        build_operator_tree("1 + 2")
+    );
+
+    // Expressions that fail to parse are not cached.
+    assert_eq!(
+        cache.eval("(1"),
+        Err(EvalexprError::UnmatchedLBrace.at_position(0))
+    );
+    assert!(!cache.contains("(1"));
+
+    let mut context = HashMapContext::new();
+    assert_eq!(
+        cache.eval_with_context_mut("a = 2", &mut context),
+        Ok(Value::Empty)
+    );
+    assert_eq!(
+        cache.eval_with_context("a * 3", &context),
+        Ok(Value::Int(6))
+    );
+    context.set_value("a".into(), Value::Int(4)).unwrap();
+    assert_eq!(
+        cache.eval_with_context("a * 3", &context),
+        Ok(Value::Int(12))
+    );
+    assert_eq!(cache.len(), 3);
+    cache.clear();
+    assert!(cache.is_empty());
+
+    let mut cache = ExpressionCache::with_capacity(2);
+    assert_eq!(cache.capacity(), Some(2));
+    for expression in ["1", "2", "1", "3"] {
+        cache.eval(expression).unwrap();
+    }
+    assert_eq!(cache.len(), 2);
+    assert!(cache.contains("1"));
+    assert!(!cache.contains("2"));
+    assert!(cache.contains("3"));
+    cache.eval("4").unwrap();
+    assert!(!cache.contains("1"));
+    assert!(cache.contains("3"));
+    assert!(cache.contains("4"));
+
+    let mut cache = ExpressionCache::with_capacity(0);
+    assert_eq!(cache.capacity(), Some(1));
+    assert_eq!(cache.eval("5"), Ok(Value::Int(5)));
+    assert_eq!(cache.eval("6"), Ok(Value::Int(6)));
+    assert_eq!(cache.len(), 1);
+    assert!(cache.contains("6"));
+}
@@ -0,0 +1,120 @@
+#![cfg(not(tarpaulin_include))]
+#![cfg(feature = "json_support")]
+
+use evalexpr::*;
+use serde_json::json;
+use std::convert::TryFrom;
+
+#[test]
+fn test_json_context() {
+    let mut context = JsonContext::new(json!({
+        "int": 3,
+        "negative": -4,
+        "float": 2.5,
+        "big": 18446744073709551615u64,
+        "string": "abc",
+        "boolean": true,
+        "null": null,
+        "array": [1, "two", [3.0]],
+        "objects": [{ "a": 1 }],
+        "user": { "name": "Ada", "age": 36, "address": { "city": "London" } },
+        "dotted.key": 1,
+    }));
+
+    assert_eq!(eval_with_context("int", &context), Ok(Value::Int(3)));
+    assert_eq!(eval_with_context("negative", &context), Ok(Value::Int(-4)));
+    assert_eq!(eval_with_context("float", &context), Ok(Value::Float(2.5)));
+    assert_eq!(
+        eval_with_context("big", &context),
+        Ok(Value::Float(18446744073709551615u64 as FloatType))
+    );
+    assert_eq!(
+        eval_with_context("string", &context),
+        Ok(Value::from("abc"))
+    );
+    assert_eq!(
+        eval_with_context("boolean", &context),
+        Ok(Value::from(true))
+    );
+    assert_eq!(eval_with_context("null", &context), Ok(Value::Empty));
+    assert_eq!(
+        eval_with_context("array", &context),
+        Ok(Value::from(vec![
+            Value::Int(1),
+            Value::from("two"),
+            Value::from(vec![Value::Float(3.0)])
+        ]))
+    );
+    assert_eq!(
+        eval_with_context("int * float", &context),
+        Ok(Value::Float(7.5))
+    );
+
+    assert_eq!(
+        eval_with_context("user.name", &context),
+        Ok(Value::from("Ada"))
+    );
+    assert_eq!(
+        eval_with_context("user.address.city", &context),
+        Ok(Value::from("London"))
+    );
+    assert_eq!(
+        eval_with_context("user.age >= 18", &context),
+        Ok(Value::from(true))
+    );
+    assert_eq!(eval_with_context("dotted.key", &context), Ok(Value::Int(1)));
+
+    let user = MapType::try_from(eval_with_context("user", &context).unwrap()).unwrap();
+    assert_eq!(user["name"], Value::from("Ada"));
+    assert_eq!(
+        user["address"],
+        Value::Map(MapType::from([("city".to_owned(), Value::from("London"))]))
+    );
+    assert_eq!(
+        eval_with_context("objects[0][\"a\"]", &context),
+        Ok(Value::Int(1))
+    );
+    assert_eq!(
+        eval_with_context("user[\"address\"][\"city\"]", &context),
+        Ok(Value::from("London"))
+    );
+    assert_eq!(
+        eval_with_context("user.email", &context),
+        Err(EvalexprError::VariableIdentifierNotFound(
+            "user.email".to_owned()
+        ))
+    );
+    assert_eq!(
+        eval_with_context("int.value", &context),
+        Err(EvalexprError::VariableIdentifierNotFound(
+            "int.value".to_owned()
+        ))
+    );
+    assert_eq!(context.get_value("int"), None);
+    assert_eq!(context.get_owned_value("int"), Some(Value::Int(3)));
+    assert_eq!(context.json()["int"], json!(3));
+
+    assert_eq!(
+        eval_with_context("f(int)", &context),
+        Err(EvalexprError::FunctionIdentifierNotFound("f".to_owned()))
+    );
+    assert_eq!(
+        eval_with_context("max(int, negative)", &context),
+        Ok(Value::Int(3))
+    );
+    context.set_builtin_functions_disabled(true).unwrap();
+    assert_eq!(
+        eval_with_context("max(int, negative)", &context),
+        Err(EvalexprError::FunctionIdentifierNotFound("max".to_owned()))
+    );
+}
+
+#[test]
+fn test_json_context_without_object() {
+    let context = JsonContext::new(json!([1, 2]));
+    assert_eq!(
+        eval_with_context("a", &context),
+        Err(EvalexprError::VariableIdentifierNotFound("a".to_owned()))
+    );
+    assert_eq!(eval_with_context("1 + 2", &context), Ok(Value::Int(3)));
+}
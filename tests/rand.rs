@@ -1,6 +1,9 @@
 #![cfg(feature = "rand")]
 
 use evalexpr::*;
+use rand::{Rng, SeedableRng};
+use rand_pcg::Pcg32;
+use std::sync::{Arc, Mutex};
 
 fn assert_expr(expr: &str) {
     assert_eq!(eval(expr), Ok(Value::Boolean(true)))
@@ -21,3 +24,58 @@ fn test_random_errors() {
     assert!(eval("random(9)").is_err());
     assert!(eval("random(\"a\", \"b\")").is_err());
 }
+
+#[test]
+fn test_random_int() {
+    for _ in 0..100 {
+        assert_expr("x = random_int(-2, 3); -2 <= x && x <= 3");
+        assert_expr("random_int(5, 5) == 5");
+        assert_expr("typeof(random_int(0, 1)) == \"int\"");
+    }
+    assert_expr("typeof(random_int(-9223372036854775807 - 1, 9223372036854775807)) == \"int\"");
+}
+
+#[test]
+fn test_random_int_errors() {
+    assert_eq!(
+        eval("random_int(3, 2)"),
+        Err(EvalexprError::IllegalRange {
+            lower: Value::Int(3),
+            upper: Value::Int(2)
+        })
+    );
+    assert_eq!(
+        eval("random_int(1.0, 2)"),
+        Err(EvalexprError::expected_int(Value::Float(1.0)))
+    );
+    assert!(eval("random_int(1)").is_err());
+}
+
+#[test]
+fn test_seeded_random() {
+    fn seeded_context(seed: u64) -> HashMapContext {
+        let rng = Arc::new(Mutex::new(Pcg32::seed_from_u64(seed)));
+        let int_rng = rng.clone();
+        context_map! {
+            "random" => Function::new(move |argument| {
+                argument.as_empty()?;
+                Ok(Value::Float(rng.lock().unwrap().gen()))
+            }),
+            "random_int" => Function::new(move |argument| {
+                let arguments = argument.as_fixed_len_tuple(2)?;
+                let (lower, upper) = (arguments[0].as_int()?, arguments[1].as_int()?);
+                Ok(Value::Int(int_rng.lock().unwrap().gen_range(lower..=upper)))
+            }),
+        }
+        .unwrap()
+    }
+
+    let expression = "(random(), random_int(0, 1000), random())";
+    let first = eval_with_context(expression, &seeded_context(7)).unwrap();
+    let second = eval_with_context(expression, &seeded_context(7)).unwrap();
+    assert_eq!(first, second);
+    assert_ne!(
+        first,
+        eval_with_context(expression, &seeded_context(8)).unwrap()
+    );
+}
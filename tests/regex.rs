@@ -13,6 +13,14 @@ fn test_regex_functions() {
         eval("str::regex_matches(\"gazonk\", \"[ob]{3}\")"),
         Ok(Value::Boolean(false))
     );
+    assert_eq!(
+        eval("str::regex_matches(\"12345\", \"^[0-9]+$\")"),
+        Ok(Value::Boolean(true))
+    );
+    assert_eq!(
+        eval("str::regex_matches(\"123a5\", \"^[0-9]+$\")"),
+        Ok(Value::Boolean(false))
+    );
     match eval("str::regex_matches(\"foo\", \"[\")") {
         Err(EvalexprError::InvalidRegex { regex, message }) => {
             assert_eq!(regex, "[");
@@ -28,4 +36,46 @@ fn test_regex_functions() {
         eval("str::regex_replace(\"foobar\", \".*?(i+)\", \"b$1\")"),
         Ok(Value::String("foobar".to_owned()))
     );
+
+    match eval("str::regex_replace(\"foo\", \"(\", \"\")") {
+        Err(EvalexprError::InvalidRegex { regex, .. }) => assert_eq!(regex, "("),
+        v => panic!("{:?}", v),
+    };
+}
+
+#[test]
+fn test_regex_function_argument_errors() {
+    assert_eq!(
+        eval("str::regex_matches(\"foo\",)"),
+        Err(EvalexprError::ExpectedTuple {
+            actual: Value::from("foo")
+        })
+    );
+    assert_eq!(
+        eval("t = (\"foo\",); str::regex_matches(t)"),
+        Err(EvalexprError::ExpectedFixedLenTuple {
+            expected_len: 2,
+            actual: Value::from(vec![Value::from("foo")])
+        })
+    );
+    assert_eq!(
+        eval("str::regex_matches(\"foo\", \"o\", \"a\")"),
+        Err(EvalexprError::ExpectedFixedLenTuple {
+            expected_len: 2,
+            actual: Value::from(vec![Value::from("foo"), Value::from("o"), Value::from("a")])
+        })
+    );
+    assert_eq!(
+        eval("str::regex_matches(5, \"[0-9]\")"),
+        Err(EvalexprError::ExpectedString {
+            actual: Value::Int(5)
+        })
+    );
+    assert_eq!(
+        eval("str::regex_replace(\"foo\", \"o\")"),
+        Err(EvalexprError::ExpectedFixedLenTuple {
+            expected_len: 3,
+            actual: Value::from(vec![Value::from("foo"), Value::from("o")])
+        })
+    );
 }
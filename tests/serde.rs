@@ -1,7 +1,7 @@
 #![cfg(not(tarpaulin_include))]
 #![cfg(feature = "serde")]
 
-use evalexpr::{build_operator_tree, Node};
+use evalexpr::{build_operator_tree, IntType, MapType, Node, Value};
 
 #[test]
 fn test_serde() {
@@ -11,7 +11,12 @@ fn test_serde() {
         let manual_tree = build_operator_tree(string).unwrap();
         let serde_tree: Node = ron::de::from_str(&format!("\"{}\"", string)).unwrap();
         assert_eq!(manual_tree.eval(), serde_tree.eval());
+        assert_eq!(manual_tree, serde_tree);
     }
+
+    // Trees are equal regardless of the formatting of the expression.
+    let spaced_tree: Node = ron::de::from_str("\"4 + 4\"").unwrap();
+    assert_eq!(spaced_tree, build_operator_tree("4+4").unwrap());
 }
 
 #[test]
@@ -24,11 +29,10 @@ fn test_serde_errors() {
         })
     );
     assert_eq!(
-        ron::de::from_str::<Node>("\"&\""),
+        ron::de::from_str::<Node>("\"(\""),
         Err(ron::Error {
             code: ron::de::ErrorCode::Message(
-                "Found a partial token '&' that should be followed by another partial token."
-                    .to_owned()
+                "At position 0: Found an unmatched opening parenthesis '('.".to_owned()
             ),
             position: ron::de::Position { line: 0, col: 0 }
         })
@@ -41,3 +45,50 @@ fn test_serde_errors() {
         ""
     );
 }
+
+#[test]
+fn test_serde_value() {
+    let values = [
+        Value::from("abc"),
+        Value::from(3.0),
+        Value::from(-3.5),
+        Value::from(3),
+        Value::from(IntType::MIN),
+        Value::from(true),
+        Value::Tuple(vec![Value::from(1), Value::from(2.5), Value::Empty]),
+        Value::Tuple(vec![]),
+        Value::Map(MapType::from([
+            ("a".to_owned(), Value::from(1)),
+            ("b".to_owned(), Value::Tuple(vec![Value::from("c")])),
+        ])),
+        Value::Map(MapType::new()),
+        Value::Empty,
+    ];
+
+    for value in &values {
+        let json = serde_json::to_string(value).unwrap();
+        assert_eq!(&serde_json::from_str::<Value>(&json).unwrap(), value);
+    }
+
+    assert_eq!(serde_json::to_string(&Value::from(3)).unwrap(), "3");
+    assert_eq!(serde_json::to_string(&Value::from(3.0)).unwrap(), "3.0");
+    assert_eq!(
+        serde_json::to_string(&Value::from("abc")).unwrap(),
+        "\"abc\""
+    );
+    assert_eq!(serde_json::to_string(&Value::from(false)).unwrap(), "false");
+    assert_eq!(
+        serde_json::to_string(&Value::Tuple(vec![Value::from(1), Value::from("a")])).unwrap(),
+        "[1,\"a\"]"
+    );
+    assert_eq!(
+        serde_json::to_string(&Value::Map(MapType::from([(
+            "a".to_owned(),
+            Value::from(1)
+        )])))
+        .unwrap(),
+        "{\"a\":1}"
+    );
+    assert_eq!(serde_json::to_string(&Value::Empty).unwrap(), "null");
+    assert!(serde_json::from_str::<Value>("18446744073709551615").is_err());
+}